@@ -4,15 +4,58 @@ use arcis_imports::*;
 mod circuits {
     use arcis_imports::*;
 
+    /// Number of encrypted proposal counters, and so the most proposals a round can hold.
+    /// Must match `MAX_PROPOSALS` on-chain: build both crates with or without the
+    /// `proposals-20` feature.
+    #[cfg(not(feature = "proposals-20"))]
+    const MAX_PROPOSALS: usize = 10;
+    #[cfg(feature = "proposals-20")]
+    const MAX_PROPOSALS: usize = 20;
+
+    /// Fixed-point scale of the revealed vote concentration: a round where every vote
+    /// went to one proposal reveals exactly this value.
+    const CONCENTRATION_SCALE: u128 = 10_000;
+
     /// Tracks the encrypted vote counts for all proposals.
-    /// Each proposal has a unique ID and vote count.
+    /// Each proposal has a unique ID and vote count. Counters are signed so net
+    /// voting can push a proposal below zero; every other ballot type only adds.
     pub struct ProposalVotes {
-        proposal_votes: [u64; 10], // Support up to 10 proposals
+        proposal_votes: [i64; MAX_PROPOSALS], // One counter per proposal slot
     }
 
     /// Represents a single encrypted vote for a specific proposal.
     pub struct UserVote {
-        proposal_id: u8, // Which proposal is being voted for (0-9)
+        proposal_id: u8, // Which proposal is being voted for (below MAX_PROPOSALS)
+    }
+
+    /// Represents a single encrypted net vote: support for or opposition to a proposal.
+    pub struct NetVote {
+        proposal_id: u8, // Which proposal is being voted on (below MAX_PROPOSALS)
+        support: bool,   // true to upvote, false to downvote
+    }
+
+    /// Represents an encrypted approval ballot.
+    /// Each flag marks whether the voter approves the proposal with that ID.
+    pub struct Approvals {
+        approvals: [bool; MAX_PROPOSALS],
+    }
+
+    /// Represents an encrypted ranked-choice ballot.
+    /// Proposal IDs in order of preference; out-of-range IDs mark unused slots.
+    pub struct RankedVote {
+        ranking: [u8; MAX_PROPOSALS],
+    }
+
+    /// Fixed-size box of ranked ballots for one round.
+    /// Holds up to 8 ballots (must match `MAX_RANKED_BALLOTS` on-chain).
+    pub struct RankedBallots {
+        ballots: [RankedVote; 8],
+    }
+
+    /// Round winner encrypted to a designated recipient instead of being revealed.
+    pub struct SharedWinner {
+        proposal_id: u8, // Winning proposal ID (u8::MAX when nothing was tallied)
+        vote_count: i64, // Winning proposal's counter
     }
 
     /// Initializes encrypted vote counters for all proposals.
@@ -21,8 +64,8 @@ mod circuits {
     /// The counters remain encrypted and can only be updated through MPC operations.
     #[instruction]
     pub fn init_proposal_votes(mxe: Mxe) -> Enc<Mxe, ProposalVotes> {
-        let proposal_votes = ProposalVotes { 
-            proposal_votes: [0; 10] 
+        let proposal_votes = ProposalVotes {
+            proposal_votes: [0; MAX_PROPOSALS],
         };
         mxe.from_arcis(proposal_votes)
     }
@@ -33,54 +76,803 @@ mod circuits {
     /// without revealing which proposal was voted for. The updated vote statistics 
     /// remain encrypted and can only be revealed by the system authority.
     ///
+    /// A vote that would push a proposal past `max_votes_per_proposal` leaves the
+    /// counter unchanged and is reported as rejected.
+    ///
     /// # Arguments
     /// * `vote_ctxt` - The encrypted vote containing proposal ID
     /// * `proposal_votes_ctxt` - Current encrypted vote tallies for all proposals
+    /// * `max_votes_per_proposal` - Cap on any single proposal's count
+    /// * `eligible_mask` - Bitmask of the proposals that may receive votes (bit `i` set
+    ///   when proposal `i` exists and has been seconded); votes for others are ignored
     ///
     /// # Returns
-    /// Updated encrypted vote statistics with the new vote included
+    /// Updated encrypted vote statistics with the new vote included, whether the
+    /// proposal ID named an eligible proposal, and whether the vote was rejected by
+    /// the cap
     #[instruction]
     pub fn vote_for_proposal(
         vote_ctxt: Enc<Shared, UserVote>,
         proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
-    ) -> Enc<Mxe, ProposalVotes> {
+        max_votes_per_proposal: u64,
+        eligible_mask: u32,
+    ) -> (Enc<Mxe, ProposalVotes>, bool, bool) {
         let user_vote = vote_ctxt.to_arcis();
         let mut proposal_votes = proposal_votes_ctxt.to_arcis();
 
-        // Increment the counter for the specific proposal
-        let proposal_id = user_vote.proposal_id as usize;
-        if proposal_id < 10 {
-            proposal_votes.proposal_votes[proposal_id] += 1;
+        // Increment the counter for the specific proposal, unless it is at the cap
+        let mut accepted = false;
+        let mut rejected = false;
+        for i in 0..MAX_PROPOSALS {
+            let eligible = (eligible_mask >> i) & 1 == 1;
+            if eligible && user_vote.proposal_id == i as u8 {
+                accepted = true;
+                // Counters are never negative outside net voting, which does not use this circuit
+                if proposal_votes.proposal_votes[i] as u64 >= max_votes_per_proposal {
+                    rejected = true;
+                } else {
+                    proposal_votes.proposal_votes[i] += 1;
+                }
+            }
         }
 
-        proposal_votes_ctxt.owner.from_arcis(proposal_votes)
+        (
+            proposal_votes_ctxt.owner.from_arcis(proposal_votes),
+            accepted.reveal(),
+            rejected.reveal(),
+        )
+    }
+
+    /// Folds a batch of up to 4 encrypted votes into the tally in one computation.
+    ///
+    /// Each slot behaves exactly like `vote_for_proposal`, including the per-proposal
+    /// cap. Slots at or past `count` are padding and are ignored.
+    ///
+    /// # Arguments
+    /// * `vote_0`..`vote_3` - The encrypted votes
+    /// * `proposal_votes_ctxt` - Current encrypted vote tallies for all proposals
+    /// * `count` - Number of slots holding real votes
+    /// * `max_votes_per_proposal` - Cap on any single proposal's count
+    /// * `eligible_mask` - Bitmask of the proposals that may receive votes
+    ///
+    /// # Returns
+    /// Updated encrypted vote statistics, and per-slot accepted and rejected flags
+    #[instruction]
+    pub fn batch_vote(
+        vote_0: Enc<Shared, UserVote>,
+        vote_1: Enc<Shared, UserVote>,
+        vote_2: Enc<Shared, UserVote>,
+        vote_3: Enc<Shared, UserVote>,
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        count: u8,
+        max_votes_per_proposal: u64,
+        eligible_mask: u32,
+    ) -> (Enc<Mxe, ProposalVotes>, [bool; 4], [bool; 4]) {
+        let votes = [
+            vote_0.to_arcis(),
+            vote_1.to_arcis(),
+            vote_2.to_arcis(),
+            vote_3.to_arcis(),
+        ];
+        let mut proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut accepted = [false; 4];
+        let mut rejected = [false; 4];
+        for v in 0..4 {
+            if (v as u8) < count {
+                for i in 0..MAX_PROPOSALS {
+                    let eligible = (eligible_mask >> i) & 1 == 1;
+                    if eligible && votes[v].proposal_id == i as u8 {
+                        accepted[v] = true;
+                        if proposal_votes.proposal_votes[i] as u64 >= max_votes_per_proposal {
+                            rejected[v] = true;
+                        } else {
+                            proposal_votes.proposal_votes[i] += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        (
+            proposal_votes_ctxt.owner.from_arcis(proposal_votes),
+            accepted.reveal(),
+            rejected.reveal(),
+        )
+    }
+
+    /// Processes an encrypted vote that carries a plaintext voting weight.
+    ///
+    /// Works like `vote_for_proposal`, but adds `weight` instead of 1 to the chosen
+    /// proposal's counter. The weight is derived on-chain from the voter's token
+    /// balance and capped there to keep the counters from overflowing.
+    ///
+    /// # Arguments
+    /// * `vote_ctxt` - The encrypted vote containing proposal ID
+    /// * `weight` - Voting power of the voter
+    /// * `proposal_votes_ctxt` - Current encrypted vote tallies for all proposals
+    /// * `eligible_mask` - Bitmask of the proposals that may receive votes
+    ///
+    /// # Returns
+    /// Updated encrypted vote statistics with the weighted vote included, and whether
+    /// the vote was counted (false if the proposal ID named no eligible proposal)
+    #[instruction]
+    pub fn weighted_vote(
+        vote_ctxt: Enc<Shared, UserVote>,
+        weight: u64,
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        eligible_mask: u32,
+    ) -> (Enc<Mxe, ProposalVotes>, bool) {
+        let user_vote = vote_ctxt.to_arcis();
+        let mut proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut accepted = false;
+        for i in 0..MAX_PROPOSALS {
+            let eligible = (eligible_mask >> i) & 1 == 1;
+            if eligible && user_vote.proposal_id == i as u8 {
+                proposal_votes.proposal_votes[i] += weight as i64;
+                accepted = true;
+            }
+        }
+
+        (
+            proposal_votes_ctxt.owner.from_arcis(proposal_votes),
+            accepted.reveal(),
+        )
+    }
+
+    /// Processes an encrypted net vote.
+    ///
+    /// Increments the chosen proposal's counter for an upvote and decrements it for a
+    /// downvote, so the tally becomes each proposal's net score. Neither the proposal
+    /// nor the direction of the vote is revealed.
+    ///
+    /// # Arguments
+    /// * `vote_ctxt` - The encrypted net vote
+    /// * `proposal_votes_ctxt` - Current encrypted net scores for all proposals
+    /// * `eligible_mask` - Bitmask of the proposals that may receive votes
+    ///
+    /// # Returns
+    /// Updated encrypted net scores, and whether the vote was counted (false if the
+    /// proposal ID named no eligible proposal)
+    #[instruction]
+    pub fn net_vote(
+        vote_ctxt: Enc<Shared, NetVote>,
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        eligible_mask: u32,
+    ) -> (Enc<Mxe, ProposalVotes>, bool) {
+        let net_vote = vote_ctxt.to_arcis();
+        let mut proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut accepted = false;
+        for i in 0..MAX_PROPOSALS {
+            let eligible = (eligible_mask >> i) & 1 == 1;
+            if eligible && net_vote.proposal_id == i as u8 {
+                if net_vote.support {
+                    proposal_votes.proposal_votes[i] += 1;
+                } else {
+                    proposal_votes.proposal_votes[i] -= 1;
+                }
+                accepted = true;
+            }
+        }
+
+        (
+            proposal_votes_ctxt.owner.from_arcis(proposal_votes),
+            accepted.reveal(),
+        )
+    }
+
+    /// Processes an encrypted approval ballot.
+    ///
+    /// Increments the counter of every proposal the voter approved, so the tally
+    /// becomes the number of approvals each proposal received. Approvals for slots
+    /// outside `eligible_mask` are ignored so empty slots and unseconded proposals can
+    /// never win.
+    ///
+    /// # Arguments
+    /// * `approvals_ctxt` - The encrypted approval flags
+    /// * `proposal_votes_ctxt` - Current encrypted vote tallies for all proposals
+    /// * `eligible_mask` - Bitmask of the proposals that may receive votes
+    ///
+    /// # Returns
+    /// Updated encrypted vote statistics with the approvals included, and whether
+    /// at least one approval was counted
+    #[instruction]
+    pub fn approval_vote(
+        approvals_ctxt: Enc<Shared, Approvals>,
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        eligible_mask: u32,
+    ) -> (Enc<Mxe, ProposalVotes>, bool) {
+        let approvals = approvals_ctxt.to_arcis();
+        let mut proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut accepted = false;
+        for i in 0..MAX_PROPOSALS {
+            let eligible = (eligible_mask >> i) & 1 == 1;
+            if approvals.approvals[i] && eligible {
+                proposal_votes.proposal_votes[i] += 1;
+                accepted = true;
+            }
+        }
+
+        (
+            proposal_votes_ctxt.owner.from_arcis(proposal_votes),
+            accepted.reveal(),
+        )
+    }
+
+    /// Stores an encrypted ranked ballot in the round's ballot box.
+    ///
+    /// Every slot is visited so the circuit shape does not depend on `slot`.
+    ///
+    /// # Arguments
+    /// * `ballot_ctxt` - The encrypted ranked ballot
+    /// * `ballots_ctxt` - The round's encrypted ballot box
+    /// * `slot` - Index of the slot reserved for this ballot
+    ///
+    /// # Returns
+    /// The updated encrypted ballot box
+    #[instruction]
+    pub fn cast_ranked_vote(
+        ballot_ctxt: Enc<Shared, RankedVote>,
+        ballots_ctxt: Enc<Mxe, RankedBallots>,
+        slot: u8,
+    ) -> Enc<Mxe, RankedBallots> {
+        let ballot = ballot_ctxt.to_arcis();
+        let mut ballots = ballots_ctxt.to_arcis();
+
+        for b in 0..8 {
+            if (b as u8) == slot {
+                ballots.ballots[b].ranking = ballot.ranking;
+            }
+        }
+
+        ballots_ctxt.owner.from_arcis(ballots)
+    }
+
+    /// Moves an existing vote from one proposal to another.
+    ///
+    /// Decrements the counter of the previously chosen proposal and increments the
+    /// counter of the newly chosen one in a single computation, so neither choice is
+    /// revealed and the total number of votes stays the same.
+    ///
+    /// # Arguments
+    /// * `old_vote_ctxt` - The encrypted vote being replaced
+    /// * `new_vote_ctxt` - The encrypted replacement vote
+    /// * `proposal_votes_ctxt` - Current encrypted vote tallies for all proposals
+    /// * `eligible_mask` - Bitmask of the proposals that may receive votes
+    ///
+    /// # Returns
+    /// Updated encrypted vote statistics with the vote moved, and whether the new vote
+    /// was counted (false if its proposal ID named no eligible proposal; the old vote
+    /// is withdrawn either way, matching the receipt that now holds the new vote)
+    #[instruction]
+    pub fn change_vote(
+        old_vote_ctxt: Enc<Shared, UserVote>,
+        new_vote_ctxt: Enc<Shared, UserVote>,
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        eligible_mask: u32,
+    ) -> (Enc<Mxe, ProposalVotes>, bool) {
+        let old_vote = old_vote_ctxt.to_arcis();
+        let new_vote = new_vote_ctxt.to_arcis();
+        let mut proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut accepted = false;
+        for i in 0..MAX_PROPOSALS {
+            // Never underflow a counter, even if the old vote was never tallied
+            if old_vote.proposal_id == i as u8 && proposal_votes.proposal_votes[i] > 0 {
+                proposal_votes.proposal_votes[i] -= 1;
+            }
+            let eligible = (eligible_mask >> i) & 1 == 1;
+            if eligible && new_vote.proposal_id == i as u8 {
+                proposal_votes.proposal_votes[i] += 1;
+                accepted = true;
+            }
+        }
+
+        (
+            proposal_votes_ctxt.owner.from_arcis(proposal_votes),
+            accepted.reveal(),
+        )
     }
 
     /// Reveals the winning proposal by finding the one with maximum votes.
     ///
-    /// Decrypts the vote counters and determines which proposal has the most votes.
-    /// Returns both the winning proposal ID and its vote count.
+    /// Decrypts the vote counters of the proposals in the round and determines which
+    /// one has the highest score. Ties are broken deterministically in favour of the
+    /// lowest proposal ID, and the proposals sharing the top score are revealed as a
+    /// bitmask so callers know the result was arbitrary and who tied. The margin over the runner-up is revealed too, and is always 0
+    /// on a tie. With net voting every score can be negative; the least disliked
+    /// proposal still wins and that condition is revealed as a flag. If every counter
+    /// is zero there is no winner, and the proposal ID is revealed as `u8::MAX`.
     ///
     /// # Arguments
     /// * `proposal_votes_ctxt` - Encrypted vote tallies for all proposals
+    /// * `proposal_count` - Number of proposals in the round
     ///
     /// # Returns
-    /// A tuple containing (winning_proposal_id, vote_count)
+    /// A tuple containing (winning_proposal_id, score, tied_mask, margin, all_negative),
+    /// where bit `i` of `tied_mask` is set for every proposal sharing the top score
     #[instruction]
-    pub fn reveal_winning_proposal(proposal_votes_ctxt: Enc<Mxe, ProposalVotes>) -> (u8, u64) {
+    pub fn reveal_winning_proposal(
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        proposal_count: u8,
+    ) -> (u8, i64, u32, u64, bool) {
         let proposal_votes = proposal_votes_ctxt.to_arcis();
         
-        let mut max_votes = 0u64;
+        let mut max_votes = i64::MIN;
         let mut winning_proposal = 0u8;
+        let mut all_zero = true;
+        
+        // Only slots holding a proposal compete, so an empty slot's 0 can't beat
+        // a round of negative net scores
+        for (i, &votes) in proposal_votes.proposal_votes.iter().enumerate() {
+            if (i as u8) < proposal_count {
+                if votes > max_votes {
+                    max_votes = votes;
+                    winning_proposal = i as u8;
+                }
+                if votes != 0 {
+                    all_zero = false;
+                }
+            }
+        }
+        let all_negative = max_votes < 0;
+
+        // Mark every proposal that shares the maximum
+        let mut tied_mask = 0u32;
+        for (i, &votes) in proposal_votes.proposal_votes.iter().enumerate() {
+            if (i as u8) < proposal_count && votes == max_votes {
+                tied_mask |= 1u32 << i;
+            }
+        }
+
+        // Highest score among every other proposal; with no other proposal the margin
+        // is measured from zero, or is 0 when the winner is itself negative
+        let mut runner_up_votes = if all_negative { max_votes } else { 0i64 };
+        let mut has_runner_up = false;
+        for (i, &votes) in proposal_votes.proposal_votes.iter().enumerate() {
+            if (i as u8) < proposal_count && i as u8 != winning_proposal {
+                if !has_runner_up || votes > runner_up_votes {
+                    runner_up_votes = votes;
+                    has_runner_up = true;
+                }
+            }
+        }
+        let margin = (max_votes - runner_up_votes) as u64;
+
+        // Sentinel for "no winner" when nothing was tallied
+        if all_zero {
+            winning_proposal = u8::MAX;
+            max_votes = 0;
+            tied_mask = 0;
+        }
         
+        (winning_proposal, max_votes, tied_mask, margin, all_negative).reveal()
+    }
+
+    /// Finds the winning proposal and encrypts it to a recipient instead of revealing it.
+    ///
+    /// Picks the winner exactly like `reveal_winning_proposal` (first proposal with the
+    /// highest counter among the first `proposal_count` slots), but only the holder of
+    /// the recipient's key can decrypt the result, e.g. an oversight committee.
+    ///
+    /// # Arguments
+    /// * `proposal_votes_ctxt` - Encrypted vote tallies for all proposals
+    /// * `proposal_count` - Number of slots holding a proposal this round
+    /// * `recipient` - Key and nonce the result is encrypted to
+    ///
+    /// # Returns
+    /// The winner and its count, encrypted to `recipient`
+    #[instruction]
+    pub fn reveal_winner_to_recipient(
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        proposal_count: u8,
+        recipient: Shared,
+    ) -> Enc<Shared, SharedWinner> {
+        let proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut max_votes = i64::MIN;
+        let mut winning_proposal = 0u8;
+        let mut all_zero = true;
+        for (i, &votes) in proposal_votes.proposal_votes.iter().enumerate() {
+            if (i as u8) < proposal_count {
+                if votes > max_votes {
+                    max_votes = votes;
+                    winning_proposal = i as u8;
+                }
+                if votes != 0 {
+                    all_zero = false;
+                }
+            }
+        }
+
+        // Same "no winner" sentinel as the public reveal
+        if all_zero {
+            winning_proposal = u8::MAX;
+            max_votes = 0;
+        }
+
+        recipient.from_arcis(SharedWinner {
+            proposal_id: winning_proposal,
+            vote_count: max_votes,
+        })
+    }
+
+    /// Reveals the leading proposal among those in a single category.
+    ///
+    /// Tallies stay encrypted, so the category filter is a plaintext bitmask computed
+    /// on-chain from the proposals' categories: bit `i` set means counter `i` takes
+    /// part. Counters outside the mask are ignored. The category is echoed back so the
+    /// callback can report it.
+    ///
+    /// # Arguments
+    /// * `proposal_votes_ctxt` - Encrypted vote tallies for all proposals
+    /// * `category_mask` - Bitmask of the proposals in the category
+    /// * `category` - The category being revealed
+    ///
+    /// # Returns
+    /// A tuple containing (winning_proposal_id, vote_count, is_tie, category)
+    #[instruction]
+    pub fn reveal_category_winner(
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        category_mask: u32,
+        category: u8,
+    ) -> (u8, i64, bool, u8) {
+        let proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut max_votes = i64::MIN;
+        let mut winning_proposal = 0u8;
+
         for (i, &votes) in proposal_votes.proposal_votes.iter().enumerate() {
-            if votes > max_votes {
+            let in_category = (category_mask >> i) & 1 == 1;
+            if in_category && votes > max_votes {
                 max_votes = votes;
                 winning_proposal = i as u8;
             }
         }
-        
-        (winning_proposal, max_votes).reveal()
+
+        let mut max_count = 0u8;
+        for (i, &votes) in proposal_votes.proposal_votes.iter().enumerate() {
+            let in_category = (category_mask >> i) & 1 == 1;
+            if in_category && votes == max_votes {
+                max_count += 1;
+            }
+        }
+
+        (
+            winning_proposal.reveal(),
+            max_votes.reveal(),
+            (max_count > 1).reveal(),
+            category,
+        )
+    }
+
+    /// Reveals the winning proposal only if it reaches a supermajority.
+    ///
+    /// Works like `reveal_winning_proposal` for non-negative tallies, but the winner
+    /// must hold at least `threshold_pct` percent of all votes cast for the round's
+    /// proposals. When it doesn't, nothing about the leader is revealed: the proposal
+    /// ID is `u8::MAX`, the count, tie mask and margin are zeroed, and only the failed
+    /// threshold is reported.
+    ///
+    /// # Arguments
+    /// * `proposal_votes_ctxt` - Encrypted vote tallies for all proposals
+    /// * `proposal_count` - Number of proposals in the round
+    /// * `threshold_pct` - Share of the votes the winner needs (1-100)
+    ///
+    /// # Returns
+    /// A tuple containing (winning_proposal_id, vote_count, tied_mask, margin, threshold_met)
+    #[instruction]
+    pub fn reveal_winner_with_threshold(
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        proposal_count: u8,
+        threshold_pct: u8,
+    ) -> (u8, i64, u32, u64, bool) {
+        let proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut max_votes = 0i64;
+        let mut winning_proposal = 0u8;
+        let mut total = 0i64;
+
+        for (i, &votes) in proposal_votes.proposal_votes.iter().enumerate() {
+            if (i as u8) < proposal_count {
+                total += votes;
+                if votes > max_votes {
+                    max_votes = votes;
+                    winning_proposal = i as u8;
+                }
+            }
+        }
+
+        // Mark every proposal that shares the maximum
+        let mut tied_mask = 0u32;
+        for (i, &votes) in proposal_votes.proposal_votes.iter().enumerate() {
+            if (i as u8) < proposal_count && votes == max_votes {
+                tied_mask |= 1u32 << i;
+            }
+        }
+
+        // Highest count among every proposal except the winner
+        let mut runner_up_votes = 0i64;
+        for (i, &votes) in proposal_votes.proposal_votes.iter().enumerate() {
+            if (i as u8) < proposal_count && i as u8 != winning_proposal && votes > runner_up_votes
+            {
+                runner_up_votes = votes;
+            }
+        }
+        let mut margin = (max_votes - runner_up_votes) as u64;
+
+        let threshold_met = max_votes > 0 && max_votes * 100 >= total * threshold_pct as i64;
+
+        // "No decision" sentinel that hides the leader
+        if !threshold_met {
+            winning_proposal = u8::MAX;
+            max_votes = 0;
+            tied_mask = 0;
+            margin = 0;
+        }
+
+        (
+            winning_proposal,
+            max_votes,
+            tied_mask,
+            margin,
+            threshold_met,
+        )
+            .reveal()
+    }
+
+    /// Reveals a ranked leaderboard of the top `n` proposals.
+    ///
+    /// Sorts the decrypted counters in descending order (stable, so ties keep the
+    /// lower proposal ID first) and reveals the first `n` entries. Slots past `n`
+    /// are padded with a proposal ID of `u8::MAX` and a vote count of 0.
+    ///
+    /// # Arguments
+    /// * `proposal_votes_ctxt` - Encrypted vote tallies for all proposals
+    /// * `n` - Number of leaderboard entries to reveal (at most `MAX_PROPOSALS`)
+    ///
+    /// # Returns
+    /// A tuple containing (ranked_proposal_ids, ranked_vote_counts)
+    #[instruction]
+    pub fn reveal_top_n(
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        n: u8,
+    ) -> ([u8; MAX_PROPOSALS], [i64; MAX_PROPOSALS]) {
+        let proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut ids = [0u8; MAX_PROPOSALS];
+        for i in 0..MAX_PROPOSALS {
+            ids[i] = i as u8;
+        }
+        let mut counts = proposal_votes.proposal_votes;
+
+        // Bubble sort with a fixed number of passes so the circuit shape is data-independent
+        for _ in 0..MAX_PROPOSALS {
+            for j in 0..MAX_PROPOSALS - 1 {
+                if counts[j + 1] > counts[j] {
+                    let count = counts[j];
+                    counts[j] = counts[j + 1];
+                    counts[j + 1] = count;
+                    let id = ids[j];
+                    ids[j] = ids[j + 1];
+                    ids[j + 1] = id;
+                }
+            }
+        }
+
+        // Pad the entries past `n` with sentinels
+        for i in 0..MAX_PROPOSALS {
+            if (i as u8) >= n {
+                ids[i] = u8::MAX;
+                counts[i] = 0;
+            }
+        }
+
+        (ids, counts).reveal()
+    }
+
+    /// Reveals the total number of votes tallied across all proposals.
+    ///
+    /// Sums the decrypted counters without revealing any individual count, so the
+    /// turnout can be compared against the number of voters recorded on-chain.
+    ///
+    /// # Arguments
+    /// * `proposal_votes_ctxt` - Encrypted vote tallies for all proposals
+    ///
+    /// # Returns
+    /// The sum of all vote counters (the net total under net voting)
+    #[instruction]
+    pub fn reveal_total_votes(proposal_votes_ctxt: Enc<Mxe, ProposalVotes>) -> i64 {
+        let proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut total = 0i64;
+        for i in 0..MAX_PROPOSALS {
+            total += proposal_votes.proposal_votes[i];
+        }
+
+        total.reveal()
+    }
+
+    /// Reveals the vote count of every proposal.
+    ///
+    /// # Arguments
+    /// * `proposal_votes_ctxt` - Encrypted vote tallies for all proposals
+    ///
+    /// # Returns
+    /// The decrypted vote counters, indexed by proposal ID
+    #[instruction]
+    pub fn reveal_all_tallies(
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+    ) -> [i64; MAX_PROPOSALS] {
+        let proposal_votes = proposal_votes_ctxt.to_arcis();
+        proposal_votes.proposal_votes.reveal()
+    }
+
+    /// Reveals the vote count of a single proposal, keeping every other counter secret.
+    ///
+    /// Meant for pass/fail proposals that only need to clear a fixed number of votes,
+    /// where publishing the full leaderboard would leak more than necessary.
+    ///
+    /// # Arguments
+    /// * `proposal_votes_ctxt` - Encrypted vote tallies for all proposals
+    /// * `proposal_id` - Proposal whose counter is revealed
+    ///
+    /// # Returns
+    /// The decrypted counter of `proposal_id` (0 if it is out of range)
+    #[instruction]
+    pub fn reveal_single_proposal_count(
+        proposal_votes_ctxt: Enc<Mxe, ProposalVotes>,
+        proposal_id: u8,
+    ) -> i64 {
+        let proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut count = 0i64;
+        for i in 0..MAX_PROPOSALS {
+            if i as u8 == proposal_id {
+                count = proposal_votes.proposal_votes[i];
+            }
+        }
+
+        count.reveal()
+    }
+
+    /// Reveals how concentrated the round's votes are, without revealing any counter.
+    ///
+    /// Computes the Herfindahl index (sum of squared vote shares) scaled by
+    /// `CONCENTRATION_SCALE`: `CONCENTRATION_SCALE / n` means votes were spread evenly
+    /// over `n` proposals, `CONCENTRATION_SCALE` means one proposal took them all.
+    /// Counters pushed below zero by net voting count as zero.
+    ///
+    /// # Arguments
+    /// * `proposal_votes_ctxt` - Encrypted vote tallies for all proposals
+    ///
+    /// # Returns
+    /// The scaled index, or 0 if no votes were counted
+    #[instruction]
+    pub fn reveal_vote_concentration(proposal_votes_ctxt: Enc<Mxe, ProposalVotes>) -> u64 {
+        let proposal_votes = proposal_votes_ctxt.to_arcis();
+
+        let mut total = 0u128;
+        let mut sum_of_squares = 0u128;
+        for i in 0..MAX_PROPOSALS {
+            let votes = proposal_votes.proposal_votes[i];
+            let count = if votes > 0 { votes as u128 } else { 0u128 };
+            total += count;
+            sum_of_squares += count * count;
+        }
+
+        // Both branches are evaluated, so never divide by zero
+        let divisor = if total == 0 { 1u128 } else { total * total };
+        let concentration = if total == 0 {
+            0u64
+        } else {
+            (sum_of_squares * CONCENTRATION_SCALE / divisor) as u64
+        };
+
+        concentration.reveal()
+    }
+
+    /// Runs instant-runoff elimination over the ranked ballots and reveals the winner.
+    ///
+    /// Each elimination round counts every ballot toward its highest-ranked proposal
+    /// that is still in the running; ballots with no remaining preference are exhausted
+    /// and no longer counted. A proposal with a strict majority of the counted ballots
+    /// wins. Otherwise the proposal with the fewest votes is eliminated, ties going
+    /// against the highest proposal ID. If one proposal remains it wins outright, and
+    /// ties for the lead are broken in favour of the lowest proposal ID.
+    ///
+    /// # Arguments
+    /// * `ballots_ctxt` - The round's encrypted ballot box
+    /// * `ballot_count` - Number of ballot slots in use
+    /// * `proposal_count` - Number of proposals in the round
+    ///
+    /// # Returns
+    /// A tuple containing (winning_proposal_id, elimination_rounds)
+    #[instruction]
+    pub fn reveal_ranked_winner(
+        ballots_ctxt: Enc<Mxe, RankedBallots>,
+        ballot_count: u8,
+        proposal_count: u8,
+    ) -> (u8, u8) {
+        let ballots = ballots_ctxt.to_arcis();
+
+        // Empty proposal slots start out eliminated
+        let mut eliminated = [false; MAX_PROPOSALS];
+        for p in 0..MAX_PROPOSALS {
+            if (p as u8) >= proposal_count {
+                eliminated[p] = true;
+            }
+        }
+
+        let mut winner = 0u8;
+        let mut rounds = 0u8;
+        let mut done = false;
+
+        // At most one elimination per round, so MAX_PROPOSALS rounds always reach a result
+        for _ in 0..MAX_PROPOSALS {
+            let mut counts = [0u64; MAX_PROPOSALS];
+            let mut active = 0u64;
+            for b in 0..8 {
+                if (b as u8) < ballot_count {
+                    let mut counted = false;
+                    for k in 0..MAX_PROPOSALS {
+                        let choice = ballots.ballots[b].ranking[k];
+                        for p in 0..MAX_PROPOSALS {
+                            if !counted && choice == p as u8 && !eliminated[p] {
+                                counts[p] += 1;
+                                counted = true;
+                            }
+                        }
+                    }
+                    if counted {
+                        active += 1;
+                    }
+                }
+            }
+
+            if !done {
+                rounds += 1;
+
+                let mut leader = 0u8;
+                let mut leader_votes = 0u64;
+                let mut has_leader = false;
+                let mut remaining = 0u8;
+                for p in 0..MAX_PROPOSALS {
+                    if !eliminated[p] {
+                        remaining += 1;
+                        if !has_leader || counts[p] > leader_votes {
+                            leader = p as u8;
+                            leader_votes = counts[p];
+                            has_leader = true;
+                        }
+                    }
+                }
+
+                if leader_votes * 2 > active || remaining <= 1 {
+                    winner = leader;
+                    done = true;
+                } else {
+                    let mut loser = 0u8;
+                    let mut loser_votes = u64::MAX;
+                    for p in 0..MAX_PROPOSALS {
+                        if !eliminated[p] && counts[p] <= loser_votes {
+                            loser = p as u8;
+                            loser_votes = counts[p];
+                        }
+                    }
+                    for p in 0..MAX_PROPOSALS {
+                        if (p as u8) == loser {
+                            eliminated[p] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        (winner, rounds).reveal()
     }
 
     /// Decrypts an encrypted vote and returns the plaintext proposal ID.
@@ -100,6 +892,22 @@ mod circuits {
         user_vote.proposal_id.reveal()
     }
 
+    /// Decrypts an encrypted vote without touching any system state.
+    ///
+    /// Identical to `decrypt_vote`, but registered as its own instruction so its
+    /// on-chain callback needs no system account.
+    ///
+    /// # Arguments
+    /// * `vote_ctxt` - The encrypted vote containing proposal ID
+    ///
+    /// # Returns
+    /// The decrypted proposal ID as a plaintext value
+    #[instruction]
+    pub fn decrypt_vote_standalone(vote_ctxt: Enc<Shared, UserVote>) -> u8 {
+        let user_vote = vote_ctxt.to_arcis();
+        user_vote.proposal_id.reveal()
+    }
+
     /// Decrypts an encrypted vote and verifies if it was for the winning proposal in a given round.
     ///
     /// This function decrypts a vote and compares it against the winning proposal ID
@@ -120,4 +928,137 @@ mod circuits {
         (decrypted_proposal_id == winning_proposal_id).reveal()
     }
 
+    /// Counts how many of up to 4 encrypted votes were for their round's winner.
+    ///
+    /// Batched form of `verify_winning_vote` for reputation scoring: only the number of
+    /// correct votes is revealed, not which ones. Slots at or past `count` are padding
+    /// and are ignored.
+    ///
+    /// # Arguments
+    /// * `vote_0`..`vote_3` - The encrypted votes
+    /// * `winner_0`..`winner_3` - The winning proposal ID of each vote's round
+    /// * `count` - Number of slots holding real votes
+    ///
+    /// # Returns
+    /// The number of votes for their round's winner, and `count`
+    #[instruction]
+    pub fn verify_winning_votes_batch(
+        vote_0: Enc<Shared, UserVote>,
+        vote_1: Enc<Shared, UserVote>,
+        vote_2: Enc<Shared, UserVote>,
+        vote_3: Enc<Shared, UserVote>,
+        winner_0: u8,
+        winner_1: u8,
+        winner_2: u8,
+        winner_3: u8,
+        count: u8,
+    ) -> (u8, u8) {
+        let votes = [
+            vote_0.to_arcis(),
+            vote_1.to_arcis(),
+            vote_2.to_arcis(),
+            vote_3.to_arcis(),
+        ];
+        let winners = [winner_0, winner_1, winner_2, winner_3];
+
+        let mut correct = 0u8;
+        for v in 0..4 {
+            if (v as u8) < count && votes[v].proposal_id == winners[v] {
+                correct += 1;
+            }
+        }
+
+        (correct.reveal(), count)
+    }
+
+    /// Checks whether an encrypted vote was NOT for the winning proposal.
+    ///
+    /// The mirror of `verify_winning_vote`, letting a voter prove they dissented
+    /// without revealing which proposal they backed instead.
+    ///
+    /// # Arguments
+    /// * `vote_ctxt` - The encrypted vote containing proposal ID
+    /// * `winning_proposal_id` - The winning proposal ID for the round
+    ///
+    /// # Returns
+    /// True if the vote was for a proposal other than the winner, false otherwise
+    #[instruction]
+    pub fn verify_not_winning_vote(
+        vote_ctxt: Enc<Shared, UserVote>,
+        winning_proposal_id: u8,
+    ) -> bool {
+        let user_vote = vote_ctxt.to_arcis();
+        (user_vote.proposal_id != winning_proposal_id).reveal()
+    }
+
+    /// Checks whether an encrypted vote was for an arbitrary target proposal.
+    ///
+    /// Generalizes `verify_winning_vote` so a voter can prove they backed any proposal.
+    /// The plaintext target is echoed back so the on-chain callback can report it.
+    ///
+    /// # Arguments
+    /// * `vote_ctxt` - The encrypted vote containing proposal ID
+    /// * `target_proposal_id` - The proposal ID to compare against
+    ///
+    /// # Returns
+    /// A tuple containing (vote_matches_target, target_proposal_id)
+    #[instruction]
+    pub fn verify_vote_for_proposal(
+        vote_ctxt: Enc<Shared, UserVote>,
+        target_proposal_id: u8,
+    ) -> (bool, u8) {
+        let user_vote = vote_ctxt.to_arcis();
+        (
+            (user_vote.proposal_id == target_proposal_id).reveal(),
+            target_proposal_id,
+        )
+    }
+
+    /// Checks whether an encrypted vote was for any proposal in a set, without
+    /// revealing which one.
+    ///
+    /// The set is a plaintext bitmask like the one used by `reveal_category_winner`: bit
+    /// `i` set means proposal `i` is in the set. The mask is echoed back so the on-chain
+    /// callback can report it.
+    ///
+    /// # Arguments
+    /// * `vote_ctxt` - The encrypted vote containing proposal ID
+    /// * `mask` - Bitmask of the proposals in the set
+    ///
+    /// # Returns
+    /// A tuple containing (vote_in_set, mask)
+    #[instruction]
+    pub fn verify_vote_in_set(vote_ctxt: Enc<Shared, UserVote>, mask: u32) -> (bool, u32) {
+        let user_vote = vote_ctxt.to_arcis();
+
+        let mut in_set = false;
+        for i in 0..MAX_PROPOSALS {
+            let in_mask = (mask >> i) & 1 == 1;
+            if in_mask && user_vote.proposal_id == i as u8 {
+                in_set = true;
+            }
+        }
+
+        (in_set.reveal(), mask)
+    }
+
+    /// Checks whether an encrypted vote was for the winning proposal, for reward claims.
+    ///
+    /// Identical to `verify_winning_vote`, but registered as its own instruction so the
+    /// on-chain callback can pay out the voter's reward when the result is true.
+    ///
+    /// # Arguments
+    /// * `vote_ctxt` - The encrypted vote containing proposal ID
+    /// * `winning_proposal_id` - The winning proposal ID for the round
+    ///
+    /// # Returns
+    /// True if the vote was for the winning proposal, false otherwise
+    #[instruction]
+    pub fn claim_winning_vote_reward(
+        vote_ctxt: Enc<Shared, UserVote>,
+        winning_proposal_id: u8,
+    ) -> bool {
+        let user_vote = vote_ctxt.to_arcis();
+        (user_vote.proposal_id == winning_proposal_id).reveal()
+    }
 }
\ No newline at end of file
@@ -1,13 +1,161 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::keccak;
 use arcium_anchor::prelude::*;
 use arcium_client::idl::arcium::types::CallbackAccount;
 use anchor_lang::solana_program::rent::Rent;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 const COMP_DEF_OFFSET_INIT_PROPOSAL_VOTES: u32 = comp_def_offset("init_proposal_votes");
 const COMP_DEF_OFFSET_VOTE_FOR_PROPOSAL: u32 = comp_def_offset("vote_for_proposal");
 const COMP_DEF_OFFSET_REVEAL_WINNER: u32 = comp_def_offset("reveal_winning_proposal");
 const COMP_DEF_OFFSET_DECRYPT_VOTE: u32 = comp_def_offset("decrypt_vote");
 const COMP_DEF_OFFSET_VERIFY_WINNING_VOTE: u32 = comp_def_offset("verify_winning_vote");
+const COMP_DEF_OFFSET_VERIFY_WINNING_VOTES_BATCH: u32 =
+    comp_def_offset("verify_winning_votes_batch");
+const COMP_DEF_OFFSET_REVEAL_TOP_N: u32 = comp_def_offset("reveal_top_n");
+const COMP_DEF_OFFSET_CHANGE_VOTE: u32 = comp_def_offset("change_vote");
+const COMP_DEF_OFFSET_WEIGHTED_VOTE: u32 = comp_def_offset("weighted_vote");
+const COMP_DEF_OFFSET_CLAIM_WINNING_VOTE_REWARD: u32 = comp_def_offset("claim_winning_vote_reward");
+const COMP_DEF_OFFSET_REVEAL_TOTAL_VOTES: u32 = comp_def_offset("reveal_total_votes");
+const COMP_DEF_OFFSET_REVEAL_ALL_TALLIES: u32 = comp_def_offset("reveal_all_tallies");
+const COMP_DEF_OFFSET_APPROVAL_VOTE: u32 = comp_def_offset("approval_vote");
+const COMP_DEF_OFFSET_CAST_RANKED_VOTE: u32 = comp_def_offset("cast_ranked_vote");
+const COMP_DEF_OFFSET_REVEAL_RANKED_WINNER: u32 = comp_def_offset("reveal_ranked_winner");
+const COMP_DEF_OFFSET_VERIFY_VOTE_FOR_PROPOSAL: u32 = comp_def_offset("verify_vote_for_proposal");
+const COMP_DEF_OFFSET_BATCH_VOTE: u32 = comp_def_offset("batch_vote");
+const COMP_DEF_OFFSET_NET_VOTE: u32 = comp_def_offset("net_vote");
+const COMP_DEF_OFFSET_REVEAL_WINNER_WITH_THRESHOLD: u32 =
+    comp_def_offset("reveal_winner_with_threshold");
+const COMP_DEF_OFFSET_VERIFY_NOT_WINNING_VOTE: u32 = comp_def_offset("verify_not_winning_vote");
+const COMP_DEF_OFFSET_REVEAL_CATEGORY_WINNER: u32 = comp_def_offset("reveal_category_winner");
+const COMP_DEF_OFFSET_DECRYPT_VOTE_STANDALONE: u32 = comp_def_offset("decrypt_vote_standalone");
+const COMP_DEF_OFFSET_REVEAL_SINGLE_PROPOSAL_COUNT: u32 =
+    comp_def_offset("reveal_single_proposal_count");
+const COMP_DEF_OFFSET_REVEAL_VOTE_CONCENTRATION: u32 = comp_def_offset("reveal_vote_concentration");
+const COMP_DEF_OFFSET_REVEAL_WINNER_TO_RECIPIENT: u32 =
+    comp_def_offset("reveal_winner_to_recipient");
+const COMP_DEF_OFFSET_VERIFY_VOTE_IN_SET: u32 = comp_def_offset("verify_vote_in_set");
+
+/// Upper bound on a single voter's weight, so even `u32::MAX` voters at full
+/// weight cannot overflow a `u64` tally counter.
+const MAX_VOTE_WEIGHT: u64 = u32::MAX as u64;
+
+/// Deepest voter snapshot tree a weight proof may walk (about a million voters).
+const MAX_WEIGHT_PROOF_DEPTH: usize = 20;
+
+/// `computation_offset` value asking the program to assign the offset from the
+/// system's counter instead of trusting the client's choice.
+const AUTO_COMPUTATION_OFFSET: u64 = 0;
+
+/// Number of encrypted proposal counters in the tally, and so the most proposals a
+/// round can hold. Must match `MAX_PROPOSALS` in the encrypted-ixs crate: build both
+/// crates with or without the `proposals-20` feature.
+#[cfg(not(feature = "proposals-20"))]
+const MAX_PROPOSALS: usize = 10;
+#[cfg(feature = "proposals-20")]
+const MAX_PROPOSALS: usize = 20;
+
+/// Byte offset of `ProposalSystemAccount::proposal_votes`, the encrypted tally every
+/// tally computation reads through `Argument::Account`.
+///
+/// `proposal_votes` must stay at this offset: new fields go after it, never before.
+const PROPOSAL_VOTES_OFFSET: u32 = (8 + ProposalVotesPrefix::INIT_SPACE) as u32;
+
+/// Byte length of the encrypted tally: one 32-byte ciphertext per proposal counter.
+const PROPOSAL_VOTES_LEN: u32 = (32 * MAX_PROPOSALS) as u32;
+
+// The tally window must lie inside the account, or MPC would read past its end.
+const _: () = assert!(
+    PROPOSAL_VOTES_OFFSET as usize + PROPOSAL_VOTES_LEN as usize
+        <= 8 + ProposalSystemAccount::INIT_SPACE
+);
+
+/// The fields `ProposalSystemAccount` declares before `proposal_votes`, in the same
+/// order, so `PROPOSAL_VOTES_OFFSET` comes from the same `InitSpace` derivation that
+/// sizes the account. Only its size is used on-chain; `mxe_layout` checks it
+/// serializes like the account's head.
+#[derive(AnchorSerialize, InitSpace)]
+#[cfg_attr(not(test), allow(dead_code))]
+struct ProposalVotesPrefix {
+    bump: u8,
+    authority: Pubkey,
+    nonce: u128,
+    next_proposal_id: u8,
+}
+
+/// Byte limits for proposal text (match the `#[max_len]` bounds on `ProposalAccount`).
+const MAX_TITLE_LEN: usize = 50;
+const MAX_DESCRIPTION_LEN: usize = 200;
+const MAX_URL_LEN: usize = 200;
+
+/// Maximum number of co-submitters a proposal can list (matches `ProposalAccount::co_submitters`).
+const MAX_CO_SUBMITTERS: usize = 4;
+
+/// Maximum number of keys the voter allowlist can hold (matches `AllowlistAccount::voters`).
+const MAX_ALLOWLISTED_VOTERS: usize = 100;
+
+/// Maximum number of co-authorities that can approve reveals (matches
+/// `ProposalSystemAccount::authorities` and `RevealApprovalAccount::approvers`).
+const MAX_REVEAL_AUTHORITIES: usize = 5;
+
+/// Number of ballots the encrypted ranked ballot box holds per round (matches the
+/// `RankedBallots` circuit struct).
+const MAX_RANKED_BALLOTS: usize = 8;
+
+/// Ciphertexts in the ranked ballot box: one per preference slot per ballot.
+const RANKED_BALLOT_CIPHERTEXTS: usize = MAX_PROPOSALS * MAX_RANKED_BALLOTS;
+
+/// Byte offset of `RankedVoteAccount::encrypted_ballots`, the ballot box the ranked
+/// computations read through `Argument::Account`.
+const RANKED_BALLOTS_OFFSET: u32 = (8 + RankedBallotsPrefix::INIT_SPACE) as u32;
+
+/// The fields `RankedVoteAccount` declares before `encrypted_ballots`, in the same
+/// order, so `RANKED_BALLOTS_OFFSET` follows the account's `InitSpace` derivation the
+/// same way `PROPOSAL_VOTES_OFFSET` does.
+#[derive(AnchorSerialize, InitSpace)]
+#[cfg_attr(not(test), allow(dead_code))]
+struct RankedBallotsPrefix {
+    bump: u8,
+    round_id: u64,
+    ballot_count: u8,
+    nonce: u128,
+}
+
+/// Maximum number of votes folded into a single `batch_vote` computation (matches the
+/// `batch_vote` circuit).
+const MAX_BATCH_VOTES: usize = 4;
+
+/// Maximum number of past votes checked by a single `verify_winning_votes_batch`
+/// computation (matches the `verify_winning_votes_batch` circuit).
+const MAX_BATCH_VERIFICATIONS: usize = 4;
+
+/// Maximum number of delegations a delegate can fold into a single vote.
+const MAX_DELEGATIONS_PER_VOTE: usize = 16;
+
+/// Slots a prepared vote stays confirmable before anyone may clear it (about a minute).
+const PENDING_VOTE_EXPIRY_SLOTS: u64 = 150;
+
+/// Lamports Solana charges per transaction signature, added to reveal cost estimates.
+const BASE_SIGNATURE_FEE_LAMPORTS: u64 = 5_000;
+
+/// Seconds after a round's winner is recorded during which winning voters register
+/// for the voter reward pool; the pool is split across them once it closes.
+const REWARD_REGISTRATION_PERIOD: i64 = 3 * 24 * 60 * 60;
+
+/// Seconds after an escrow's creation before the authority may sweep leftover funds.
+const EMERGENCY_WITHDRAW_GRACE_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+/// Seconds a queued reveal may go without its callback before the authority can clear
+/// `reveal_in_progress` with `clear_stuck_reveal`.
+const REVEAL_RECOVERY_TIMEOUT: i64 = 60 * 60;
+
+/// Longest voting window a system can be initialized with, which keeps every deadline
+/// derived from it (including a round's capped extension) far from `i64` overflow.
+const MAX_VOTING_DURATION: i64 = 365 * 24 * 60 * 60;
+
+/// Number of past winners kept in the winner index (matches `WinnerIndexAccount::entries`).
+const MAX_WINNER_INDEX_ENTRIES: usize = 32;
 
 declare_id!("GnBSkvi8ZRCrtvz6huKMeZF7GrnDtHHyh73GWA2eXmuw");
 
@@ -27,30 +175,111 @@ pub mod proposal_system {
     /// All individual votes remain completely confidential throughout the voting process.
     ///
     /// # Arguments
+    /// * `system_id` - Identifier seeding this system's PDAs (lets several systems coexist)
     /// * `nonce` - Cryptographic nonce for initializing encrypted vote counters
+    /// * `refund_bps` - Share of the submission fee refunded to losing proposers (basis points)
+    /// * `voting_duration` - Length of each round's voting window (in seconds, at most
+    ///   `MAX_VOTING_DURATION`)
+    /// * `min_quorum` - Minimum number of voters required before a winner can be revealed
+    /// * `governance_mint` - Token whose balance weights votes (None = one vote per voter)
+    /// * `voter_reward_bps` - Share of each round's fees paid out to winning voters (basis points)
+    /// * `submission_duration` - Length of each round's submission window before voting
+    ///   opens (in seconds, 0 = no separate window); must end before voting does
     pub fn init_proposal_system(
         ctx: Context<InitProposalSystem>,
         computation_offset: u64,
+        system_id: u32,
         nonce: u128,
+        refund_bps: u16,
+        voting_duration: i64,
+        min_quorum: u64,
+        governance_mint: Option<Pubkey>,
+        voter_reward_bps: u16,
+        submission_duration: i64,
     ) -> Result<()> {
         msg!("Initializing proposal voting system");
 
+        require!(refund_bps <= 10_000, ErrorCode::InvalidBasisPoints);
+        require!(voter_reward_bps <= 10_000, ErrorCode::InvalidBasisPoints);
+        require!(
+            (1..=MAX_VOTING_DURATION).contains(&voting_duration),
+            ErrorCode::InvalidVotingDuration
+        );
+        // The submission deadline must fall before the voting deadline
+        require!(
+            (0..voting_duration).contains(&submission_duration),
+            ErrorCode::InvalidSubmissionWindow
+        );
+
         // Initialize the system account with the provided parameters
         ctx.accounts.system_acc.bump = ctx.bumps.system_acc;
         ctx.accounts.system_acc.authority = ctx.accounts.payer.key();
+        ctx.accounts.system_acc.pending_authority = None;
+        ctx.accounts.system_acc.paused = false;
+        ctx.accounts.system_acc.voter_reward_bps = voter_reward_bps;
+        ctx.accounts.system_acc.approval_voting = false; // Plurality voting by default
+        ctx.accounts.system_acc.net_voting = false;
+        ctx.accounts.system_acc.min_voting_power = 0; // No power quorum until configured
+        ctx.accounts.system_acc.threshold_pct = 0; // No supermajority until configured
+        ctx.accounts.system_acc.supermajority_met = None;
+        ctx.accounts.system_acc.fee_mint = None; // Fees are paid in SOL by default
+        ctx.accounts.system_acc.next_computation_offset = 1;
+        ctx.accounts.system_acc.authority_can_vote = false;
+        ctx.accounts.system_acc.authorities = Vec::new();
+        ctx.accounts.system_acc.reveal_threshold = 0; // Single-authority reveals
+        ctx.accounts.system_acc.rate_limit = RateLimitConfig::default(); // No vote cooldown
+        ctx.accounts.system_acc.min_proposals = 2; // A single proposal is no competition
+        ctx.accounts.system_acc.treasury = ctx.accounts.payer.key();
+        ctx.accounts.system_acc.winner_bps = 10_000; // Winner takes the whole escrow
+        ctx.accounts.system_acc.required_seconds = 0; // Proposals are votable immediately
+        ctx.accounts.system_acc.max_rounds = None; // Open-ended until configured
+        ctx.accounts.system_acc.finished = false;
+        ctx.accounts.system_acc.voter_snapshot_root = None; // Weighted votes wait for a snapshot
+        ctx.accounts.system_acc.reveal_in_progress = false;
+        ctx.accounts.system_acc.reveal_queued_at = 0;
+        ctx.accounts.system_acc.distribution_delay = 0; // Winner can be paid right away
+        ctx.accounts.system_acc.all_scores_negative = false;
+        ctx.accounts.system_acc.system_id = system_id;
+        ctx.accounts.system_acc.max_votes_per_proposal = None; // Uncapped until configured
         ctx.accounts.system_acc.nonce = nonce;
-        ctx.accounts.system_acc.proposal_votes = [[0; 32]; 10]; // 10 proposals max
+        ctx.accounts.system_acc.proposal_votes = [[0; 32]; MAX_PROPOSALS]; // One counter per proposal slot
+        ctx.accounts.system_acc.tally_reset_pending = true; // Until the encrypted counters land
+        ctx.accounts.system_acc.tally_reset_failed = false;
         ctx.accounts.system_acc.next_proposal_id = 0;
         ctx.accounts.system_acc.winning_proposal_id = None; // No winner yet
         ctx.accounts.system_acc.winning_vote_count = None; // No vote count yet
-        ctx.accounts.system_acc.proposal_submission_fee = 1_000_000; // 0.001 SOL fee
+        ctx.accounts.system_acc.winner_was_tie = false; // No tie yet
+        ctx.accounts.system_acc.winner_tied_mask = 0;
+        ctx.accounts.system_acc.winning_margin = None; // No margin yet
+
+        // Fees start in SOL, at the proposal account's rent floor that set_submission_fee
+        // enforces for SOL fees
+        ctx.accounts.system_acc.proposal_submission_fee =
+            Rent::get()?.minimum_balance(8 + ProposalAccount::INIT_SPACE);
+        ctx.accounts.system_acc.refund_bps = refund_bps;
+        ctx.accounts.system_acc.voting_duration = voting_duration;
+        ctx.accounts.system_acc.submission_duration = submission_duration;
+        ctx.accounts.system_acc.min_quorum = min_quorum;
+        ctx.accounts.system_acc.governance_mint = governance_mint;
+        ctx.accounts.system_acc.allowlist_enabled = false; // Anyone can vote until an allowlist is created
 
         // Initialize the round metadata account (separate from system_acc to avoid MXE issues)
         ctx.accounts.round_metadata.bump = ctx.bumps.round_metadata;
         ctx.accounts.round_metadata.current_round = 0; // Start at round 0
         ctx.accounts.round_metadata.proposals_in_current_round = 0; // Start with 0 proposals
         ctx.accounts.round_metadata.total_voters = 0; // Start with 0 voters
+        ctx.accounts.round_metadata.total_voting_power = 0;
         ctx.accounts.round_metadata.round_started = Clock::get()?.unix_timestamp; // Initialize with current timestamp
+        ctx.accounts.round_metadata.round_started_slot = Clock::get()?.slot;
+        ctx.accounts.round_metadata.round_extension = 0; // No extension granted yet
+        ctx.accounts.round_metadata.commit_deadline = None; // Direct submissions by default
+        ctx.accounts.round_metadata.reveal_deadline = None;
+        ctx.accounts.round_metadata.proposal_limit = MAX_PROPOSALS as u8; // Full encrypted tally by default
+        ctx.accounts.round_metadata.next_proposal_limit = MAX_PROPOSALS as u8;
+        ctx.accounts.round_metadata.url_hashes = Vec::new();
+        ctx.accounts.round_metadata.title_hashes = Vec::new();
+        ctx.accounts.round_metadata.revealed_tallies = None; // Nothing revealed yet
+        ctx.accounts.round_metadata.revealed_tallies_round = 0;
 
         let args = vec![Argument::PlaintextU128(nonce)];
 
@@ -78,11 +307,66 @@ pub mod proposal_system {
     ) -> Result<()> {
         let o = match output {
             ComputationOutputs::Success(InitProposalVotesOutput { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
+            _ => {
+                // Votes stay closed until retry_tally_reset queues the reset again
+                ctx.accounts.system_acc.tally_reset_failed = true;
+                return computation_aborted("init_proposal_votes", None);
+            }
         };
 
         ctx.accounts.system_acc.proposal_votes = o.ciphertexts;
         ctx.accounts.system_acc.nonce = o.nonce;
+        ctx.accounts.system_acc.tally_reset_pending = false;
+
+        Ok(())
+    }
+
+    /// Queues the encrypted tally reset again after its computation aborted.
+    ///
+    /// `init_proposal_system`, `create_round_history` and `cancel_round` encrypt a fresh
+    /// zeroed tally through MPC, and votes are refused until it lands. If the
+    /// computation aborts, the callback marks the reset failed, and only then can the
+    /// system authority retry it here, so a slow reset is never overwritten after votes
+    /// have been counted.
+    ///
+    /// # Arguments
+    /// * `computation_offset` - Unique MPC computation offset
+    /// * `new_nonce` - Nonce the zeroed tally is encrypted under; must differ from the
+    ///   current nonce
+    pub fn retry_tally_reset(
+        ctx: Context<RetryTallyReset>,
+        computation_offset: u64,
+        new_nonce: u128,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            ctx.accounts.system_acc.tally_reset_pending
+                && ctx.accounts.system_acc.tally_reset_failed,
+            ErrorCode::TallyResetNotFailed
+        );
+        require!(
+            new_nonce != ctx.accounts.system_acc.nonce,
+            ErrorCode::NonceNotRotated
+        );
+
+        ctx.accounts.system_acc.tally_reset_failed = false;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Re-encrypt zeroed counters for the next round under the new nonce
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            vec![Argument::PlaintextU128(new_nonce)],
+            None,
+            vec![InitProposalVotesCallback::callback_ix(&[CallbackAccount {
+                pubkey: ctx.accounts.system_acc.key(),
+                is_writable: true,
+            }])],
+        )?;
 
         Ok(())
     }
@@ -93,103 +377,399 @@ pub mod proposal_system {
     /// The proposal gets assigned a unique ID within the current round and can be voted on by users.
     ///
     /// # Arguments
-    /// * `title` - Short title of the proposal (max 50 chars)
-    /// * `description` - Detailed description of the proposal (max 200 chars)
-    /// * `url` - URL associated with the proposal (max 200 chars)
+    /// * `title` - Short title of the proposal (max 50 bytes, must not be empty)
+    /// * `description` - Detailed description of the proposal (max 200 bytes, must not be empty)
+    /// * `url` - URL associated with the proposal (max 200 bytes)
+    /// * `category` - Track the proposal competes in (e.g. funding vs policy)
+    /// * `content_hash` - Hash of the document behind `url`, pinned so voters can detect
+    ///   later changes to it
+    /// * `co_submitters` - Teammates who share the winner payout (at most `MAX_CO_SUBMITTERS`)
     pub fn submit_proposal(
         ctx: Context<SubmitProposal>,
         title: String,
         description: String,
         url: String,
+        category: u8,
+        content_hash: [u8; 32],
+        co_submitters: Vec<Pubkey>,
     ) -> Result<()> {
-        // Check if we can add more proposals to this round
+        // While a commit phase is configured, proposals must go through commit/reveal
         require!(
-            ctx.accounts.round_metadata.proposals_in_current_round < 10,
-            ErrorCode::MaxProposalsReached
+            ctx.accounts.round_metadata.commit_deadline.is_none(),
+            ErrorCode::CommitPhaseActive
         );
 
-        let proposal_id_in_round = ctx.accounts.round_metadata.proposals_in_current_round;
-        let current_round = ctx.accounts.round_metadata.current_round;
-        let fee = ctx.accounts.system_acc.proposal_submission_fee;
-        
-        // Check if payer has enough SOL for the fee
+        create_proposal(
+            ctx.accounts,
+            &ctx.bumps,
+            title,
+            description,
+            url,
+            category,
+            content_hash,
+            co_submitters,
+        )
+    }
+
+    /// Sets the proposal limit for upcoming rounds.
+    ///
+    /// The limit is locked in when a round's first proposal is submitted, so calling
+    /// this before then also applies to the current round. Only the system authority
+    /// can change it, and it can't exceed the `MAX_PROPOSALS` slots of the encrypted tally.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of proposals per round (1 to `MAX_PROPOSALS`)
+    pub fn set_next_round_limit(ctx: Context<SetNextRoundLimit>, limit: u8) -> Result<()> {
         require!(
-            ctx.accounts.payer.lamports() >= fee,
-            ErrorCode::InsufficientFunds
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            limit > 0 && limit as usize <= MAX_PROPOSALS,
+            ErrorCode::InvalidProposalLimit
         );
 
-        // Initialize round escrow if this is the first proposal in the round
-        // Check if escrow is uninitialized by checking if round_id is 0 (default value)
-        // Check if escrow is uninitialized by checking if it's a new account
-if ctx.accounts.round_escrow.round_id == 0 && ctx.accounts.round_escrow.total_collected == 0 {
-    // Only initialize if both round_id and total_collected are 0 (uninitialized)
-    ctx.accounts.round_escrow.bump = ctx.bumps.round_escrow;
-    ctx.accounts.round_escrow.round_id = current_round;
-    ctx.accounts.round_escrow.total_collected = 0;
-    ctx.accounts.round_escrow.total_distributed = 0;
-    ctx.accounts.round_escrow.current_balance = 0;
-    ctx.accounts.round_escrow.round_status = RoundStatus::Active;
-    ctx.accounts.round_escrow.created_at = Clock::get()?.unix_timestamp;
-}
+        ctx.accounts.round_metadata.next_proposal_limit = limit;
+
+        msg!("Proposal limit for upcoming rounds set to {}", limit);
 
-        // Validate escrow is for the correct round
+        emit!(ProposalLimitSetEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            limit,
+        });
+
+        Ok(())
+    }
+
+    /// Configures the commit phase for proposal submissions in the current round.
+    ///
+    /// With a deadline set, `submit_proposal` is disabled: submitters first publish a
+    /// hash of their proposal with `commit_proposal` before the commit deadline, then
+    /// disclose it with `reveal_proposal` between the commit and reveal deadlines.
+    /// Nobody can see a proposal's content until commits are closed, which stops
+    /// last-minute copycats, and nobody can hold a commitment back to reveal it late.
+    /// Only the authority can configure it, and only before the round's first proposal.
+    /// The setting is cleared when the round ends.
+    ///
+    /// # Arguments
+    /// * `commit_deadline` - Unix timestamp closing the commit phase, or None to disable it
+    /// * `reveal_deadline` - Unix timestamp closing the reveal phase; must be after
+    ///   `commit_deadline`, and None exactly when it is
+    pub fn set_commit_deadline(
+        ctx: Context<SetCommitDeadline>,
+        commit_deadline: Option<i64>,
+        reveal_deadline: Option<i64>,
+    ) -> Result<()> {
         require!(
-            ctx.accounts.round_escrow.round_id == current_round,
-            ErrorCode::InvalidEscrowRoundId
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            ctx.accounts.round_metadata.proposals_in_current_round == 0,
+            ErrorCode::ProposalsAlreadySubmitted
+        );
+        require!(
+            match (commit_deadline, reveal_deadline) {
+                (Some(commit), Some(reveal)) => reveal > commit,
+                (None, None) => true,
+                _ => false,
+            },
+            ErrorCode::InvalidRevealDeadline
         );
 
-        // Validate escrow is in active status
+        ctx.accounts.round_metadata.commit_deadline = commit_deadline;
+        ctx.accounts.round_metadata.reveal_deadline = reveal_deadline;
+
+        emit!(CommitDeadlineSetEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            commit_deadline,
+            reveal_deadline,
+        });
+
+        Ok(())
+    }
+
+    /// Commits to a proposal without revealing its content.
+    ///
+    /// `commitment` is
+    /// `sha256(borsh((submitter, title, description, url, category, content_hash, co_submitters, salt)))`,
+    /// where `submitter` is the committing key and `salt` is a random 32-byte value kept
+    /// secret until the reveal. Binding the submitter stops anyone from copying another
+    /// submitter's published commitment and revealing it as their own. Each submitter can hold
+    /// one commitment per round. No fee is charged until the reveal.
+    ///
+    /// # Arguments
+    /// * `commitment` - Hash of the proposal and salt
+    pub fn commit_proposal(ctx: Context<CommitProposal>, commitment: [u8; 32]) -> Result<()> {
+        require!(!ctx.accounts.system_acc.paused, ErrorCode::SystemPaused);
+
+        let commit_deadline = ctx
+            .accounts
+            .round_metadata
+            .commit_deadline
+            .ok_or(ErrorCode::CommitPhaseInactive)?;
+        let current_timestamp = Clock::get()?.unix_timestamp;
         require!(
-            ctx.accounts.round_escrow.round_status == RoundStatus::Active,
-            ErrorCode::RoundEscrowNotActive
+            current_timestamp <= commit_deadline,
+            ErrorCode::CommitPhaseClosed
         );
 
-        // REAL SOL TRANSFER: Payer → Round Escrow Account
-        let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.payer.key(),
-            &ctx.accounts.round_escrow.key(),
-            fee,
+        let commit = &mut ctx.accounts.proposal_commit;
+        commit.bump = ctx.bumps.proposal_commit;
+        commit.round_id = ctx.accounts.round_metadata.current_round;
+        commit.submitter = ctx.accounts.payer.key();
+        commit.commitment = commitment;
+        commit.committed_at = current_timestamp;
+
+        emit!(ProposalCommittedEvent {
+            round_id: commit.round_id,
+            submitter: commit.submitter,
+            commitment,
+        });
+
+        Ok(())
+    }
+
+    /// Reveals a committed proposal and submits it to the current round.
+    ///
+    /// Only possible after the commit deadline and until the reveal deadline. The
+    /// preimage, including the revealing submitter's key, must hash to the stored
+    /// commitment; the proposal is then created exactly as by `submit_proposal`,
+    /// including the submission fee, and the commit account's rent is returned.
+    ///
+    /// # Arguments
+    /// * `title` - Short title of the proposal
+    /// * `description` - Detailed description of the proposal
+    /// * `url` - URL associated with the proposal
+    /// * `category` - Track the proposal competes in
+    /// * `content_hash` - Hash of the document behind `url`
+    /// * `co_submitters` - Teammates who share the winner payout
+    /// * `salt` - Secret salt used when committing
+    pub fn reveal_proposal(
+        ctx: Context<RevealProposal>,
+        title: String,
+        description: String,
+        url: String,
+        category: u8,
+        content_hash: [u8; 32],
+        co_submitters: Vec<Pubkey>,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let commit_deadline = ctx
+            .accounts
+            .submit
+            .round_metadata
+            .commit_deadline
+            .ok_or(ErrorCode::CommitPhaseInactive)?;
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            current_timestamp > commit_deadline,
+            ErrorCode::CommitPhaseOpen
+        );
+        let reveal_deadline = ctx
+            .accounts
+            .submit
+            .round_metadata
+            .reveal_deadline
+            .ok_or(ErrorCode::CommitPhaseInactive)?;
+        require!(
+            current_timestamp <= reveal_deadline,
+            ErrorCode::RevealPhaseClosed
         );
 
-        anchor_lang::solana_program::program::invoke(
-            &transfer_instruction,
-            &[
-                ctx.accounts.payer.to_account_info(),
-                ctx.accounts.round_escrow.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
+        let preimage = (
+            ctx.accounts.submit.payer.key(),
+            &title,
+            &description,
+            &url,
+            category,
+            &content_hash,
+            &co_submitters,
+            &salt,
+        )
+            .try_to_vec()?;
+        require!(
+            hash(&preimage).to_bytes() == ctx.accounts.proposal_commit.commitment,
+            ErrorCode::CommitmentMismatch
+        );
+
+        create_proposal(
+            &mut ctx.accounts.submit,
+            &ctx.bumps.submit,
+            title,
+            description,
+            url,
+            category,
+            content_hash,
+            co_submitters,
+        )
+    }
+
+    /// Withdraws a proposal submitted by mistake and refunds its submission fee.
+    ///
+    /// Only the submitter can withdraw, only in the round the proposal was submitted, and
+    /// only before anyone has voted in that round (vote counts are encrypted, so this is
+    /// the only way to be sure the proposal has no votes). Proposal IDs double as tally
+    /// slots, so only the most recently submitted proposal can be withdrawn; its ID is
+    /// then reused by the next submission. Like submission, withdrawal is closed while
+    /// the system is paused or finished. The withdrawn proposal no longer counts towards
+    /// the submitter's `total_submitted`.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal within the current round
+    pub fn withdraw_proposal(ctx: Context<WithdrawProposal>, proposal_id: u8) -> Result<()> {
+        require!(!ctx.accounts.system_acc.paused, ErrorCode::SystemPaused);
+        require!(!ctx.accounts.system_acc.finished, ErrorCode::SystemFinished);
+
+        require!(
+            ctx.accounts.round_metadata.total_voters == 0,
+            ErrorCode::VotingAlreadyStarted
+        );
+
+        require!(
+            proposal_id + 1 == ctx.accounts.round_metadata.proposals_in_current_round,
+            ErrorCode::OnlyLatestProposalWithdrawable
+        );
+
+        let refund_amount = ctx.accounts.proposal_acc.fee_paid;
+
+        require!(
+            refund_amount <= ctx.accounts.round_escrow.fee_balance(),
+            ErrorCode::InsufficientEscrowBalance
+        );
+
+        let recipient = escrow_recipient(
+            &ctx.accounts.round_escrow,
+            &ctx.accounts.payer.to_account_info(),
+            ctx.accounts.payer_token_account.as_ref(),
+        )?;
+        pay_from_escrow(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.round_escrow,
+            &recipient,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.escrow_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            refund_amount,
         )?;
 
-        // Update escrow balance
-        ctx.accounts.round_escrow.total_collected += fee;
-        ctx.accounts.round_escrow.current_balance += fee;
+        let round_escrow = &mut ctx.accounts.round_escrow;
+        round_escrow.record_payout(refund_amount)?;
+        round_escrow.refundable_fees = round_escrow
+            .refundable_fees
+            .checked_sub(refund_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        ctx.accounts.round_metadata.url_hashes.pop();
+        ctx.accounts.round_metadata.title_hashes.pop();
+        ctx.accounts.round_metadata.proposals_in_current_round -= 1;
+        ctx.accounts.system_acc.next_proposal_id -= 1;
+
+        let submitter_stats = &mut ctx.accounts.submitter_stats;
+        submitter_stats.total_submitted = submitter_stats
+            .total_submitted
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(SubmitterStatsUpdatedEvent {
+            submitter: submitter_stats.submitter,
+            total_submitted: submitter_stats.total_submitted,
+            total_wins: submitter_stats.total_wins,
+        });
 
         msg!(
-            "Proposal submission fee collected: {} lamports ({} SOL) for round {}",
-            fee,
-            fee as f64 / 1_000_000_000.0,
-            current_round
+            "Proposal {} withdrawn from round {} - refunded {} lamports",
+            proposal_id,
+            ctx.accounts.round_metadata.current_round,
+            refund_amount
         );
-        
-        // Initialize the proposal account
-        ctx.accounts.proposal_acc.bump = ctx.bumps.proposal_acc;
-        ctx.accounts.proposal_acc.id = proposal_id_in_round;
-        ctx.accounts.proposal_acc.round_id = current_round;
+
+        emit!(ProposalWithdrawnEvent {
+            proposal_id,
+            round_id: ctx.accounts.round_metadata.current_round,
+            submitter: ctx.accounts.payer.key(),
+            refund_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Rewrites a proposal's title, description, URL, and content hash.
+    ///
+    /// Only the submitter can edit, only in the round the proposal was submitted, and only
+    /// before anyone has voted in that round, so voters always see the final text. Edits
+    /// are closed while the system is paused or finished.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal within the current round
+    /// * `title` - New title (max 50 bytes, must not be empty)
+    /// * `description` - New description (max 200 bytes, must not be empty)
+    /// * `url` - New URL (max 200 bytes)
+    /// * `content_hash` - Hash of the document behind the new URL
+    pub fn edit_proposal(
+        ctx: Context<EditProposal>,
+        proposal_id: u8,
+        title: String,
+        description: String,
+        url: String,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.system_acc.paused, ErrorCode::SystemPaused);
+        require!(!ctx.accounts.system_acc.finished, ErrorCode::SystemFinished);
+
+        require!(
+            ctx.accounts.proposal_acc.round_id == ctx.accounts.round_metadata.current_round,
+            ErrorCode::InvalidRoundId
+        );
+
+        require!(
+            ctx.accounts.round_metadata.total_voters == 0,
+            ErrorCode::VotingAlreadyStarted
+        );
+
+        validate_proposal_fields(&title, &description, &url)?;
+
+        let url_hash = hash(url.as_bytes()).to_bytes();
+        check_duplicate_url(
+            &ctx.accounts.round_metadata,
+            &url,
+            &url_hash,
+            Some(proposal_id),
+        )?;
+        if let Some(entry) = ctx
+            .accounts
+            .round_metadata
+            .url_hashes
+            .get_mut(proposal_id as usize)
+        {
+            *entry = url_hash;
+        }
+
+        let title_hash = normalized_title_hash(&title);
+        check_duplicate_title(&ctx.accounts.round_metadata, &title_hash, Some(proposal_id))?;
+        if let Some(entry) = ctx
+            .accounts
+            .round_metadata
+            .title_hashes
+            .get_mut(proposal_id as usize)
+        {
+            *entry = title_hash;
+        }
+
         ctx.accounts.proposal_acc.title = title;
         ctx.accounts.proposal_acc.description = description;
         ctx.accounts.proposal_acc.url = url;
-        ctx.accounts.proposal_acc.submitter = ctx.accounts.payer.key();
-        ctx.accounts.proposal_acc.vote_count = 0;
+        ctx.accounts.proposal_acc.content_hash = content_hash;
 
-        // Increment the round-specific proposal counter
-        ctx.accounts.round_metadata.proposals_in_current_round += 1;
-        
-        // Also increment global counter for tracking
-        ctx.accounts.system_acc.next_proposal_id += 1;
+        msg!(
+            "Proposal {} edited in round {}",
+            proposal_id,
+            ctx.accounts.round_metadata.current_round
+        );
 
-        emit!(ProposalSubmittedEvent {
-            proposal_id: proposal_id_in_round,
-            round_id: current_round,
+        emit!(ProposalEditedEvent {
+            proposal_id,
+            round_id: ctx.accounts.round_metadata.current_round,
             submitter: ctx.accounts.payer.key(),
         });
 
@@ -210,8 +790,10 @@ if ctx.accounts.round_escrow.round_id == 0 && ctx.accounts.round_escrow.total_co
     /// Note: The proposal_id_nonce is kept client-side only for privacy.
     ///
     /// # Arguments
+    /// * `computation_offset` - Unique MPC computation offset, or `AUTO_COMPUTATION_OFFSET`
+    ///   to have the program assign one from the system's counter
     /// * `proposal_id` - ID of the proposal being voted for (plaintext for validation)
-    /// * `encrypted_proposal_id` - Encrypted proposal ID for ballot secrecy (nonce kept client-side)
+    /// * `encrypted_proposal_id` - Encrypted proposal ID stored in the receipt (must equal `vote`)
     /// * `vote` - Encrypted vote containing the proposal ID
     /// * `vote_encryption_pubkey` - Voter's public key for encryption
     /// * `vote_nonce` - Cryptographic nonce for the vote encryption
@@ -226,104 +808,58 @@ if ctx.accounts.round_escrow.round_id == 0 && ctx.accounts.round_escrow.total_co
         round_id: u64,
     ) -> Result<()> {
         msg!("vote_for_proposal called with round_id: {}", round_id);
-        
-        // Manually derive the vote_receipt PDA
-        let round_id_bytes = round_id.to_le_bytes();
-        let (expected_vote_receipt_pda, vote_receipt_bump) = Pubkey::find_program_address(
-            &[b"vote_receipt", ctx.accounts.payer.key().as_ref(), &round_id_bytes],
-            &crate::ID
-        );
-        
-        // Log the PDA, program ID, and payer key
-        msg!("Vote Receipt PDA: {}", expected_vote_receipt_pda);
-
-        msg!("-------------------------------------------------------");
-        msg!("vote_for_proposal called with round_id: {}", round_id);
-        msg!("Program ID: {}", crate::ID);
-        msg!("Payer Key: {}", ctx.accounts.payer.key());
-        msg!("Vote Receipt Bump: {}", vote_receipt_bump);
 
-        msg!("-------------------------------------------------------");
-        
-        // Log the PDA received from the client
-        msg!("PDA received from client: {}", ctx.accounts.vote_receipt.key());
-        
-        // Manually verify the vote_receipt account
-        require!(
-            ctx.accounts.vote_receipt.key() == expected_vote_receipt_pda,
-            ErrorCode::InvalidAuthority
-        );
-        
-        // Check if the account is already initialized
-        require!(
-            ctx.accounts.vote_receipt.data_is_empty(),
-            ErrorCode::AccountAlreadyInitialized
-        );
-        
-        // Validate that the round_id matches the current active round
+        // An all-zero ciphertext means the client skipped the encryption step
         require!(
-            round_id == ctx.accounts.round_metadata.current_round,
-            ErrorCode::InvalidRoundId
+            encrypted_proposal_id != [0u8; 32] && vote != [0u8; 32],
+            ErrorCode::EmptyCiphertext
         );
-        
-        // For round-based proposals, we need to check if the proposal exists in the current round
-        // We'll validate this by checking if the proposal_id is less than the proposals in current round
+
+        // Weighted systems must vote through weighted_vote_for_proposal
         require!(
-            proposal_id < ctx.accounts.round_metadata.proposals_in_current_round,
-            ErrorCode::InvalidProposalId
+            ctx.accounts.system_acc.governance_mint.is_none(),
+            ErrorCode::WeightedVotingRequired
         );
 
-        // Get current timestamp for the vote receipt
-        let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp;
+        validate_vote(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            round_id,
+            proposal_id,
+        )?;
 
-        // Create the vote receipt account using system program
-        let space = 8 + VoteReceiptAccount::INIT_SPACE;
-        let rent = Rent::get()?;
-        let lamports = rent.minimum_balance(space);
-        
-        let create_account_ix = anchor_lang::solana_program::system_instruction::create_account(
+        // Votes are only accepted while the round's escrow is still open
+        check_round_escrow_open(&ctx.accounts.round_escrow, &ctx.accounts.round_metadata)?;
+
+        check_allowlist(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.allowlist,
             &ctx.accounts.payer.key(),
-            &expected_vote_receipt_pda,
-            lamports,
-            space as u64,
-            &crate::ID,
-        );
-        
-        anchor_lang::solana_program::program::invoke_signed(
-            &create_account_ix,
-            &[
-                ctx.accounts.payer.to_account_info(),
-                ctx.accounts.vote_receipt.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[&[b"vote_receipt", ctx.accounts.payer.key().as_ref(), &round_id_bytes, &[vote_receipt_bump]]],
+        )?;
+        check_authority_vote(&ctx.accounts.system_acc, &ctx.accounts.payer.key())?;
+        check_blacklist(&ctx.accounts.blacklist)?;
+
+        enforce_vote_rate_limit(
+            &ctx.accounts.system_acc,
+            &mut ctx.accounts.voter_rate,
+            ctx.bumps.voter_rate,
+            ctx.accounts.payer.key(),
+            round_id,
         )?;
 
-        // Manually initialize the vote receipt account
-        let vote_receipt_account = VoteReceiptAccount {
-            bump: vote_receipt_bump,
-            voter: ctx.accounts.payer.key(),
+        msg!("Storing nonce: {}", vote_nonce);
+
+        let current_timestamp = create_vote_receipt(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.vote_receipt.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            round_id,
             encrypted_proposal_id,
-            timestamp: current_timestamp,
+            vote,
             vote_encryption_pubkey,
-        };
-        
-        // DEBUG: Log what we're storing in the vote receipt
-        msg!("=== VOTE RECEIPT STORAGE DEBUG ===");
-        msg!("Storing encrypted_proposal_id (first 8 bytes): {:?}", &encrypted_proposal_id[0..8]);
-        msg!("Storing encrypted_proposal_id (last 8 bytes): {:?}", &encrypted_proposal_id[24..32]);
-        msg!("Storing encrypted_proposal_id (full): {:?}", &encrypted_proposal_id);
-        msg!("Storing vote_encryption_pubkey (full): {:?}", &vote_encryption_pubkey);
-        msg!("Storing nonce: {}", vote_nonce);
-        msg!("Vote receipt account voter: {}", vote_receipt_account.voter);
-        msg!("Vote receipt account timestamp: {}", vote_receipt_account.timestamp);
-        msg!("===================================");
-        
-        // Serialize and write the account data
-        let mut vote_receipt_data = ctx.accounts.vote_receipt.try_borrow_mut_data()?;
-        let serialized = vote_receipt_account.try_to_vec()?;
-        vote_receipt_data[0..serialized.len()].copy_from_slice(&serialized);
+            vote_nonce,
+        )?;
 
         // Emit event for vote receipt creation
         emit!(VoteReceiptCreatedEvent {
@@ -333,8 +869,11 @@ if ctx.accounts.round_escrow.round_id == 0 && ctx.accounts.round_escrow.total_co
             timestamp: current_timestamp,
         });
 
-        // Increment total voter count for this round
-        ctx.accounts.round_metadata.total_voters += 1;
+        record_voter(
+            &mut ctx.accounts.round_metadata,
+            ctx.accounts.system_acc.min_quorum,
+            round_id,
+        );
 
         let args = vec![
             Argument::ArcisPubkey(vote_encryption_pubkey),
@@ -343,12 +882,27 @@ if ctx.accounts.round_escrow.round_id == 0 && ctx.accounts.round_escrow.total_co
             Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
             Argument::Account(
                 ctx.accounts.system_acc.key(),
-                // Offset calculation: 8 bytes (discriminator) + 1 byte (bump) + 32 bytes (authority) + 16 bytes (nonce) + 1 byte (next_proposal_id)
-                8 + 1 + 32 + 16 + 1,
-                32 * 10, // 10 proposal vote counters, each stored as 32-byte ciphertext
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU64(
+                ctx.accounts
+                    .system_acc
+                    .max_votes_per_proposal
+                    .unwrap_or(u64::MAX),
             ),
+            Argument::PlaintextU32(seconded_mask(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+            )),
         ];
 
+        let computation_offset =
+            consume_computation_offset(&mut ctx.accounts.system_acc, computation_offset)?;
+
+        // Cleared by the callback once the tally lands; an abort marks it failed instead
+        record_tally_queued(&ctx.accounts.vote_receipt, computation_offset)?;
+
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
         queue_computation(
@@ -361,434 +915,12443 @@ if ctx.accounts.round_escrow.round_id == 0 && ctx.accounts.round_escrow.total_co
                     pubkey: ctx.accounts.system_acc.key(),
                     is_writable: true,
                 },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.vote_receipt.key(),
+                    is_writable: true,
+                },
             ])],
         )?;
         Ok(())
     }
 
-    pub fn init_decrypt_vote_comp_def(ctx: Context<InitDecryptVoteCompDef>) -> Result<()> {
-        init_comp_def(ctx.accounts, true, 0, None, None)?;
-        Ok(())
-    }
-
-
-    /// Decrypts an encrypted vote to reveal the plaintext proposal ID.
+    /// First step of a two-step vote: commits the encrypted ballot without counting it.
     ///
-    /// This function allows the system authority to decrypt individual votes
-    /// for auditing purposes or verification. The vote must have been previously
-    /// encrypted using the same encryption scheme as the voting system.
+    /// The ballot is stored in the voter's `PendingVoteAccount` so a wallet can show a
+    /// confirmation prompt with the payload already on-chain. Nothing is tallied and no
+    /// receipt exists until `confirm_vote` runs within `PENDING_VOTE_EXPIRY_SLOTS`; after
+    /// that, anyone can clear the pending vote with `clear_expired_pending_vote`.
     ///
     /// # Arguments
-    /// * `vote` - The encrypted vote containing the proposal ID
-    /// * `vote_encryption_pubkey` - The public key used to encrypt the vote
-    /// * `vote_nonce` - The nonce used for vote encryption
-    pub fn decrypt_vote(
-        ctx: Context<DecryptVote>,
-        computation_offset: u64,
+    /// * `proposal_id` - ID of the proposal being voted for (plaintext for validation)
+    /// * `encrypted_proposal_id` - Encrypted proposal ID stored in the receipt (must equal `vote`)
+    /// * `vote` - Encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - Voter's public key for encryption
+    /// * `vote_nonce` - Cryptographic nonce for the vote encryption
+    /// * `round_id` - The round being voted in
+    pub fn prepare_vote(
+        ctx: Context<PrepareVote>,
+        proposal_id: u8,
+        encrypted_proposal_id: [u8; 32],
         vote: [u8; 32],
         vote_encryption_pubkey: [u8; 32],
         vote_nonce: u128,
+        round_id: u64,
     ) -> Result<()> {
-        msg!("Decrypting vote for verification/auditing purposes");
+        require!(
+            encrypted_proposal_id != [0u8; 32] && vote != [0u8; 32],
+            ErrorCode::EmptyCiphertext
+        );
+        require!(
+            ctx.accounts.system_acc.governance_mint.is_none(),
+            ErrorCode::WeightedVotingRequired
+        );
+
+        // Reject ballots that could never be confirmed
+        validate_vote(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            round_id,
+            proposal_id,
+        )?;
+        check_allowlist(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.allowlist,
+            &ctx.accounts.payer.key(),
+        )?;
+        check_authority_vote(&ctx.accounts.system_acc, &ctx.accounts.payer.key())?;
+        check_blacklist(&ctx.accounts.blacklist)?;
+
+        let prepared_slot = Clock::get()?.slot;
+        let pending_vote = &mut ctx.accounts.pending_vote;
+        pending_vote.bump = ctx.bumps.pending_vote;
+        pending_vote.voter = ctx.accounts.payer.key();
+        pending_vote.round_id = round_id;
+        pending_vote.proposal_id = proposal_id;
+        pending_vote.encrypted_proposal_id = encrypted_proposal_id;
+        pending_vote.vote = vote;
+        pending_vote.vote_encryption_pubkey = vote_encryption_pubkey;
+        pending_vote.vote_nonce = vote_nonce;
+        pending_vote.prepared_slot = prepared_slot;
+
+        emit!(PendingVoteCreatedEvent {
+            voter: pending_vote.voter,
+            round_id,
+            expires_at_slot: pending_vote.expires_at_slot(),
+        });
+
+        Ok(())
+    }
+
+    /// Second step of a two-step vote: counts the ballot stored by `prepare_vote`.
+    ///
+    /// Re-runs the checks of `vote_for_proposal` against the current state, creates the
+    /// vote receipt and queues the same tally computation. The pending vote is closed and
+    /// its rent returned to the voter.
+    ///
+    /// # Arguments
+    /// * `computation_offset` - Unique MPC computation offset, or `AUTO_COMPUTATION_OFFSET`
+    ///   to have the program assign one from the system's counter
+    pub fn confirm_vote(ctx: Context<ConfirmVote>, computation_offset: u64) -> Result<()> {
+        let pending_vote = &ctx.accounts.pending_vote;
+        require!(
+            Clock::get()?.slot <= pending_vote.expires_at_slot(),
+            ErrorCode::PendingVoteExpired
+        );
+
+        let round_id = pending_vote.round_id;
+        let proposal_id = pending_vote.proposal_id;
+        let encrypted_proposal_id = pending_vote.encrypted_proposal_id;
+        let vote = pending_vote.vote;
+        let vote_encryption_pubkey = pending_vote.vote_encryption_pubkey;
+        let vote_nonce = pending_vote.vote_nonce;
+
+        require!(
+            ctx.accounts.system_acc.governance_mint.is_none(),
+            ErrorCode::WeightedVotingRequired
+        );
+
+        validate_vote(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            round_id,
+            proposal_id,
+        )?;
+
+        // Votes are only accepted while the round's escrow is still open
+        check_round_escrow_open(&ctx.accounts.round_escrow, &ctx.accounts.round_metadata)?;
+
+        check_allowlist(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.allowlist,
+            &ctx.accounts.payer.key(),
+        )?;
+        check_authority_vote(&ctx.accounts.system_acc, &ctx.accounts.payer.key())?;
+        check_blacklist(&ctx.accounts.blacklist)?;
+
+        enforce_vote_rate_limit(
+            &ctx.accounts.system_acc,
+            &mut ctx.accounts.voter_rate,
+            ctx.bumps.voter_rate,
+            ctx.accounts.payer.key(),
+            round_id,
+        )?;
+
+        let current_timestamp = create_vote_receipt(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.vote_receipt.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            round_id,
+            encrypted_proposal_id,
+            vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+        )?;
+
+        emit!(VoteReceiptCreatedEvent {
+            voter: ctx.accounts.payer.key(),
+            proposal_id,
+            encrypted_proposal_id,
+            timestamp: current_timestamp,
+        });
+
+        record_voter(
+            &mut ctx.accounts.round_metadata,
+            ctx.accounts.system_acc.min_quorum,
+            round_id,
+        );
 
         let args = vec![
             Argument::ArcisPubkey(vote_encryption_pubkey),
             Argument::PlaintextU128(vote_nonce),
             Argument::EncryptedU8(vote),
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU64(
+                ctx.accounts
+                    .system_acc
+                    .max_votes_per_proposal
+                    .unwrap_or(u64::MAX),
+            ),
+            Argument::PlaintextU32(seconded_mask(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+            )),
         ];
 
+        let computation_offset =
+            consume_computation_offset(&mut ctx.accounts.system_acc, computation_offset)?;
+
+        // Cleared by the callback once the tally lands; an abort marks it failed instead
+        record_tally_queued(&ctx.accounts.vote_receipt, computation_offset)?;
+
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+        // Tallied by the regular vote circuit and callback
         queue_computation(
             ctx.accounts,
             computation_offset,
             args,
             None,
-            vec![DecryptVoteCallback::callback_ix(&[
+            vec![VoteForProposalCallback::callback_ix(&[
                 CallbackAccount {
                     pubkey: ctx.accounts.system_acc.key(),
                     is_writable: true,
                 },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.vote_receipt.key(),
+                    is_writable: true,
+                },
             ])],
         )?;
         Ok(())
     }
 
-    #[arcium_callback(encrypted_ix = "decrypt_vote")]
-    pub fn decrypt_vote_callback(
-        ctx: Context<DecryptVoteCallback>,
-        output: ComputationOutputs<DecryptVoteOutput>,
-    ) -> Result<()> {
-        let decrypted_proposal_id = match output {
-            ComputationOutputs::Success(DecryptVoteOutput { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
-        };
-
-        // Emit event with the decrypted proposal ID
-        emit!(VoteDecryptedEvent {
-            decrypted_proposal_id,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(())
-    }
-
-    pub fn init_verify_winning_vote_comp_def(ctx: Context<InitVerifyWinningVoteCompDef>) -> Result<()> {
-        init_comp_def(ctx.accounts, true, 0, None, None)?;
-        Ok(())
-    }
-
-    /// Decrypts an encrypted vote and verifies if it was for the winning proposal in a given round.
+    /// Re-queues the tally of a vote whose MPC computation aborted.
     ///
-    /// This function allows verification of whether a specific vote was cast for the winning proposal
-    /// in a particular voting round. It decrypts the vote and compares it against the winning proposal
-    /// stored in the round history. Requires a valid vote receipt to prevent fake vote verification.
+    /// `vote_for_proposal` creates the receipt before the computation runs, so an
+    /// aborted computation would otherwise leave the voter locked out with a vote that
+    /// was never counted. The receipt records the offset of its queued tally, and the
+    /// vote callback marks it `tally_failed` when that computation aborts; only then can
+    /// the voter submit a fresh encryption of their ballot here. The receipt is updated
+    /// to hold the new ciphertext, key and nonce, keeps its original vote timestamp, and
+    /// turnout is not counted again.
     ///
     /// # Arguments
-    /// * `vote` - The encrypted vote containing the proposal ID
-    /// * `vote_encryption_pubkey` - The public key used to encrypt the vote
-    /// * `vote_nonce` - The nonce used for vote encryption
-    /// * `round_id` - The round ID to check against
-    pub fn verify_winning_vote(
-        ctx: Context<VerifyWinningVote>,
+    /// * `computation_offset` - Unique MPC computation offset, or `AUTO_COMPUTATION_OFFSET`
+    ///   to have the program assign one from the system's counter
+    /// * `vote` - Encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - Voter's public key for encryption
+    /// * `vote_nonce` - Cryptographic nonce for the vote encryption
+    /// * `round_id` - The round the receipt belongs to
+    pub fn reissue_vote(
+        ctx: Context<ReissueVote>,
         computation_offset: u64,
         vote: [u8; 32],
         vote_encryption_pubkey: [u8; 32],
         vote_nonce: u128,
         round_id: u64,
     ) -> Result<()> {
-        msg!("Verifying if vote was for winning proposal in round {}", round_id);
-        
-        // Log the seeds used to derive round_history account
-        let round_id_bytes = round_id.to_le_bytes();
+        require!(vote != [0u8; 32], ErrorCode::EmptyCiphertext);
 
-        // Manually derive the round_history PDA to verify
-        let (_expected_round_history_pda, _round_history_bump) = Pubkey::find_program_address(
-            &[
-                b"voting_round_history",
-                ctx.accounts.system_acc.key().as_ref(),
-                &round_id_bytes,
-            ],
-            &crate::ID
-        );
+        validate_voting_open(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            round_id,
+        )?;
+        check_blacklist(&ctx.accounts.blacklist)?;
 
-        // Manually derive the round_escrow PDA to verify
-        let (expected_round_escrow_pda, _round_escrow_bump) = Pubkey::find_program_address(
-            &[b"round_escrow", &round_id_bytes],
-            &crate::ID
-        );
-        
-        // Verify the round_escrow account
+        let mut vote_receipt_account = VoteReceiptAccount::load(&ctx.accounts.vote_receipt)?;
         require!(
-            ctx.accounts.round_escrow.key() == expected_round_escrow_pda,
+            vote_receipt_account.voter == ctx.accounts.payer.key(),
             ErrorCode::InvalidAuthority
         );
-        
- 
-
-        // Verify that the round history exists for the given round
         require!(
-            round_id < ctx.accounts.round_metadata.current_round,
-            ErrorCode::InvalidRoundId
-        );
-
-        // SECURITY FIX: Verify the vote receipt exists and belongs to the caller
-        let (expected_vote_receipt_pda, _) = Pubkey::find_program_address(
-            &[b"vote_receipt", ctx.accounts.payer.key().as_ref(), &round_id_bytes],
-            &crate::ID
+            vote_receipt_account.awaiting_tally,
+            ErrorCode::VoteNotAwaitingTally
         );
-        
-        // DEBUG: Log vote receipt PDA validation
-      
-        
+        // A computation that is merely slow may still land and count the vote
         require!(
-            ctx.accounts.vote_receipt.key() == expected_vote_receipt_pda,
-            ErrorCode::InvalidVoteReceipt
+            vote_receipt_account.tally_failed,
+            ErrorCode::VoteReissueTooEarly
         );
-        
-        // Verify the vote receipt contains the same encrypted proposal ID
-        let vote_receipt_data = &ctx.accounts.vote_receipt.data.borrow();
-        let account_data = vote_receipt_data; // No discriminator to skip
- 
-        
-        // Extract encrypted_proposal_id from vote receipt
-        // VoteReceiptAccount structure: bump(1) + voter(32) + encrypted_proposal_id(32) + timestamp(8) + vote_encryption_pubkey(32)
-        let stored_encrypted_proposal_id: [u8; 32] = account_data[1 + 32..1 + 32 + 32].try_into().unwrap();
-        
-   
-        
-        // CRITICAL: Compare the vote parameter with the stored encrypted_proposal_id
-        // This ensures the vote being verified is the same as what was actually cast
-        require!(
-            stored_encrypted_proposal_id == vote,
-            ErrorCode::VoteMismatch
+
+        // The receipt must hold the ballot that is actually tallied
+        vote_receipt_account.encrypted_proposal_id = vote;
+        vote_receipt_account.vote_encryption_pubkey = vote_encryption_pubkey;
+        vote_receipt_account.vote_nonce = vote_nonce;
+        vote_receipt_account.store(&ctx.accounts.vote_receipt)?;
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        msg!(
+            "Reissuing vote of {} for round {}",
+            ctx.accounts.payer.key(),
+            round_id
         );
-        
-        msg!("Vote receipt validation passed - vote matches stored encrypted proposal ID");
 
-        // Manually deserialize the round history account data
-        let round_history_data = &ctx.accounts.round_history.data.borrow();
-        
-        // Skip the discriminator (8 bytes) and deserialize the VotingRoundHistoryAccount
-        let account_data = &round_history_data[8..];
-        
-        // Deserialize the winning_proposal_id (it's at offset 9 after discriminator: bump(1) + round_id(8) = 9)
-        let winning_proposal_id = account_data[9];
-        
-        msg!("Winning proposal ID from round history: {}", winning_proposal_id);
+        emit!(VoteReissuedEvent {
+            voter: ctx.accounts.payer.key(),
+            round_id,
+            timestamp: current_timestamp,
+        });
 
         let args = vec![
             Argument::ArcisPubkey(vote_encryption_pubkey),
             Argument::PlaintextU128(vote_nonce),
             Argument::EncryptedU8(vote),
-            Argument::PlaintextU8(winning_proposal_id),
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU64(
+                ctx.accounts
+                    .system_acc
+                    .max_votes_per_proposal
+                    .unwrap_or(u64::MAX),
+            ),
+            Argument::PlaintextU32(seconded_mask(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+            )),
         ];
 
+        let computation_offset =
+            consume_computation_offset(&mut ctx.accounts.system_acc, computation_offset)?;
+
+        // Cleared by the callback once the tally lands; an abort marks it failed instead
+        record_tally_queued(&ctx.accounts.vote_receipt, computation_offset)?;
+
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+        // Tallied by the regular vote circuit and callback
         queue_computation(
             ctx.accounts,
             computation_offset,
             args,
             None,
-            vec![VerifyWinningVoteCallback::callback_ix(&[
+            vec![VoteForProposalCallback::callback_ix(&[
                 CallbackAccount {
                     pubkey: ctx.accounts.system_acc.key(),
                     is_writable: true,
                 },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.vote_receipt.key(),
+                    is_writable: true,
+                },
             ])],
         )?;
         Ok(())
     }
 
-    #[arcium_callback(encrypted_ix = "verify_winning_vote")]
-    pub fn verify_winning_vote_callback(
-        ctx: Context<VerifyWinningVoteCallback>,
-        output: ComputationOutputs<VerifyWinningVoteOutput>,
-    ) -> Result<()> {
-        let verification_result = match output {
-            ComputationOutputs::Success(VerifyWinningVoteOutput { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
-        };
-
-        // Log verification result
-        if verification_result {
-            msg!("✅ Vote was for winning proposal!");
-        } else {
-            msg!("❌ Vote was not for winning proposal");
-        }
-
-        // Emit event with the verification result
-        emit!(VoteVerificationEvent {
-            is_winning_vote: verification_result,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(())
-    }
-
-
-
-    #[arcium_callback(encrypted_ix = "vote_for_proposal")]
-    pub fn vote_for_proposal_callback(
-        ctx: Context<VoteForProposalCallback>,
-        output: ComputationOutputs<VoteForProposalOutput>,
-    ) -> Result<()> {
-        let o = match output {
-            ComputationOutputs::Success(VoteForProposalOutput { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
-        };
-
-        ctx.accounts.system_acc.proposal_votes = o.ciphertexts;
-        ctx.accounts.system_acc.nonce = o.nonce;
-
-        let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp;
+    /// Closes a pending vote that was never confirmed, returning its rent to the voter.
+    ///
+    /// Permissionless once the pending vote is past `PENDING_VOTE_EXPIRY_SLOTS`, so
+    /// abandoned ballots don't block the voter from preparing a new one.
+    pub fn clear_expired_pending_vote(ctx: Context<ClearExpiredPendingVote>) -> Result<()> {
+        require!(
+            Clock::get()?.slot > ctx.accounts.pending_vote.expires_at_slot(),
+            ErrorCode::PendingVoteNotExpired
+        );
 
-        emit!(VoteEvent {
-            timestamp: current_timestamp,
-        });
+        msg!(
+            "Cleared expired pending vote of {} for round {}",
+            ctx.accounts.pending_vote.voter,
+            ctx.accounts.pending_vote.round_id
+        );
 
         Ok(())
     }
 
-    pub fn init_reveal_winner_comp_def(ctx: Context<InitRevealWinnerCompDef>) -> Result<()> {
+    pub fn init_batch_vote_comp_def(ctx: Context<InitBatchVoteCompDef>) -> Result<()> {
         init_comp_def(ctx.accounts, true, 0, None, None)?;
         Ok(())
     }
 
-    /// Reveals the winning proposal with the most votes.
+    /// Submits up to `MAX_BATCH_VOTES` encrypted votes from distinct voters in one computation.
     ///
-    /// Only the system authority can call this function to decrypt and reveal the vote tallies.
-    /// The MPC computation finds the proposal with the maximum votes and returns its ID and vote count.
-    /// Creates a voting round history account to permanently store the results.
+    /// Each vote is validated and gets its own receipt exactly as in `vote_for_proposal`,
+    /// but all of them are folded into the tally by a single MPC computation, so the
+    /// computation fee is paid once per batch. The tradeoff is atomicity: if any
+    /// sub-vote is invalid the whole batch is rejected and no vote is counted.
+    ///
+    /// For every vote, the remaining accounts must contain the voter (signer, writable,
+    /// pays for its receipt), the voter's vote receipt PDA and the voter's blacklist PDA,
+    /// in the same order as `votes`.
     ///
     /// # Arguments
-    /// * `system_id` - The system ID to reveal results for
-    pub fn reveal_winning_proposal(
-        ctx: Context<RevealWinningProposal>,
+    /// * `votes` - The encrypted votes, one per voter
+    /// * `round_id` - The round being voted in
+    pub fn batch_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchVote<'info>>,
         computation_offset: u64,
-        _system_id: u32,
+        votes: Vec<BatchedVote>,
+        round_id: u64,
     ) -> Result<()> {
+        msg!(
+            "batch_vote called with {} votes for round_id: {}",
+            votes.len(),
+            round_id
+        );
+
         require!(
-            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
-            ErrorCode::InvalidAuthority
+            !votes.is_empty() && votes.len() <= MAX_BATCH_VOTES,
+            ErrorCode::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == votes.len() * 3,
+            ErrorCode::InvalidBatchSize
         );
 
-        msg!("Revealing winning proposal for round {}", ctx.accounts.round_metadata.current_round);
+        // Weighted systems must vote through weighted_vote_for_proposal
+        require!(
+            ctx.accounts.system_acc.governance_mint.is_none(),
+            ErrorCode::WeightedVotingRequired
+        );
 
-        let args = vec![
+        // Votes are only accepted while the round's escrow is still open
+        check_round_escrow_open(&ctx.accounts.round_escrow, &ctx.accounts.round_metadata)?;
+
+        let system_acc_key = ctx.accounts.system_acc.key();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let mut voters: Vec<Pubkey> = Vec::with_capacity(votes.len());
+
+        for (batched_vote, accounts) in votes.iter().zip(ctx.remaining_accounts.chunks(3)) {
+            let voter = &accounts[0];
+            let vote_receipt = &accounts[1];
+
+            require!(voter.is_signer, ErrorCode::InvalidAuthority);
+            require!(!voters.contains(voter.key), ErrorCode::DuplicateBatchVoter);
+            voters.push(voter.key());
+
+            validate_vote(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+                round_id,
+                batched_vote.proposal_id,
+            )?;
+
+            check_allowlist(&ctx.accounts.system_acc, &ctx.accounts.allowlist, voter.key)?;
+            check_authority_vote(&ctx.accounts.system_acc, voter.key)?;
+            check_blacklist_for(&system_acc_key, voter.key, &accounts[2])?;
+
+            let current_timestamp = create_vote_receipt(
+                &system_acc_key,
+                voter,
+                vote_receipt,
+                &system_program,
+                round_id,
+                batched_vote.encrypted_proposal_id,
+                batched_vote.vote,
+                batched_vote.vote_encryption_pubkey,
+                batched_vote.vote_nonce,
+            )?;
+
+            emit!(VoteReceiptCreatedEvent {
+                voter: voter.key(),
+                proposal_id: batched_vote.proposal_id,
+                encrypted_proposal_id: batched_vote.encrypted_proposal_id,
+                timestamp: current_timestamp,
+            });
+
+            record_voter(
+                &mut ctx.accounts.round_metadata,
+                ctx.accounts.system_acc.min_quorum,
+                round_id,
+            );
+        }
+
+        // The circuit always takes MAX_BATCH_VOTES votes; unused slots repeat the first
+        // vote and are skipped because they sit at or past `count`
+        let mut args = Vec::new();
+        for i in 0..MAX_BATCH_VOTES {
+            let batched_vote = votes.get(i).unwrap_or(&votes[0]);
+            args.push(Argument::ArcisPubkey(batched_vote.vote_encryption_pubkey));
+            args.push(Argument::PlaintextU128(batched_vote.vote_nonce));
+            args.push(Argument::EncryptedU8(batched_vote.vote));
+        }
+        args.extend([
             Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
             Argument::Account(
                 ctx.accounts.system_acc.key(),
-                // Offset calculation: 8 bytes (discriminator) + 1 byte (bump) + 32 bytes (authority) + 16 bytes (nonce) + 1 byte (next_proposal_id)
-                8 + 1 + 32 + 16 + 1,
-                32 * 10, // 10 encrypted proposal vote counters, 32 bytes each
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
             ),
-        ];
+            Argument::PlaintextU8(votes.len() as u8),
+            Argument::PlaintextU64(
+                ctx.accounts
+                    .system_acc
+                    .max_votes_per_proposal
+                    .unwrap_or(u64::MAX),
+            ),
+            Argument::PlaintextU32(seconded_mask(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+            )),
+        ]);
 
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
-
         queue_computation(
             ctx.accounts,
             computation_offset,
             args,
             None,
-            vec![RevealWinningProposalCallback::callback_ix(&[
+            vec![BatchVoteCallback::callback_ix(&[
                 CallbackAccount {
                     pubkey: ctx.accounts.system_acc.key(),
                     is_writable: true,
                 },
                 CallbackAccount {
                     pubkey: ctx.accounts.round_metadata.key(),
-                    is_writable: true,
+                    is_writable: false,
                 },
             ])],
         )?;
         Ok(())
     }
 
+    #[arcium_callback(encrypted_ix = "batch_vote")]
+    pub fn batch_vote_callback(
+        ctx: Context<BatchVoteCallback>,
+        output: ComputationOutputs<BatchVoteOutput>,
+    ) -> Result<()> {
+        let o = match output {
+            ComputationOutputs::Success(BatchVoteOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "batch_vote",
+                    Some(ctx.accounts.round_metadata.current_round),
+                )
+            }
+        };
 
+        ctx.accounts.system_acc.proposal_votes = o.field_0.ciphertexts;
+        ctx.accounts.system_acc.nonce = o.field_0.nonce;
 
+        emit!(BatchVoteEvent {
+            timestamp: Clock::get()?.unix_timestamp,
+            round_id: ctx.accounts.round_metadata.current_round,
+            total_voters: ctx.accounts.round_metadata.total_voters,
+            accepted: o.field_1,
+            rejected: o.field_2,
+        });
 
+        Ok(())
+    }
 
+    pub fn init_weighted_vote_comp_def(ctx: Context<InitWeightedVoteCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
 
-    #[arcium_callback(encrypted_ix = "reveal_winning_proposal")]
-    pub fn reveal_winning_proposal_callback(
-        ctx: Context<RevealWinningProposalCallback>,
-        output: ComputationOutputs<RevealWinningProposalOutput>,
+    /// Submits an encrypted vote weighted by the voter's governance token holdings.
+    ///
+    /// Works like `vote_for_proposal`, but the voter's weight (capped at
+    /// `MAX_VOTE_WEIGHT`) is added to the chosen proposal's counter instead of a single
+    /// vote. Only available when the system was initialized with a governance mint.
+    /// Accepts `AUTO_COMPUTATION_OFFSET` like `vote_for_proposal`.
+    ///
+    /// The weight comes from `weight_proof`, which must prove `(voter, weight)` against
+    /// the voter snapshot set with `set_voter_snapshot`. A live token balance can't be
+    /// used: the same tokens could be moved to another wallet and vote again.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal being voted for (plaintext for validation)
+    /// * `encrypted_proposal_id` - Encrypted proposal ID stored in the receipt (must equal `vote`)
+    /// * `vote` - Encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - Voter's public key for encryption
+    /// * `vote_nonce` - Cryptographic nonce for the vote encryption
+    /// * `weight_proof` - Snapshot weight and its Merkle proof (snapshot systems only)
+    pub fn weighted_vote_for_proposal(
+        ctx: Context<WeightedVoteForProposal>,
+        computation_offset: u64,
+        proposal_id: u8,
+        encrypted_proposal_id: [u8; 32],
+        vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+        weight_proof: WeightProof,
     ) -> Result<()> {
-        let result = match output {
-            ComputationOutputs::Success(RevealWinningProposalOutput { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
-        };
-        
-        let winning_proposal_id = result.field_0;
-        let winning_vote_count = result.field_1;
+        msg!(
+            "weighted_vote_for_proposal called with round_id: {}",
+            round_id
+        );
 
-        // Debug: Log the results from the encrypted computation
-        msg!("🔍 DEBUG: Encrypted computation results:");
-        msg!("🔍 DEBUG: - Winning proposal ID: {}", winning_proposal_id);
-        msg!("🔍 DEBUG: - Winning vote count: {}", winning_vote_count);
+        require!(
+            encrypted_proposal_id != [0u8; 32] && vote != [0u8; 32],
+            ErrorCode::EmptyCiphertext
+        );
+
+        require!(
+            ctx.accounts.system_acc.governance_mint.is_some(),
+            ErrorCode::WeightedVotingDisabled
+        );
+        let root = ctx
+            .accounts
+            .system_acc
+            .voter_snapshot_root
+            .ok_or(ErrorCode::VoterSnapshotRequired)?;
 
-        // Store the winning proposal ID and vote count on-chain in the system account
-        ctx.accounts.system_acc.winning_proposal_id = Some(winning_proposal_id);
-        ctx.accounts.system_acc.winning_vote_count = Some(winning_vote_count);
+        // The claimed weight must be a leaf of the snapshot tree
+        require!(
+            verify_weight_proof(
+                &root,
+                &ctx.accounts.payer.key(),
+                weight_proof.weight,
+                &weight_proof.proof,
+            ),
+            ErrorCode::InvalidWeightProof
+        );
 
-        // Get current round before incrementing
-        let current_round_id = ctx.accounts.round_metadata.current_round;
+        let weight = weight_proof.weight.min(MAX_VOTE_WEIGHT);
+        require!(weight > 0, ErrorCode::NoVotingPower);
 
-        // Note: Round history account will be created in a separate instruction
+        validate_vote(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            round_id,
+            proposal_id,
+        )?;
 
-        // Increment the round counter for the next voting round
-        ctx.accounts.round_metadata.current_round += 1;
-        
-        // Update the round start timestamp
-        ctx.accounts.round_metadata.round_started = Clock::get()?.unix_timestamp;
+        // Votes are only accepted while the round's escrow is still open
+        check_round_escrow_open(&ctx.accounts.round_escrow, &ctx.accounts.round_metadata)?;
 
-        msg!(
-            "Round {} completed - Winner: Proposal {} with {} votes", 
-            current_round_id, 
-            winning_proposal_id,
-            winning_vote_count
+        check_allowlist(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.allowlist,
+            &ctx.accounts.payer.key(),
+        )?;
+        check_authority_vote(&ctx.accounts.system_acc, &ctx.accounts.payer.key())?;
+        check_blacklist(&ctx.accounts.blacklist)?;
+
+        let current_timestamp = create_vote_receipt(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.vote_receipt.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            round_id,
+            encrypted_proposal_id,
+            vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+        )?;
+
+        emit!(VoteReceiptCreatedEvent {
+            voter: ctx.accounts.payer.key(),
+            proposal_id,
+            encrypted_proposal_id,
+            timestamp: current_timestamp,
+        });
+
+        record_voter(
+            &mut ctx.accounts.round_metadata,
+            ctx.accounts.system_acc.min_quorum,
+            round_id,
         );
+        record_voting_power(&mut ctx.accounts.round_metadata, weight)?;
 
-        emit!(WinningProposalEvent { 
-            winning_proposal_id,
-            winning_vote_count,
-            round_id: current_round_id,
+        msg!("Casting vote with weight {}", weight);
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),
+            Argument::PlaintextU64(weight),
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU32(seconded_mask(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+            )),
+        ];
+
+        let computation_offset =
+            consume_computation_offset(&mut ctx.accounts.system_acc, computation_offset)?;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![WeightedVoteCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "weighted_vote")]
+    pub fn weighted_vote_callback(
+        ctx: Context<WeightedVoteCallback>,
+        output: ComputationOutputs<WeightedVoteOutput>,
+    ) -> Result<()> {
+        let o = match output {
+            ComputationOutputs::Success(WeightedVoteOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "weighted_vote",
+                    Some(ctx.accounts.round_metadata.current_round),
+                )
+            }
+        };
+
+        ctx.accounts.system_acc.proposal_votes = o.field_0.ciphertexts;
+        ctx.accounts.system_acc.nonce = o.field_0.nonce;
+
+        emit!(VoteEvent {
+            timestamp: Clock::get()?.unix_timestamp,
+            accepted: o.field_1,
+            rejected: false,
+            round_id: ctx.accounts.round_metadata.current_round,
+            total_voters: ctx.accounts.round_metadata.total_voters,
+            voter: ctx.accounts.voter.key(),
         });
 
         Ok(())
     }
 
-    /// Creates a voting round history account after a winner has been revealed.
-    /// This is called separately from the reveal callback to avoid MXE complexity.
-    /// All data is read from the system state to prevent tampering.
-    pub fn create_round_history(ctx: Context<CreateRoundHistory>) -> Result<()> {
-        // Verify that the caller is the system authority
+    /// Delegates the caller's voting power to another key.
+    ///
+    /// The delegate can then fold this delegation into their own vote with
+    /// `delegated_vote_for_proposal`. Calling this again replaces the delegate.
+    ///
+    /// # Arguments
+    /// * `delegate` - Key that will vote on the caller's behalf
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Pubkey) -> Result<()> {
         require!(
-            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
-            ErrorCode::InvalidAuthority
+            delegate != ctx.accounts.payer.key(),
+            ErrorCode::InvalidDelegate
+        );
+
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.bump = ctx.bumps.delegation;
+        delegation.delegator = ctx.accounts.payer.key();
+        delegation.delegate = delegate;
+
+        emit!(DelegateSetEvent {
+            delegator: ctx.accounts.payer.key(),
+            delegate,
+        });
+
+        Ok(())
+    }
+
+    /// Revokes the caller's delegation and returns the account rent.
+    ///
+    /// Votes already cast with the delegation stay counted.
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        emit!(DelegateRevokedEvent {
+            delegator: ctx.accounts.payer.key(),
+            delegate: ctx.accounts.delegation.delegate,
+        });
+
+        Ok(())
+    }
+
+    /// Casts an encrypted vote carrying the weight of the caller plus their delegators.
+    ///
+    /// Each delegator adds one vote, so the ballot goes through the weighted-vote
+    /// circuit with a weight of `1 + delegations`. Only systems without a governance
+    /// mint support delegation, since every voter there weighs the same.
+    ///
+    /// For every delegation, the remaining accounts must contain the delegation PDA, the
+    /// delegator's vote receipt PDA for the round and the delegator's blacklist PDA. A
+    /// blacklisted delegator can't have a vote cast for them. A delegator who has
+    /// already voted is rejected, and the call creates the delegator's receipt (funded
+    /// by the delegate) so they can't vote again themselves. This also breaks cycles:
+    /// once either side of a mutual delegation has voted, the other can't count it.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal being voted for (plaintext for validation)
+    /// * `encrypted_proposal_id` - Encrypted proposal ID stored in the receipts
+    /// * `vote` - Encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - Delegate's public key for encryption
+    /// * `vote_nonce` - Cryptographic nonce for the vote encryption
+    /// * `round_id` - The round being voted in
+    pub fn delegated_vote_for_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DelegatedVoteForProposal<'info>>,
+        computation_offset: u64,
+        proposal_id: u8,
+        encrypted_proposal_id: [u8; 32],
+        vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+    ) -> Result<()> {
+        msg!(
+            "delegated_vote_for_proposal called with round_id: {}",
+            round_id
         );
 
-        // Verify that a winner has been revealed
         require!(
-            ctx.accounts.system_acc.winning_proposal_id.is_some(),
-            ErrorCode::NoWinnerRevealed
+            ctx.accounts.system_acc.governance_mint.is_none(),
+            ErrorCode::WeightedVotingRequired
+        );
+        require!(
+            ctx.remaining_accounts.len() % 3 == 0
+                && ctx.remaining_accounts.len() / 3 <= MAX_DELEGATIONS_PER_VOTE,
+            ErrorCode::InvalidDelegation
         );
 
-        // Get current timestamp
-        let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp;
+        validate_vote(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            round_id,
+            proposal_id,
+        )?;
 
-        // Read all data from system state (not from parameters)
-        let round_id = ctx.accounts.round_metadata.current_round - 1; // Previous round (since current_round was incremented)
-        let winning_proposal_id = ctx.accounts.system_acc.winning_proposal_id.unwrap();
-        let total_proposals = ctx.accounts.system_acc.next_proposal_id;
+        // Votes are only accepted while the round's escrow is still open
+        check_round_escrow_open(&ctx.accounts.round_escrow, &ctx.accounts.round_metadata)?;
 
-        // Initialize the round history account with verified data
-        ctx.accounts.round_history.bump = ctx.bumps.round_history;
-        ctx.accounts.round_history.round_id = round_id;
-        ctx.accounts.round_history.winning_proposal_id = winning_proposal_id;
-        ctx.accounts.round_history.revealed_at = current_timestamp;
-        ctx.accounts.round_history.revealed_by = ctx.accounts.payer.key();
-        ctx.accounts.round_history.total_proposals = total_proposals;
+        check_allowlist(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.allowlist,
+            &ctx.accounts.payer.key(),
+        )?;
+        check_authority_vote(&ctx.accounts.system_acc, &ctx.accounts.payer.key())?;
+        check_blacklist(&ctx.accounts.blacklist)?;
 
-        // Reset system state for the next voting round
-        // Note: We don't reset next_proposal_id to 0 because proposal accounts still exist
-        // Instead, we keep the counter and let new proposals get new IDs
-        ctx.accounts.system_acc.winning_proposal_id = None; // Clear winner
-        ctx.accounts.system_acc.winning_vote_count = None; // Clear vote count
-        ctx.accounts.system_acc.proposal_votes = [[0; 32]; 10]; // Reset encrypted vote counters
-        ctx.accounts.system_acc.nonce = ctx.accounts.system_acc.nonce; // Increment nonce for new round
-        
-        // Reset the round proposal counter for the next round
-        ctx.accounts.round_metadata.proposals_in_current_round = 0;
-        // Reset the voter counter for the next round
-        ctx.accounts.round_metadata.total_voters = 0;
+        let system_acc_key = ctx.accounts.system_acc.key();
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
 
-        msg!(
-            "Created round history for round {} - Winner: Proposal {}",
+        // The delegate's own receipt goes first, so a delegation pointing back at
+        // the delegate can never be counted in the same round
+        let current_timestamp = create_vote_receipt_for(
+            &system_acc_key,
+            &ctx.accounts.payer.key(),
+            &payer,
+            &ctx.accounts.vote_receipt.to_account_info(),
+            &system_program,
+            round_id,
+            encrypted_proposal_id,
+            vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+            true,
+        )?;
+
+        emit!(VoteReceiptCreatedEvent {
+            voter: ctx.accounts.payer.key(),
+            proposal_id,
+            encrypted_proposal_id,
+            timestamp: current_timestamp,
+        });
+
+        record_voter(
+            &mut ctx.accounts.round_metadata,
+            ctx.accounts.system_acc.min_quorum,
             round_id,
-            winning_proposal_id
-        );
-        msg!(
-            "System state reset for next round - Proposals: 0, Winner: None, Nonce: {}",
-            ctx.accounts.system_acc.nonce
         );
 
+        for accounts in ctx.remaining_accounts.chunks(3) {
+            let delegation = Account::<DelegationAccount>::try_from(&accounts[0])
+                .map_err(|_| ErrorCode::InvalidDelegation)?;
+            let delegator_receipt = &accounts[1];
+
+            let (expected_delegation, _) = Pubkey::find_program_address(
+                &[
+                    b"delegation",
+                    system_acc_key.as_ref(),
+                    delegation.delegator.as_ref(),
+                ],
+                &crate::ID,
+            );
+            require!(
+                delegation.key() == expected_delegation,
+                ErrorCode::InvalidDelegation
+            );
+            require!(
+                delegation.delegate == ctx.accounts.payer.key(),
+                ErrorCode::InvalidDelegation
+            );
+
+            check_allowlist(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.allowlist,
+                &delegation.delegator,
+            )?;
+            check_authority_vote(&ctx.accounts.system_acc, &delegation.delegator)?;
+            check_blacklist_for(&system_acc_key, &delegation.delegator, &accounts[2])?;
+
+            // Fails if the delegator already has a receipt for this round
+            create_vote_receipt_for(
+                &system_acc_key,
+                &delegation.delegator,
+                &payer,
+                delegator_receipt,
+                &system_program,
+                round_id,
+                encrypted_proposal_id,
+                vote,
+                vote_encryption_pubkey,
+                vote_nonce,
+                true,
+            )?;
+
+            record_voter(
+                &mut ctx.accounts.round_metadata,
+                ctx.accounts.system_acc.min_quorum,
+                round_id,
+            );
+        }
+
+        let delegations = (ctx.remaining_accounts.len() / 3) as u64;
+        let weight = 1 + delegations;
+
+        emit!(DelegatedVoteEvent {
+            delegate: ctx.accounts.payer.key(),
+            delegations,
+            weight,
+            round_id,
+        });
+
+        msg!("Casting delegated vote with weight {}", weight);
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),
+            Argument::PlaintextU64(weight),
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU32(seconded_mask(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+            )),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![WeightedVoteCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
         Ok(())
     }
 
+    pub fn init_approval_vote_comp_def(ctx: Context<InitApprovalVoteCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
 
-}
+    /// Switches the system between plurality and approval voting.
+    ///
+    /// With approval voting enabled, voters cast an encrypted mask through
+    /// `approval_vote` and every approved proposal gains one vote, so the winner is the
+    /// proposal with the most total approvals rather than the most first choices.
+    /// Only the system authority can switch modes, and only before anyone has voted in
+    /// the current round so the two ballot types never share a tally.
+    pub fn set_approval_voting(ctx: Context<SetApprovalVoting>, enabled: bool) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            ctx.accounts.round_metadata.total_voters == 0,
+            ErrorCode::VotingAlreadyStarted
+        );
+        require!(
+            !(enabled && ctx.accounts.system_acc.net_voting),
+            ErrorCode::NetVotingEnabled
+        );
+
+        ctx.accounts.system_acc.approval_voting = enabled;
+
+        msg!(
+            "Approval voting {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+
+        emit!(VotingModeChangedEvent {
+            approval_voting: enabled,
+            net_voting: ctx.accounts.system_acc.net_voting,
+            round_id: ctx.accounts.round_metadata.current_round,
+        });
+
+        Ok(())
+    }
+
+    /// Switches the system between plurality and net (up/down) voting.
+    ///
+    /// With net voting enabled, voters cast an encrypted proposal ID and support flag
+    /// through `net_vote`, and the chosen proposal's score moves up or down by one.
+    /// The winner is the proposal with the highest net score, even if every score is
+    /// negative. The same restrictions as `set_approval_voting` apply, and the two
+    /// modes can't be enabled together.
+    pub fn set_net_voting(ctx: Context<SetApprovalVoting>, enabled: bool) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            ctx.accounts.round_metadata.total_voters == 0,
+            ErrorCode::VotingAlreadyStarted
+        );
+        require!(
+            !(enabled && ctx.accounts.system_acc.approval_voting),
+            ErrorCode::ApprovalVotingEnabled
+        );
+
+        ctx.accounts.system_acc.net_voting = enabled;
+
+        msg!(
+            "Net voting {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+
+        emit!(VotingModeChangedEvent {
+            approval_voting: ctx.accounts.system_acc.approval_voting,
+            net_voting: enabled,
+            round_id: ctx.accounts.round_metadata.current_round,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_net_vote_comp_def(ctx: Context<InitNetVoteCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Submits an encrypted upvote or downvote for a proposal.
+    ///
+    /// Works like `vote_for_proposal`, but the encrypted ballot also carries a support
+    /// flag, and the circuit adds or subtracts one from the chosen proposal's score.
+    /// Only available while net voting is enabled.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal being voted on (plaintext for validation)
+    /// * `encrypted_proposal_id` - Encrypted proposal ID stored in the receipt
+    /// * `vote` - Encrypted proposal ID of the ballot
+    /// * `support` - Encrypted support flag of the ballot
+    /// * `vote_encryption_pubkey` - Voter's public key for encryption
+    /// * `vote_nonce` - Cryptographic nonce for the ballot encryption
+    /// * `round_id` - The round being voted in
+    pub fn net_vote(
+        ctx: Context<NetVoteForProposal>,
+        computation_offset: u64,
+        proposal_id: u8,
+        encrypted_proposal_id: [u8; 32],
+        vote: [u8; 32],
+        support: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+    ) -> Result<()> {
+        msg!("net_vote called with round_id: {}", round_id);
+
+        require!(
+            ctx.accounts.system_acc.net_voting,
+            ErrorCode::NetVotingDisabled
+        );
+        require!(
+            ctx.accounts.system_acc.governance_mint.is_none(),
+            ErrorCode::WeightedVotingRequired
+        );
+
+        validate_voting_open(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            round_id,
+        )?;
+        require!(
+            proposal_id < ctx.accounts.round_metadata.proposals_in_current_round,
+            ErrorCode::InvalidProposalId
+        );
+
+        // Votes are only accepted while the round's escrow is still open
+        check_round_escrow_open(&ctx.accounts.round_escrow, &ctx.accounts.round_metadata)?;
+
+        check_allowlist(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.allowlist,
+            &ctx.accounts.payer.key(),
+        )?;
+        check_authority_vote(&ctx.accounts.system_acc, &ctx.accounts.payer.key())?;
+        check_blacklist(&ctx.accounts.blacklist)?;
+
+        let current_timestamp = create_vote_receipt(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.vote_receipt.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            round_id,
+            encrypted_proposal_id,
+            vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+        )?;
+
+        emit!(VoteReceiptCreatedEvent {
+            voter: ctx.accounts.payer.key(),
+            proposal_id,
+            encrypted_proposal_id,
+            timestamp: current_timestamp,
+        });
+
+        record_voter(
+            &mut ctx.accounts.round_metadata,
+            ctx.accounts.system_acc.min_quorum,
+            round_id,
+        );
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),      // NetVote.proposal_id
+            Argument::EncryptedBool(support), // NetVote.support
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU32(seconded_mask(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+            )),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![NetVoteCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "net_vote")]
+    pub fn net_vote_callback(
+        ctx: Context<NetVoteCallback>,
+        output: ComputationOutputs<NetVoteOutput>,
+    ) -> Result<()> {
+        let o = match output {
+            ComputationOutputs::Success(NetVoteOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "net_vote",
+                    Some(ctx.accounts.round_metadata.current_round),
+                )
+            }
+        };
+
+        ctx.accounts.system_acc.proposal_votes = o.field_0.ciphertexts;
+        ctx.accounts.system_acc.nonce = o.field_0.nonce;
+
+        if !o.field_1 {
+            msg!("⚠️ Vote was not counted - proposal ID out of range or not seconded");
+        }
+
+        emit!(VoteEvent {
+            timestamp: Clock::get()?.unix_timestamp,
+            accepted: o.field_1,
+            rejected: false,
+            round_id: ctx.accounts.round_metadata.current_round,
+            total_voters: ctx.accounts.round_metadata.total_voters,
+            voter: ctx.accounts.voter.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Submits an encrypted approval ballot covering any number of proposals.
+    ///
+    /// Works like `vote_for_proposal`, but the ballot is an encrypted mask with one flag
+    /// per proposal slot and every approved proposal's counter is incremented. Approvals
+    /// for slots past the proposals in the current round are ignored by the circuit.
+    /// The receipt stores the encrypted mask instead of a single encrypted proposal ID.
+    ///
+    /// # Arguments
+    /// * `approvals` - Encrypted approval flags, one per proposal slot
+    /// * `vote_encryption_pubkey` - Voter's public key for encryption
+    /// * `vote_nonce` - Cryptographic nonce for the ballot encryption
+    /// * `round_id` - The round being voted in
+    pub fn approval_vote(
+        ctx: Context<ApprovalVote>,
+        computation_offset: u64,
+        approvals: [[u8; 32]; MAX_PROPOSALS],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+    ) -> Result<()> {
+        msg!("approval_vote called with round_id: {}", round_id);
+
+        require!(
+            ctx.accounts.system_acc.approval_voting,
+            ErrorCode::ApprovalVotingDisabled
+        );
+        require!(
+            ctx.accounts.system_acc.governance_mint.is_none(),
+            ErrorCode::WeightedVotingRequired
+        );
+
+        validate_voting_open(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            round_id,
+        )?;
+
+        // Votes are only accepted while the round's escrow is still open
+        check_round_escrow_open(&ctx.accounts.round_escrow, &ctx.accounts.round_metadata)?;
+
+        check_allowlist(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.allowlist,
+            &ctx.accounts.payer.key(),
+        )?;
+        check_authority_vote(&ctx.accounts.system_acc, &ctx.accounts.payer.key())?;
+        check_blacklist(&ctx.accounts.blacklist)?;
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        let receipt = &mut ctx.accounts.approval_receipt;
+        receipt.bump = ctx.bumps.approval_receipt;
+        receipt.voter = ctx.accounts.payer.key();
+        receipt.encrypted_approvals = approvals;
+        receipt.timestamp = current_timestamp;
+        receipt.vote_encryption_pubkey = vote_encryption_pubkey;
+
+        emit!(ApprovalReceiptCreatedEvent {
+            voter: ctx.accounts.payer.key(),
+            round_id,
+            timestamp: current_timestamp,
+        });
+
+        record_voter(
+            &mut ctx.accounts.round_metadata,
+            ctx.accounts.system_acc.min_quorum,
+            round_id,
+        );
+
+        let mut args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+        ];
+        // Interpreted as Approvals.approvals, one flag per proposal slot
+        args.extend(
+            approvals
+                .iter()
+                .map(|approval| Argument::EncryptedBool(*approval)),
+        );
+        args.extend([
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU32(seconded_mask(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+            )),
+        ]);
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![ApprovalVoteCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "approval_vote")]
+    pub fn approval_vote_callback(
+        ctx: Context<ApprovalVoteCallback>,
+        output: ComputationOutputs<ApprovalVoteOutput>,
+    ) -> Result<()> {
+        let o = match output {
+            ComputationOutputs::Success(ApprovalVoteOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "approval_vote",
+                    Some(ctx.accounts.round_metadata.current_round),
+                )
+            }
+        };
+
+        ctx.accounts.system_acc.proposal_votes = o.field_0.ciphertexts;
+        ctx.accounts.system_acc.nonce = o.field_0.nonce;
+
+        emit!(VoteEvent {
+            timestamp: Clock::get()?.unix_timestamp,
+            accepted: o.field_1,
+            rejected: false,
+            round_id: ctx.accounts.round_metadata.current_round,
+            total_voters: ctx.accounts.round_metadata.total_voters,
+            voter: ctx.accounts.voter.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn init_cast_ranked_vote_comp_def(ctx: Context<InitCastRankedVoteCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Casts an encrypted ranked ballot for instant-runoff voting.
+    ///
+    /// The ballot lists proposal IDs in order of preference; unused preference slots
+    /// should hold an out-of-range ID such as `u8::MAX`. The MPC computation copies
+    /// the ballot into the round's encrypted ballot box, which is counted by
+    /// `reveal_ranked_winner` independently of the plurality tally. Like the other vote
+    /// paths it needs the round's escrow to still be open, and the voter counts toward
+    /// the round's turnout and quorum.
+    ///
+    /// # Arguments
+    /// * `ranking` - Encrypted proposal IDs, most preferred first
+    /// * `vote_encryption_pubkey` - Voter's public key for encryption
+    /// * `vote_nonce` - Cryptographic nonce for the ballot encryption
+    /// * `round_id` - The round being voted in
+    pub fn cast_ranked_vote(
+        ctx: Context<CastRankedVote>,
+        computation_offset: u64,
+        ranking: [[u8; 32]; MAX_PROPOSALS],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+    ) -> Result<()> {
+        msg!("cast_ranked_vote called with round_id: {}", round_id);
+
+        validate_voting_open(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            round_id,
+        )?;
+
+        // Votes are only accepted while the round's escrow is still open
+        check_round_escrow_open(&ctx.accounts.round_escrow, &ctx.accounts.round_metadata)?;
+
+        check_allowlist(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.allowlist,
+            &ctx.accounts.payer.key(),
+        )?;
+        check_authority_vote(&ctx.accounts.system_acc, &ctx.accounts.payer.key())?;
+        check_blacklist(&ctx.accounts.blacklist)?;
+
+        let ranked_votes = &mut ctx.accounts.ranked_votes;
+        if ranked_votes.ballot_count == 0 {
+            ranked_votes.bump = ctx.bumps.ranked_votes;
+            ranked_votes.round_id = round_id;
+        }
+        require!(
+            (ranked_votes.ballot_count as usize) < MAX_RANKED_BALLOTS,
+            ErrorCode::RankedBallotBoxFull
+        );
+
+        // Reserve the next slot so queued ballots never overwrite each other
+        let slot = ranked_votes.ballot_count;
+        ranked_votes.ballot_count += 1;
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        let receipt = &mut ctx.accounts.ranked_receipt;
+        receipt.bump = ctx.bumps.ranked_receipt;
+        receipt.voter = ctx.accounts.payer.key();
+        receipt.encrypted_ranking = ranking;
+        receipt.timestamp = current_timestamp;
+        receipt.vote_encryption_pubkey = vote_encryption_pubkey;
+
+        emit!(RankedVoteCastEvent {
+            voter: ctx.accounts.payer.key(),
+            round_id,
+            timestamp: current_timestamp,
+        });
+
+        record_voter(
+            &mut ctx.accounts.round_metadata,
+            ctx.accounts.system_acc.min_quorum,
+            round_id,
+        );
+
+        let mut args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+        ];
+        // Interpreted as RankedVote.ranking, most preferred first
+        args.extend(ranking.iter().map(|choice| Argument::EncryptedU8(*choice)));
+        args.extend([
+            Argument::PlaintextU128(ctx.accounts.ranked_votes.nonce),
+            Argument::Account(
+                ctx.accounts.ranked_votes.key(),
+                RANKED_BALLOTS_OFFSET,
+                32 * RANKED_BALLOT_CIPHERTEXTS as u32,
+            ),
+            Argument::PlaintextU8(slot),
+        ]);
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![CastRankedVoteCallback::callback_ix(&[CallbackAccount {
+                pubkey: ctx.accounts.ranked_votes.key(),
+                is_writable: true,
+            }])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "cast_ranked_vote")]
+    pub fn cast_ranked_vote_callback(
+        ctx: Context<CastRankedVoteCallback>,
+        output: ComputationOutputs<CastRankedVoteOutput>,
+    ) -> Result<()> {
+        let o = match output {
+            ComputationOutputs::Success(CastRankedVoteOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "cast_ranked_vote",
+                    Some(ctx.accounts.ranked_votes.round_id),
+                )
+            }
+        };
+
+        ctx.accounts.ranked_votes.encrypted_ballots = o.ciphertexts;
+        ctx.accounts.ranked_votes.nonce = o.nonce;
+
+        Ok(())
+    }
+
+    pub fn init_change_vote_comp_def(ctx: Context<InitChangeVoteCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Replaces a voter's existing vote in the current round with a new one.
+    ///
+    /// The old ballot and its nonce are checked against the voter's receipt, then the MPC
+    /// computation moves one vote from the old proposal's counter to the new one without
+    /// revealing either choice. The new ballot is held as pending on the receipt and only
+    /// replaces the old one once the callback succeeds, so an aborted computation leaves
+    /// the receipt matching the tally. The original vote must already be counted, and
+    /// votes can only be changed while the round's voting window is still open. Net
+    /// voting and weighted systems can't change votes, and neither can delegated ballots.
+    ///
+    /// # Arguments
+    /// * `old_vote` - The encrypted vote currently stored in the receipt
+    /// * `old_vote_encryption_pubkey` - The public key used to encrypt the old vote
+    /// * `old_vote_nonce` - The nonce used to encrypt the old vote
+    /// * `new_vote` - The new encrypted vote containing the proposal ID
+    /// * `new_vote_encryption_pubkey` - The public key used to encrypt the new vote
+    /// * `new_vote_nonce` - The nonce used to encrypt the new vote
+    /// * `round_id` - The current round ID
+    pub fn change_vote(
+        ctx: Context<ChangeVote>,
+        computation_offset: u64,
+        old_vote: [u8; 32],
+        old_vote_encryption_pubkey: [u8; 32],
+        old_vote_nonce: u128,
+        new_vote: [u8; 32],
+        new_vote_encryption_pubkey: [u8; 32],
+        new_vote_nonce: u128,
+        round_id: u64,
+    ) -> Result<()> {
+        msg!("change_vote called with round_id: {}", round_id);
+
+        require!(!ctx.accounts.system_acc.paused, ErrorCode::SystemPaused);
+        require!(
+            !ctx.accounts.system_acc.tally_reset_pending,
+            ErrorCode::TallyResetPending
+        );
+
+        // The circuit moves a single support vote, so it can't carry a downvote's sign
+        require!(
+            !ctx.accounts.system_acc.net_voting,
+            ErrorCode::NetVotingEnabled
+        );
+        // It also moves exactly one vote, not a weighted voter's full weight
+        require!(
+            ctx.accounts.system_acc.governance_mint.is_none(),
+            ErrorCode::WeightedVotingRequired
+        );
+
+        // Votes can only change in the active round, before it is revealed
+        require!(
+            round_id == ctx.accounts.round_metadata.current_round,
+            ErrorCode::InvalidRoundId
+        );
+        require!(
+            ctx.accounts.system_acc.winning_proposal_id.is_none(),
+            ErrorCode::RoundAlreadyRevealed
+        );
+
+        let voting_deadline = ctx
+            .accounts
+            .round_metadata
+            .voting_deadline(ctx.accounts.system_acc.voting_duration);
+        require!(
+            Clock::get()?.unix_timestamp <= voting_deadline,
+            ErrorCode::VotingClosed
+        );
+
+        check_blacklist(&ctx.accounts.blacklist)?;
+
+        enforce_vote_rate_limit(
+            &ctx.accounts.system_acc,
+            &mut ctx.accounts.voter_rate,
+            ctx.bumps.voter_rate,
+            ctx.accounts.payer.key(),
+            round_id,
+        )?;
+
+        // Verify the vote receipt exists and belongs to the caller
+        let round_id_bytes = round_id.to_le_bytes();
+        let (expected_vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[
+                b"vote_receipt",
+                ctx.accounts.system_acc.key().as_ref(),
+                ctx.accounts.payer.key().as_ref(),
+                &round_id_bytes,
+            ],
+            &crate::ID,
+        );
+        require!(
+            ctx.accounts.vote_receipt.key() == expected_vote_receipt_pda,
+            ErrorCode::InvalidVoteReceipt
+        );
+
+        let mut vote_receipt_account = VoteReceiptAccount::load(&ctx.accounts.vote_receipt)?;
+
+        // The old ballot must be exactly what was cast, so the tally stays consistent
+        require!(
+            vote_receipt_account.encrypted_proposal_id == old_vote,
+            ErrorCode::VoteMismatch
+        );
+        require!(
+            vote_receipt_account.vote_encryption_pubkey == old_vote_encryption_pubkey,
+            ErrorCode::VoteMismatch
+        );
+        check_vote_nonce(&vote_receipt_account, old_vote_nonce)?;
+
+        // A delegated ballot is public to every receipt holder and carries the combined
+        // weight, so one unit of it can't be moved on its own
+        require!(
+            !vote_receipt_account.delegated,
+            ErrorCode::DelegatedVoteLocked
+        );
+
+        // Moving a vote the tally never counted would take one from another voter
+        require!(
+            !vote_receipt_account.awaiting_tally
+                && !vote_receipt_account.tally_failed
+                && !vote_receipt_account.change_pending,
+            ErrorCode::VoteTallyOutstanding
+        );
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        // Committed by the callback once the tally has moved the vote
+        vote_receipt_account.change_pending = true;
+        vote_receipt_account.pending_proposal_id = new_vote;
+        vote_receipt_account.pending_encryption_pubkey = new_vote_encryption_pubkey;
+        vote_receipt_account.pending_nonce = new_vote_nonce;
+
+        vote_receipt_account.store(&ctx.accounts.vote_receipt)?;
+
+        emit!(VoteChangedEvent {
+            voter: ctx.accounts.payer.key(),
+            round_id,
+            encrypted_proposal_id: new_vote,
+            timestamp: current_timestamp,
+        });
+
+        let args = vec![
+            Argument::ArcisPubkey(old_vote_encryption_pubkey),
+            Argument::PlaintextU128(old_vote_nonce),
+            Argument::EncryptedU8(old_vote),
+            Argument::ArcisPubkey(new_vote_encryption_pubkey),
+            Argument::PlaintextU128(new_vote_nonce),
+            Argument::EncryptedU8(new_vote),
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU32(seconded_mask(
+                &ctx.accounts.system_acc,
+                &ctx.accounts.round_metadata,
+            )),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![ChangeVoteCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.vote_receipt.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "change_vote")]
+    pub fn change_vote_callback(
+        ctx: Context<ChangeVoteCallback>,
+        output: ComputationOutputs<ChangeVoteOutput>,
+    ) -> Result<()> {
+        let o = match output {
+            ComputationOutputs::Success(ChangeVoteOutput { field_0 }) => field_0,
+            _ => {
+                // The tally still counts the old ballot, which the receipt keeps
+                let mut vote_receipt_account =
+                    VoteReceiptAccount::load(&ctx.accounts.vote_receipt)?;
+                vote_receipt_account.change_pending = false;
+                vote_receipt_account.store(&ctx.accounts.vote_receipt)?;
+                return computation_aborted(
+                    "change_vote",
+                    Some(ctx.accounts.round_metadata.current_round),
+                );
+            }
+        };
+
+        ctx.accounts.system_acc.proposal_votes = o.field_0.ciphertexts;
+        ctx.accounts.system_acc.nonce = o.field_0.nonce;
+
+        let mut vote_receipt_account = VoteReceiptAccount::load(&ctx.accounts.vote_receipt)?;
+        vote_receipt_account.encrypted_proposal_id = vote_receipt_account.pending_proposal_id;
+        vote_receipt_account.vote_encryption_pubkey =
+            vote_receipt_account.pending_encryption_pubkey;
+        vote_receipt_account.vote_nonce = vote_receipt_account.pending_nonce;
+        vote_receipt_account.timestamp = Clock::get()?.unix_timestamp;
+        vote_receipt_account.change_pending = false;
+        vote_receipt_account.store(&ctx.accounts.vote_receipt)?;
+
+        emit!(VoteEvent {
+            timestamp: Clock::get()?.unix_timestamp,
+            accepted: o.field_1,
+            rejected: false,
+            round_id: ctx.accounts.round_metadata.current_round,
+            total_voters: ctx.accounts.round_metadata.total_voters,
+            voter: ctx.accounts.voter.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn init_decrypt_vote_comp_def(ctx: Context<InitDecryptVoteCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+
+    /// Decrypts an encrypted vote to reveal the plaintext proposal ID.
+    ///
+    /// This function allows the system authority to decrypt individual votes
+    /// for auditing purposes or verification. The vote must have been previously
+    /// encrypted using the same encryption scheme as the voting system.
+    ///
+    /// # Arguments
+    /// * `vote` - The encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - The public key used to encrypt the vote
+    /// * `vote_nonce` - The nonce used for vote encryption
+    pub fn decrypt_vote(
+        ctx: Context<DecryptVote>,
+        computation_offset: u64,
+        vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+    ) -> Result<()> {
+        msg!("Decrypting vote for verification/auditing purposes");
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![DecryptVoteCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "decrypt_vote")]
+    pub fn decrypt_vote_callback(
+        ctx: Context<DecryptVoteCallback>,
+        output: ComputationOutputs<DecryptVoteOutput>,
+    ) -> Result<()> {
+        let decrypted_proposal_id = match output {
+            ComputationOutputs::Success(DecryptVoteOutput { field_0 }) => field_0,
+            _ => return computation_aborted("decrypt_vote", None),
+        };
+
+        // Emit event with the decrypted proposal ID
+        emit!(VoteDecryptedEvent {
+            decrypted_proposal_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_decrypt_vote_standalone_comp_def(
+        ctx: Context<InitDecryptVoteStandaloneCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Decrypts an encrypted vote without loading the system account.
+    ///
+    /// Works like `decrypt_vote`, but neither the instruction nor its callback touches
+    /// system state, so auditing tools only need the Arcium accounts.
+    ///
+    /// # Arguments
+    /// * `vote` - The encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - The public key used to encrypt the vote
+    /// * `vote_nonce` - The nonce used for vote encryption
+    pub fn decrypt_vote_standalone(
+        ctx: Context<DecryptVoteStandalone>,
+        computation_offset: u64,
+        vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+    ) -> Result<()> {
+        msg!("Decrypting vote for auditing without system state");
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![DecryptVoteStandaloneCallback::callback_ix(&[])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "decrypt_vote_standalone")]
+    pub fn decrypt_vote_standalone_callback(
+        _ctx: Context<DecryptVoteStandaloneCallback>,
+        output: ComputationOutputs<DecryptVoteStandaloneOutput>,
+    ) -> Result<()> {
+        let decrypted_proposal_id = match output {
+            ComputationOutputs::Success(DecryptVoteStandaloneOutput { field_0 }) => field_0,
+            _ => return computation_aborted("decrypt_vote_standalone", None),
+        };
+
+        emit!(VoteDecryptedEvent {
+            decrypted_proposal_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_verify_winning_vote_comp_def(ctx: Context<InitVerifyWinningVoteCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Decrypts an encrypted vote and verifies if it was for the winning proposal in a given round.
+    ///
+    /// This function allows verification of whether a specific vote was cast for the winning proposal
+    /// in a particular voting round. It decrypts the vote and compares it against the winning proposal
+    /// stored in the round history. Requires a valid vote receipt to prevent fake vote verification.
+    ///
+    /// # Arguments
+    /// * `vote` - The encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - The public key used to encrypt the vote
+    /// * `vote_nonce` - The nonce used for vote encryption
+    /// * `round_id` - The round ID to check against
+    pub fn verify_winning_vote(
+        ctx: Context<VerifyWinningVote>,
+        computation_offset: u64,
+        vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+    ) -> Result<()> {
+        msg!("Verifying if vote was for winning proposal in round {}", round_id);
+        
+        // Log the seeds used to derive round_history account
+        let round_id_bytes = round_id.to_le_bytes();
+
+        // Manually derive the round_history PDA to verify
+        let (expected_round_history_pda, _round_history_bump) = Pubkey::find_program_address(
+            &[
+                b"voting_round_history",
+                ctx.accounts.system_acc.key().as_ref(),
+                &round_id_bytes,
+            ],
+            &crate::ID
+        );
+        require!(
+            ctx.accounts.round_history.key() == expected_round_history_pda,
+            ErrorCode::InvalidRoundId
+        );
+
+        // Manually derive the round_escrow PDA to verify
+        let (expected_round_escrow_pda, _round_escrow_bump) = Pubkey::find_program_address(
+            &[
+                b"round_escrow",
+                ctx.accounts.system_acc.key().as_ref(),
+                &round_id_bytes,
+            ],
+            &crate::ID,
+        );
+        
+        // Verify the round_escrow account
+        require!(
+            ctx.accounts.round_escrow.key() == expected_round_escrow_pda,
+            ErrorCode::InvalidAuthority
+        );
+        
+ 
+
+        // Verify that the round history exists for the given round
+        require!(
+            round_id < ctx.accounts.round_metadata.current_round,
+            ErrorCode::InvalidRoundId
+        );
+
+        // SECURITY FIX: Verify the vote receipt exists, belongs to the caller and
+        // records the same encrypted vote, key and nonce being verified
+        load_matching_vote_receipt(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.vote_receipt,
+            round_id,
+            vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+        )?;
+
+        msg!("Vote receipt validation passed - vote matches stored encrypted proposal ID");
+
+        let round_history = load_round_history(&ctx.accounts.round_history)?;
+        let winning_proposal_id = round_history.winning_proposal_id;
+        
+        msg!("Winning proposal ID from round history: {}", winning_proposal_id);
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),
+            Argument::PlaintextU8(winning_proposal_id),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![VerifyWinningVoteCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "verify_winning_vote")]
+    pub fn verify_winning_vote_callback(
+        ctx: Context<VerifyWinningVoteCallback>,
+        output: ComputationOutputs<VerifyWinningVoteOutput>,
+    ) -> Result<()> {
+        let verification_result = match output {
+            ComputationOutputs::Success(VerifyWinningVoteOutput { field_0 }) => field_0,
+            _ => return computation_aborted("verify_winning_vote", None),
+        };
+
+        // Log verification result
+        if verification_result {
+            msg!("✅ Vote was for winning proposal!");
+        } else {
+            msg!("❌ Vote was not for winning proposal");
+        }
+
+        // Emit event with the verification result
+        emit!(VoteVerificationEvent {
+            is_winning_vote: verification_result,
+            timestamp: Clock::get()?.unix_timestamp,
+            target_proposal_id: None,
+            dissent: false,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_verify_winning_votes_batch_comp_def(
+        ctx: Context<InitVerifyWinningVotesBatchCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Counts how many of the caller's past votes backed their round's winner, in one
+    /// computation.
+    ///
+    /// Batched `verify_winning_vote` for reputation scoring: up to
+    /// `MAX_BATCH_VERIFICATIONS` votes from distinct completed rounds share a single
+    /// MPC fee, and only the number of correct votes is revealed. Each vote is checked
+    /// against its receipt and round history exactly as in the single-round version.
+    ///
+    /// For every vote, the remaining accounts must contain the caller's vote receipt for
+    /// the round followed by the round's history account, in the same order as `votes`.
+    ///
+    /// # Arguments
+    /// * `votes` - The votes to check, each with the round it was cast in
+    pub fn verify_winning_votes_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyWinningVotesBatch<'info>>,
+        computation_offset: u64,
+        votes: Vec<BatchedVerification>,
+    ) -> Result<()> {
+        require!(
+            !votes.is_empty() && votes.len() <= MAX_BATCH_VERIFICATIONS,
+            ErrorCode::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == votes.len() * 2,
+            ErrorCode::InvalidBatchSize
+        );
+
+        let system_acc_key = ctx.accounts.system_acc.key();
+        let mut rounds: Vec<u64> = Vec::with_capacity(votes.len());
+        let mut winners: Vec<u8> = Vec::with_capacity(votes.len());
+
+        for (verification, accounts) in votes.iter().zip(ctx.remaining_accounts.chunks(2)) {
+            let vote_receipt = &accounts[0];
+            let round_history = &accounts[1];
+            let round_id = verification.round_id;
+
+            // A round counted twice would inflate the score
+            require!(!rounds.contains(&round_id), ErrorCode::DuplicateBatchRound);
+            rounds.push(round_id);
+
+            require!(
+                round_id < ctx.accounts.round_metadata.current_round,
+                ErrorCode::InvalidRoundId
+            );
+
+            load_matching_vote_receipt(
+                &system_acc_key,
+                &ctx.accounts.payer.key(),
+                vote_receipt,
+                round_id,
+                verification.vote,
+                verification.vote_encryption_pubkey,
+                verification.vote_nonce,
+            )?;
+
+            let (expected_round_history_pda, _) = Pubkey::find_program_address(
+                &[
+                    b"voting_round_history",
+                    system_acc_key.as_ref(),
+                    &round_id.to_le_bytes(),
+                ],
+                &crate::ID,
+            );
+            require!(
+                round_history.key() == expected_round_history_pda,
+                ErrorCode::InvalidRoundId
+            );
+            let round_history_account = load_round_history(round_history)?;
+            winners.push(round_history_account.winning_proposal_id);
+        }
+
+        msg!(
+            "Verifying {} past votes for rounds {:?}",
+            votes.len(),
+            rounds
+        );
+
+        // The circuit always takes MAX_BATCH_VERIFICATIONS votes; unused slots repeat
+        // the first vote and are skipped because they sit at or past `count`
+        let mut args = Vec::new();
+        for i in 0..MAX_BATCH_VERIFICATIONS {
+            let verification = votes.get(i).unwrap_or(&votes[0]);
+            args.push(Argument::ArcisPubkey(verification.vote_encryption_pubkey));
+            args.push(Argument::PlaintextU128(verification.vote_nonce));
+            args.push(Argument::EncryptedU8(verification.vote));
+        }
+        for i in 0..MAX_BATCH_VERIFICATIONS {
+            args.push(Argument::PlaintextU8(
+                *winners.get(i).unwrap_or(&winners[0]),
+            ));
+        }
+        args.push(Argument::PlaintextU8(votes.len() as u8));
+
+        // The callback reports who was scored and for which rounds; the round ids are read
+        // back from the histories, in the same order as `votes`
+        let mut callback_accounts = vec![CallbackAccount {
+            pubkey: ctx.accounts.payer.key(),
+            is_writable: false,
+        }];
+        callback_accounts.extend(ctx.remaining_accounts.chunks(2).map(|accounts| {
+            CallbackAccount {
+                pubkey: accounts[1].key(),
+                is_writable: false,
+            }
+        }));
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![VerifyWinningVotesBatchCallback::callback_ix(
+                &callback_accounts,
+            )],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "verify_winning_votes_batch")]
+    pub fn verify_winning_votes_batch_callback(
+        ctx: Context<VerifyWinningVotesBatchCallback>,
+        output: ComputationOutputs<VerifyWinningVotesBatchOutput>,
+    ) -> Result<()> {
+        let o = match output {
+            ComputationOutputs::Success(VerifyWinningVotesBatchOutput { field_0 }) => field_0,
+            _ => return computation_aborted("verify_winning_votes_batch", None),
+        };
+
+        msg!(
+            "{} of {} votes were for the round winner",
+            o.field_0,
+            o.field_1
+        );
+
+        let mut rounds = Vec::with_capacity(ctx.remaining_accounts.len());
+        for round_history in ctx.remaining_accounts {
+            rounds.push(load_round_history(round_history)?.round_id);
+        }
+
+        emit!(BatchVerificationEvent {
+            voter: ctx.accounts.voter.key(),
+            rounds,
+            correct_count: o.field_0,
+            total: o.field_1,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_verify_not_winning_vote_comp_def(
+        ctx: Context<InitVerifyNotWinningVoteCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Proves that a cast vote was NOT for the winning proposal of a completed round.
+    ///
+    /// The mirror of `verify_winning_vote`, for "I dissented" proofs. Requires the caller's
+    /// vote receipt for the round and checks it stores the same encrypted vote, so the
+    /// proof cannot be made with an arbitrary ciphertext.
+    ///
+    /// # Arguments
+    /// * `vote` - The encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - The public key used to encrypt the vote
+    /// * `vote_nonce` - The nonce used for vote encryption
+    /// * `round_id` - The completed round the vote was cast in
+    pub fn verify_not_winning_vote(
+        ctx: Context<VerifyNotWinningVote>,
+        computation_offset: u64,
+        vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+    ) -> Result<()> {
+        msg!(
+            "Verifying if vote dissented from the winner of round {}",
+            round_id
+        );
+
+        load_matching_vote_receipt(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.vote_receipt,
+            round_id,
+            vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+        )?;
+
+        let winning_proposal_id = ctx.accounts.round_history.winning_proposal_id;
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),
+            Argument::PlaintextU8(winning_proposal_id),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![VerifyNotWinningVoteCallback::callback_ix(&[])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "verify_not_winning_vote")]
+    pub fn verify_not_winning_vote_callback(
+        _ctx: Context<VerifyNotWinningVoteCallback>,
+        output: ComputationOutputs<VerifyNotWinningVoteOutput>,
+    ) -> Result<()> {
+        let dissent = match output {
+            ComputationOutputs::Success(VerifyNotWinningVoteOutput { field_0 }) => field_0,
+            _ => return computation_aborted("verify_not_winning_vote", None),
+        };
+
+        if dissent {
+            msg!("✅ Vote was not for the winning proposal");
+        } else {
+            msg!("❌ Vote was for the winning proposal");
+        }
+
+        emit!(VoteVerificationEvent {
+            is_winning_vote: !dissent,
+            timestamp: Clock::get()?.unix_timestamp,
+            target_proposal_id: None,
+            dissent,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_verify_vote_in_set_comp_def(
+        ctx: Context<InitVerifyVoteInSetCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Proves that a cast vote was for one of a set of proposals without revealing which.
+    ///
+    /// Supports privacy-preserving eligibility proofs, e.g. "I backed a proposal in this
+    /// category". Like `verify_vote_for_proposal`, the caller's vote receipt for the round
+    /// must store the same encrypted vote.
+    ///
+    /// # Arguments
+    /// * `vote` - The encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - The public key used to encrypt the vote
+    /// * `vote_nonce` - The nonce used for vote encryption
+    /// * `round_id` - The round the vote was cast in
+    /// * `mask` - Bitmask of the proposals in the set (bit `i` = proposal `i`)
+    pub fn verify_vote_in_set(
+        ctx: Context<VerifyVoteInSet>,
+        computation_offset: u64,
+        vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+        mask: u32,
+    ) -> Result<()> {
+        msg!(
+            "Verifying if vote was in set {:#b} in round {}",
+            mask,
+            round_id
+        );
+
+        // An empty set proves nothing, and bits past the tally can never match
+        require!(
+            mask != 0 && mask >> MAX_PROPOSALS == 0,
+            ErrorCode::InvalidVoteSetMask
+        );
+
+        require!(
+            round_id <= ctx.accounts.round_metadata.current_round,
+            ErrorCode::InvalidRoundId
+        );
+
+        load_matching_vote_receipt(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.vote_receipt,
+            round_id,
+            vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+        )?;
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),
+            Argument::PlaintextU32(mask),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![VerifyVoteInSetCallback::callback_ix(&[])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "verify_vote_in_set")]
+    pub fn verify_vote_in_set_callback(
+        _ctx: Context<VerifyVoteInSetCallback>,
+        output: ComputationOutputs<VerifyVoteInSetOutput>,
+    ) -> Result<()> {
+        let result = match output {
+            ComputationOutputs::Success(VerifyVoteInSetOutput { field_0 }) => field_0,
+            _ => return computation_aborted("verify_vote_in_set", None),
+        };
+
+        let in_set = result.field_0;
+        let mask = result.field_1;
+
+        if in_set {
+            msg!("✅ Vote was for a proposal in set {:#b}", mask);
+        } else {
+            msg!("❌ Vote was not for any proposal in set {:#b}", mask);
+        }
+
+        emit!(VoteSetVerificationEvent {
+            in_set,
+            mask,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_verify_vote_for_proposal_comp_def(
+        ctx: Context<InitVerifyVoteForProposalCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Verifies whether a cast vote was for an arbitrary proposal.
+    ///
+    /// Generalizes `verify_winning_vote` to any target proposal, so a voter can prove
+    /// which proposal they backed. Requires the caller's vote receipt for the round and
+    /// checks it stores the same encrypted vote, key and nonce, so proofs cannot be
+    /// fabricated.
+    ///
+    /// # Arguments
+    /// * `vote` - The encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - The public key used to encrypt the vote
+    /// * `vote_nonce` - The nonce used for vote encryption
+    /// * `round_id` - The round the vote was cast in
+    /// * `target_proposal_id` - The proposal to check the vote against
+    pub fn verify_vote_for_proposal(
+        ctx: Context<VerifyVoteForProposal>,
+        computation_offset: u64,
+        vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+        target_proposal_id: u8,
+    ) -> Result<()> {
+        msg!(
+            "Verifying if vote was for proposal {} in round {}",
+            target_proposal_id,
+            round_id
+        );
+
+        require!(
+            round_id <= ctx.accounts.round_metadata.current_round,
+            ErrorCode::InvalidRoundId
+        );
+
+        load_matching_vote_receipt(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.vote_receipt,
+            round_id,
+            vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+        )?;
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),
+            Argument::PlaintextU8(target_proposal_id),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![VerifyVoteForProposalCallback::callback_ix(&[])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "verify_vote_for_proposal")]
+    pub fn verify_vote_for_proposal_callback(
+        _ctx: Context<VerifyVoteForProposalCallback>,
+        output: ComputationOutputs<VerifyVoteForProposalOutput>,
+    ) -> Result<()> {
+        let result = match output {
+            ComputationOutputs::Success(VerifyVoteForProposalOutput { field_0 }) => field_0,
+            _ => return computation_aborted("verify_vote_for_proposal", None),
+        };
+
+        let is_match = result.field_0;
+        let target_proposal_id = result.field_1;
+
+        if is_match {
+            msg!("✅ Vote was for proposal {}", target_proposal_id);
+        } else {
+            msg!("❌ Vote was not for proposal {}", target_proposal_id);
+        }
+
+        emit!(VoteVerificationEvent {
+            is_winning_vote: is_match,
+            timestamp: Clock::get()?.unix_timestamp,
+            target_proposal_id: Some(target_proposal_id),
+            dissent: false,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_claim_winning_vote_reward_comp_def(
+        ctx: Context<InitClaimWinningVoteRewardCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Registers a voter for a share of the reward pool for backing a round's winner.
+    ///
+    /// Runs the same MPC check as `verify_winning_vote`; if the vote was for the winner,
+    /// the callback marks the receipt registered and counts the voter. Tallies can be
+    /// weighted, so the winner's vote count says nothing about how many voters backed
+    /// it; instead the pool (`voter_reward_bps` of the fees collected) is split equally
+    /// across registered voters by `collect_winning_vote_reward` once
+    /// `REWARD_REGISTRATION_PERIOD` has passed since the round was recorded.
+    ///
+    /// # Arguments
+    /// * `vote` - The encrypted vote containing the proposal ID
+    /// * `vote_encryption_pubkey` - The public key used to encrypt the vote
+    /// * `vote_nonce` - The nonce used for vote encryption
+    /// * `round_id` - The completed round to claim for
+    pub fn claim_winning_vote_reward(
+        ctx: Context<ClaimWinningVoteReward>,
+        computation_offset: u64,
+        vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
+        round_id: u64,
+    ) -> Result<()> {
+        msg!("Claiming winning vote reward for round {}", round_id);
+
+        // Distribution leaves the unpaid pool in the escrow, so a closed escrow still pays
+        require!(
+            ctx.accounts.round_escrow.round_status != RoundStatus::Active,
+            ErrorCode::RoundEscrowNotCompleted
+        );
+        require!(
+            Clock::get()?.unix_timestamp <= reward_registration_end(&ctx.accounts.round_history),
+            ErrorCode::RewardRegistrationClosed
+        );
+
+        let vote_receipt_account = load_matching_vote_receipt(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.vote_receipt,
+            round_id,
+            vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+        )?;
+        require!(
+            !vote_receipt_account.reward_registered,
+            ErrorCode::RewardAlreadyClaimed
+        );
+
+        let winning_proposal_id = ctx.accounts.round_history.winning_proposal_id;
+
+        let args = vec![
+            Argument::ArcisPubkey(vote_encryption_pubkey),
+            Argument::PlaintextU128(vote_nonce),
+            Argument::EncryptedU8(vote),
+            Argument::PlaintextU8(winning_proposal_id),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![ClaimWinningVoteRewardCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_history.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_escrow.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.vote_receipt.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "claim_winning_vote_reward")]
+    pub fn claim_winning_vote_reward_callback(
+        ctx: Context<ClaimWinningVoteRewardCallback>,
+        output: ComputationOutputs<ClaimWinningVoteRewardOutput>,
+    ) -> Result<()> {
+        let is_winning_vote = match output {
+            ComputationOutputs::Success(ClaimWinningVoteRewardOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "claim_winning_vote_reward",
+                    Some(ctx.accounts.round_history.round_id),
+                )
+            }
+        };
+
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        emit!(VoteVerificationEvent {
+            is_winning_vote,
+            timestamp,
+            target_proposal_id: None,
+            dissent: false,
+        });
+
+        if !is_winning_vote {
+            msg!("❌ Vote was not for winning proposal - no reward");
+            return Ok(());
+        }
+
+        // A second claim may have been queued before this one landed
+        let mut vote_receipt_account = VoteReceiptAccount::load(&ctx.accounts.vote_receipt)?;
+        require!(
+            !vote_receipt_account.reward_registered,
+            ErrorCode::RewardAlreadyClaimed
+        );
+        require!(
+            vote_receipt_account.voter == ctx.accounts.claimant.key(),
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            timestamp <= reward_registration_end(&ctx.accounts.round_history),
+            ErrorCode::RewardRegistrationClosed
+        );
+
+        vote_receipt_account.reward_registered = true;
+        vote_receipt_account.store(&ctx.accounts.vote_receipt)?;
+
+        let round_escrow = &mut ctx.accounts.round_escrow;
+        round_escrow.winning_voters = round_escrow
+            .winning_voters
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "✅ Registered winning voter {}",
+            ctx.accounts.claimant.key()
+        );
+
+        emit!(WinningVoterRegisteredEvent {
+            voter: ctx.accounts.claimant.key(),
+            round_id: ctx.accounts.round_history.round_id,
+            winning_voters: round_escrow.winning_voters,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pays a registered winning voter their share of the round's voter reward pool.
+    ///
+    /// Only callable once reward registration has closed, so every share is the pool
+    /// divided by the final number of registered voters. Each receipt collects once.
+    ///
+    /// # Arguments
+    /// * `round_id` - The completed round to collect for
+    pub fn collect_winning_vote_reward(
+        ctx: Context<CollectWinningVoteReward>,
+        round_id: u64,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp > reward_registration_end(&ctx.accounts.round_history),
+            ErrorCode::RewardRegistrationOpen
+        );
+
+        let (expected_vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[
+                b"vote_receipt",
+                ctx.accounts.system_acc.key().as_ref(),
+                ctx.accounts.payer.key().as_ref(),
+                &round_id.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        require!(
+            ctx.accounts.vote_receipt.key() == expected_vote_receipt_pda,
+            ErrorCode::InvalidVoteReceipt
+        );
+
+        let mut vote_receipt_account = VoteReceiptAccount::load(&ctx.accounts.vote_receipt)?;
+        require!(
+            vote_receipt_account.reward_registered,
+            ErrorCode::RewardNotRegistered
+        );
+        require!(
+            !vote_receipt_account.reward_claimed,
+            ErrorCode::RewardAlreadyClaimed
+        );
+
+        // Split the voter pool evenly across every registered winning voter
+        let winning_voters = ctx.accounts.round_escrow.winning_voters;
+        require!(winning_voters > 0, ErrorCode::RewardNotRegistered);
+        let reward = ctx
+            .accounts
+            .round_escrow
+            .voter_reward_pool(ctx.accounts.system_acc.voter_reward_bps)
+            / winning_voters;
+
+        require!(
+            reward <= ctx.accounts.round_escrow.current_balance,
+            ErrorCode::InsufficientEscrowBalance
+        );
+
+        transfer_from_escrow(
+            &ctx.accounts.round_escrow.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            reward,
+        )?;
+
+        let round_escrow = &mut ctx.accounts.round_escrow;
+        round_escrow.current_balance = round_escrow
+            .current_balance
+            .checked_sub(reward)
+            .ok_or(ErrorCode::MathOverflow)?;
+        round_escrow.total_distributed = round_escrow
+            .total_distributed
+            .checked_add(reward)
+            .ok_or(ErrorCode::MathOverflow)?;
+        round_escrow.voter_rewards_paid = round_escrow
+            .voter_rewards_paid
+            .checked_add(reward)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        vote_receipt_account.reward_claimed = true;
+        vote_receipt_account.store(&ctx.accounts.vote_receipt)?;
+
+        msg!(
+            "✅ Paid {} lamports to winning voter {}",
+            reward,
+            ctx.accounts.payer.key()
+        );
+
+        emit!(VoteRewardClaimedEvent {
+            voter: ctx.accounts.payer.key(),
+            round_id,
+            amount: reward,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "vote_for_proposal")]
+    pub fn vote_for_proposal_callback(
+        ctx: Context<VoteForProposalCallback>,
+        output: ComputationOutputs<VoteForProposalOutput>,
+    ) -> Result<()> {
+        let o = match output {
+            ComputationOutputs::Success(VoteForProposalOutput { field_0 }) => field_0,
+            _ => {
+                // The vote was never counted; this is what lets `reissue_vote` retry it
+                record_tally_failed(&ctx.accounts.vote_receipt)?;
+                return computation_aborted(
+                    "vote_for_proposal",
+                    Some(ctx.accounts.round_metadata.current_round),
+                );
+            }
+        };
+
+        ctx.accounts.system_acc.proposal_votes = o.field_0.ciphertexts;
+        ctx.accounts.system_acc.nonce = o.field_0.nonce;
+
+        // The computation finished, so the receipt is no longer orphaned
+        set_awaiting_tally(&ctx.accounts.vote_receipt, false)?;
+
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        if !o.field_1 {
+            msg!("⚠️ Vote was not counted - proposal ID out of range or not seconded");
+        }
+        if o.field_2 {
+            msg!("⚠️ Vote was not counted - proposal reached the per-proposal vote cap");
+        }
+
+        emit!(VoteEvent {
+            timestamp: current_timestamp,
+            accepted: o.field_1,
+            rejected: o.field_2,
+            round_id: ctx.accounts.round_metadata.current_round,
+            total_voters: ctx.accounts.round_metadata.total_voters,
+            voter: ctx.accounts.voter.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn init_reveal_winner_comp_def(ctx: Context<InitRevealWinnerCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Queues the winner reveal for a round whose voting period has ended.
+    ///
+    /// Permissionless counterpart of `reveal_winning_proposal`, so a round can't hang
+    /// when the authority goes offline. Anyone can call it once the deadline has passed
+    /// and the round has met quorum; it runs the same reveal circuit and callback, so
+    /// the tally stays private until MPC finishes and the result is recorded exactly as
+    /// an authority reveal would. Co-authority approvals are still required when a
+    /// reveal threshold is set, and systems with a supermajority threshold must be
+    /// finalized through `finalize_expired_round_with_threshold`.
+    pub fn finalize_expired_round(
+        ctx: Context<FinalizeExpiredRound>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        let voting_deadline = validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            None,
+        )?;
+
+        // A plurality reveal would skip the supermajority the system requires
+        require!(
+            ctx.accounts.system_acc.threshold_pct == 0 || ctx.accounts.system_acc.net_voting,
+            ErrorCode::ThresholdRevealRequired
+        );
+
+        emit!(VotingPeriodEndedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            voting_deadline,
+        });
+
+        msg!(
+            "Finalizing expired round {} on behalf of {}",
+            ctx.accounts.round_metadata.current_round,
+            ctx.accounts.payer.key()
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU8(ctx.accounts.round_metadata.proposals_in_current_round),
+        ];
+
+        ctx.accounts.system_acc.reveal_in_progress = true;
+        ctx.accounts.system_acc.reveal_queued_at = Clock::get()?.unix_timestamp;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealWinningProposalCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    /// Reveals the winning proposal with the most votes.
+    ///
+    /// Only the system authority can call this function to decrypt and reveal the vote tallies.
+    /// The MPC computation finds the proposal with the maximum votes and returns its ID and vote count.
+    /// Creates a voting round history account to permanently store the results.
+    /// Supermajority systems must be revealed with `reveal_winning_proposal_with_threshold`.
+    ///
+    /// # Arguments
+    /// * `system_id` - The system ID to reveal results for
+    pub fn reveal_winning_proposal(
+        ctx: Context<RevealWinningProposal>,
+        computation_offset: u64,
+        _system_id: u32,
+    ) -> Result<()> {
+        let voting_deadline = validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            Some(&ctx.accounts.payer.key()),
+        )?;
+
+        // A plurality reveal would skip the supermajority the system requires
+        require!(
+            ctx.accounts.system_acc.threshold_pct == 0 || ctx.accounts.system_acc.net_voting,
+            ErrorCode::ThresholdRevealRequired
+        );
+
+        emit!(VotingPeriodEndedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            voting_deadline,
+        });
+
+        msg!("Revealing winning proposal for round {}", ctx.accounts.round_metadata.current_round);
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU8(ctx.accounts.round_metadata.proposals_in_current_round),
+        ];
+
+        ctx.accounts.system_acc.reveal_in_progress = true;
+        ctx.accounts.system_acc.reveal_queued_at = Clock::get()?.unix_timestamp;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealWinningProposalCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_winning_proposal")]
+    pub fn reveal_winning_proposal_callback(
+        ctx: Context<RevealWinningProposalCallback>,
+        output: ComputationOutputs<RevealWinningProposalOutput>,
+    ) -> Result<()> {
+        // The queued reveal is settled either way; an aborted one must not block a retry
+        ctx.accounts.system_acc.reveal_in_progress = false;
+
+        let result = match output {
+            ComputationOutputs::Success(RevealWinningProposalOutput { field_0 }) => field_0,
+            _ => {
+                msg!(
+                    "Reveal computation aborted - round {} can be revealed again",
+                    ctx.accounts.round_metadata.current_round
+                );
+                return computation_aborted(
+                    "reveal_winning_proposal",
+                    Some(ctx.accounts.round_metadata.current_round),
+                );
+            }
+        };
+        
+        let winning_proposal_id = result.field_0;
+        let net_score = result.field_1;
+        let tied_mask = result.field_2;
+        let margin = result.field_3;
+        let all_negative = result.field_4;
+
+        // Every counter was zero (e.g. all ballots were rejected), so nothing won.
+        // The round stays open for reveal; the authority can cancel it instead.
+        if winning_proposal_id == u8::MAX {
+            msg!(
+                "No votes were tallied in round {} - no winner recorded",
+                ctx.accounts.round_metadata.current_round
+            );
+            emit!(NoWinnerEvent {
+                round_id: ctx.accounts.round_metadata.current_round,
+            });
+            return Ok(());
+        }
+
+        record_winner(
+            &mut ctx.accounts.system_acc,
+            &mut ctx.accounts.round_metadata,
+            winning_proposal_id,
+            net_score,
+            tied_mask,
+            margin,
+            all_negative,
+        )
+    }
+
+    /// Re-initializes the shared signer PDA used to queue MPC computations.
+    ///
+    /// Every queueing instruction creates `sign_pda_account` with `init_if_needed` and
+    /// writes its bump. If a failed `queue_computation` leaves the account partially
+    /// set up, the system authority can call this to create it if missing and rewrite
+    /// its bump from the canonical derivation.
+    pub fn reset_sign_pda(ctx: Context<ResetSignPda>) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        msg!(
+            "Sign PDA {} reset with bump {}",
+            ctx.accounts.sign_pda_account.key(),
+            ctx.bumps.sign_pda_account
+        );
+
+        Ok(())
+    }
+
+    /// Switches proposal submission fees between SOL and an SPL token.
+    ///
+    /// With a fee mint set, `proposal_submission_fee` is charged in that token's base
+    /// units and moved into a token account owned by the round escrow. Only the system
+    /// authority can switch, and only before the current round's first proposal, so
+    /// each round's fees are paid in a single currency. Payouts and refunds from a
+    /// token-fee escrow are token transfers signed by the escrow, so those instructions
+    /// then take the escrow's and each recipient's fee token accounts. The voter reward
+    /// pool is a share of SOL fees only.
+    ///
+    /// The fee amount is kept as is, so set it for the new currency with
+    /// `set_submission_fee`. Switching back to SOL requires the current fee to meet the
+    /// SOL floor.
+    ///
+    /// # Arguments
+    /// * `fee_mint` - Token mint for fees, or None to charge SOL
+    pub fn set_fee_mint(ctx: Context<SetFeeMint>, fee_mint: Option<Pubkey>) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            ctx.accounts.round_metadata.proposals_in_current_round == 0,
+            ErrorCode::ProposalsAlreadySubmitted
+        );
+        if fee_mint.is_none() {
+            check_sol_fee_floor(ctx.accounts.system_acc.proposal_submission_fee)?;
+        }
+
+        ctx.accounts.system_acc.fee_mint = fee_mint;
+
+        msg!("Submission fee mint set to {:?}", fee_mint);
+
+        emit!(FeeMintUpdatedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            fee_mint,
+        });
+
+        Ok(())
+    }
+
+    /// Configures the co-authorities that must approve reveals.
+    ///
+    /// With a non-zero threshold, `reveal_winning_proposal` and
+    /// `reveal_winning_proposal_with_threshold` only queue once `threshold` distinct
+    /// co-authorities have called `approve_reveal` for the current round. Only the
+    /// system authority can change the set, and while a threshold is in force it also
+    /// needs that many approvals for the current round, so it can't lower the threshold
+    /// to 0 and reveal alone.
+    ///
+    /// # Arguments
+    /// * `authorities` - Co-authority keys (at most `MAX_REVEAL_AUTHORITIES`, no duplicates)
+    /// * `threshold` - Approvals required per round, or 0 to let the authority reveal alone
+    pub fn set_reveal_authorities(
+        ctx: Context<SetRevealAuthorities>,
+        authorities: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            authorities.len() <= MAX_REVEAL_AUTHORITIES,
+            ErrorCode::TooManyRevealAuthorities
+        );
+        for (i, authority) in authorities.iter().enumerate() {
+            require!(
+                !authorities[..i].contains(authority),
+                ErrorCode::DuplicateRevealAuthority
+            );
+        }
+        require!(
+            threshold as usize <= authorities.len(),
+            ErrorCode::InvalidRevealThreshold
+        );
+
+        let system_acc = &ctx.accounts.system_acc;
+        if system_acc.reveal_threshold > 0 {
+            let reveal_approval = ctx
+                .accounts
+                .reveal_approval
+                .as_deref()
+                .ok_or(ErrorCode::RevealNotApproved)?;
+            require!(
+                reveal_approval.round_id == ctx.accounts.round_metadata.current_round,
+                ErrorCode::RevealNotApproved
+            );
+            require!(
+                count_reveal_approvals(system_acc, reveal_approval)
+                    >= system_acc.reveal_threshold as usize,
+                ErrorCode::RevealNotApproved
+            );
+        }
+
+        msg!(
+            "Reveal authorities set: {} keys, threshold {}",
+            authorities.len(),
+            threshold
+        );
+
+        ctx.accounts.system_acc.authorities = authorities;
+        ctx.accounts.system_acc.reveal_threshold = threshold;
+
+        Ok(())
+    }
+
+    /// Records a co-authority's approval to reveal the current round's results.
+    ///
+    /// Approvals live in a per-round account, so they start from zero every round.
+    /// Each co-authority can approve a round once.
+    pub fn approve_reveal(ctx: Context<ApproveReveal>) -> Result<()> {
+        let approver = ctx.accounts.payer.key();
+        require!(
+            ctx.accounts.system_acc.authorities.contains(&approver),
+            ErrorCode::InvalidAuthority
+        );
+
+        let current_round = ctx.accounts.round_metadata.current_round;
+        let reveal_approval = &mut ctx.accounts.reveal_approval;
+        reveal_approval.bump = ctx.bumps.reveal_approval;
+        reveal_approval.round_id = current_round;
+        require!(
+            !reveal_approval.approvers.contains(&approver),
+            ErrorCode::RevealAlreadyApproved
+        );
+        reveal_approval.approvers.push(approver);
+
+        let approvals =
+            count_reveal_approvals(&ctx.accounts.system_acc, &ctx.accounts.reveal_approval);
+
+        emit!(RevealApprovedEvent {
+            round_id: current_round,
+            approver,
+            approvals: approvals as u8,
+            threshold: ctx.accounts.system_acc.reveal_threshold,
+        });
+
+        Ok(())
+    }
+
+    /// Sets the minimum number of slots between two votes from the same key in a round.
+    ///
+    /// Applies to `vote_for_proposal` and `change_vote`. Only the system authority can
+    /// change it; 0 disables throttling.
+    ///
+    /// # Arguments
+    /// * `cooldown_slots` - Slots a voter must wait after voting before voting again
+    pub fn set_rate_limit(ctx: Context<SetRateLimit>, cooldown_slots: u64) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        ctx.accounts.system_acc.rate_limit = RateLimitConfig { cooldown_slots };
+
+        msg!("Vote cooldown set to {} slots", cooldown_slots);
+
+        emit!(RateLimitUpdatedEvent { cooldown_slots });
+
+        Ok(())
+    }
+
+    /// Allows or forbids the system authority to vote.
+    ///
+    /// The authority can decrypt and reveal results, so it is barred from voting by
+    /// default to avoid a conflict of interest. Only the authority can change this.
+    pub fn set_authority_can_vote(ctx: Context<SetAuthorityCanVote>, enabled: bool) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        ctx.accounts.system_acc.authority_can_vote = enabled;
+
+        msg!(
+            "Authority voting {}",
+            if enabled { "allowed" } else { "forbidden" }
+        );
+
+        emit!(AuthorityCanVoteUpdatedEvent { enabled });
+
+        Ok(())
+    }
+
+    /// Sets the vote share a winner needs in `reveal_winning_proposal_with_threshold`.
+    ///
+    /// Only the system authority can change it.
+    ///
+    /// # Arguments
+    /// * `threshold_pct` - Required share of the votes, in percent (1-100)
+    pub fn set_threshold_pct(ctx: Context<SetThresholdPct>, threshold_pct: u8) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            threshold_pct > 0 && threshold_pct <= 100,
+            ErrorCode::InvalidThreshold
+        );
+
+        ctx.accounts.system_acc.threshold_pct = threshold_pct;
+
+        msg!("Supermajority threshold set to {}%", threshold_pct);
+
+        emit!(ThresholdUpdatedEvent { threshold_pct });
+
+        Ok(())
+    }
+
+    pub fn init_reveal_winner_with_threshold_comp_def(
+        ctx: Context<InitRevealWinnerWithThresholdCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reveals the winning proposal only if it reaches the configured supermajority.
+    ///
+    /// Subject to the same checks as `reveal_winning_proposal`. If the leader holds at
+    /// least `system_acc.threshold_pct` percent of the votes, the round completes
+    /// exactly as with a plurality reveal. Otherwise no winner is recorded, the round
+    /// stays open for another reveal or cancellation, and `supermajority_met` is set
+    /// to `Some(false)` so clients can show that the vote failed to reach it. Not
+    /// available with net voting, where vote shares are undefined.
+    pub fn reveal_winning_proposal_with_threshold(
+        ctx: Context<RevealWinningProposalWithThreshold>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        let voting_deadline = validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            Some(&ctx.accounts.payer.key()),
+        )?;
+
+        require!(
+            !ctx.accounts.system_acc.net_voting,
+            ErrorCode::NetVotingEnabled
+        );
+        require!(
+            ctx.accounts.system_acc.threshold_pct > 0,
+            ErrorCode::InvalidThreshold
+        );
+
+        emit!(VotingPeriodEndedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            voting_deadline,
+        });
+
+        msg!(
+            "Revealing winning proposal for round {} with a {}% threshold",
+            ctx.accounts.round_metadata.current_round,
+            ctx.accounts.system_acc.threshold_pct
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU8(ctx.accounts.round_metadata.proposals_in_current_round),
+            Argument::PlaintextU8(ctx.accounts.system_acc.threshold_pct),
+        ];
+
+        ctx.accounts.system_acc.reveal_in_progress = true;
+        ctx.accounts.system_acc.reveal_queued_at = Clock::get()?.unix_timestamp;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealWinnerWithThresholdCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    /// Permissionless counterpart of `reveal_winning_proposal_with_threshold`.
+    ///
+    /// Anyone can call it once the deadline has passed and the round has met quorum,
+    /// subject to the same approvals as `finalize_expired_round`. It runs the threshold
+    /// circuit and callback, so a leader short of the supermajority is not recorded as
+    /// the winner.
+    pub fn finalize_expired_round_with_threshold(
+        ctx: Context<FinalizeExpiredRoundWithThreshold>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        let voting_deadline = validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            None,
+        )?;
+
+        require!(
+            !ctx.accounts.system_acc.net_voting,
+            ErrorCode::NetVotingEnabled
+        );
+        require!(
+            ctx.accounts.system_acc.threshold_pct > 0,
+            ErrorCode::InvalidThreshold
+        );
+
+        emit!(VotingPeriodEndedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            voting_deadline,
+        });
+
+        msg!(
+            "Finalizing expired round {} with a {}% threshold on behalf of {}",
+            ctx.accounts.round_metadata.current_round,
+            ctx.accounts.system_acc.threshold_pct,
+            ctx.accounts.payer.key()
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU8(ctx.accounts.round_metadata.proposals_in_current_round),
+            Argument::PlaintextU8(ctx.accounts.system_acc.threshold_pct),
+        ];
+
+        ctx.accounts.system_acc.reveal_in_progress = true;
+        ctx.accounts.system_acc.reveal_queued_at = Clock::get()?.unix_timestamp;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealWinnerWithThresholdCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_winner_with_threshold")]
+    pub fn reveal_winner_with_threshold_callback(
+        ctx: Context<RevealWinnerWithThresholdCallback>,
+        output: ComputationOutputs<RevealWinnerWithThresholdOutput>,
+    ) -> Result<()> {
+        // The queued reveal is settled either way; an aborted one must not block a retry
+        ctx.accounts.system_acc.reveal_in_progress = false;
+
+        let result = match output {
+            ComputationOutputs::Success(RevealWinnerWithThresholdOutput { field_0 }) => field_0,
+            _ => {
+                msg!(
+                    "Reveal computation aborted - round {} can be revealed again",
+                    ctx.accounts.round_metadata.current_round
+                );
+                return computation_aborted(
+                    "reveal_winner_with_threshold",
+                    Some(ctx.accounts.round_metadata.current_round),
+                );
+            }
+        };
+
+        let threshold_met = result.field_4;
+        ctx.accounts.system_acc.supermajority_met = Some(threshold_met);
+
+        if !threshold_met {
+            msg!(
+                "Round {} failed to reach the {}% supermajority - no winner recorded",
+                ctx.accounts.round_metadata.current_round,
+                ctx.accounts.system_acc.threshold_pct
+            );
+            emit!(SupermajorityNotReachedEvent {
+                round_id: ctx.accounts.round_metadata.current_round,
+                threshold_pct: ctx.accounts.system_acc.threshold_pct,
+            });
+            return Ok(());
+        }
+
+        record_winner(
+            &mut ctx.accounts.system_acc,
+            &mut ctx.accounts.round_metadata,
+            result.field_0,
+            result.field_1,
+            result.field_2,
+            result.field_3,
+            false,
+        )
+    }
+
+    pub fn init_reveal_top_n_comp_def(ctx: Context<InitRevealTopNCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reveals a ranked leaderboard of the top `n` proposals in the current round.
+    ///
+    /// Gated by `validate_reveal` like `reveal_winning_proposal`. `n` is capped at the
+    /// number of proposals in the round; unused leaderboard slots are padded with
+    /// sentinel entries by the MPC computation.
+    ///
+    /// # Arguments
+    /// * `n` - Number of leaderboard entries to reveal
+    pub fn reveal_top_proposals(
+        ctx: Context<RevealTopProposals>,
+        computation_offset: u64,
+        n: u8,
+    ) -> Result<()> {
+        validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            Some(&ctx.accounts.payer.key()),
+        )?;
+
+        // Never rank more entries than there are proposals in the round
+        let n = n.min(ctx.accounts.round_metadata.proposals_in_current_round);
+
+        msg!(
+            "Revealing top {} proposals for round {}",
+            n,
+            ctx.accounts.round_metadata.current_round
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU8(n),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealTopNCallback::callback_ix(&[CallbackAccount {
+                pubkey: ctx.accounts.round_metadata.key(),
+                is_writable: false,
+            }])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_top_n")]
+    pub fn reveal_top_n_callback(
+        ctx: Context<RevealTopNCallback>,
+        output: ComputationOutputs<RevealTopNOutput>,
+    ) -> Result<()> {
+        let result = match output {
+            ComputationOutputs::Success(RevealTopNOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "reveal_top_n",
+                    Some(ctx.accounts.round_metadata.current_round),
+                )
+            }
+        };
+
+        let proposal_ids = result.field_0;
+        let vote_counts = result.field_1;
+
+        emit!(TopProposalsEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            proposal_ids,
+            vote_counts,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_reveal_category_winner_comp_def(
+        ctx: Context<InitRevealCategoryWinnerCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reveals the leading proposal within one category of the current round.
+    ///
+    /// For multi-track rounds, e.g. "funding" vs "policy". Gated by `validate_reveal`
+    /// like `reveal_winning_proposal`. The round itself is not completed;
+    /// the overall winner is still revealed with `reveal_winning_proposal`.
+    ///
+    /// # Arguments
+    /// * `category` - Category whose leader to reveal
+    pub fn reveal_category_winner(
+        ctx: Context<RevealCategoryWinner>,
+        computation_offset: u64,
+        category: u8,
+    ) -> Result<()> {
+        validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            Some(&ctx.accounts.payer.key()),
+        )?;
+
+        let round_metadata = &ctx.accounts.round_metadata;
+        let category_mask = round_metadata
+            .proposal_categories
+            .iter()
+            .take(round_metadata.proposals_in_current_round as usize)
+            .enumerate()
+            .filter(|(_, &c)| c == category)
+            .fold(0u32, |mask, (i, _)| mask | (1 << i));
+        require!(category_mask != 0, ErrorCode::EmptyCategory);
+
+        msg!(
+            "Revealing category {} winner for round {}",
+            category,
+            round_metadata.current_round
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU32(category_mask),
+            Argument::PlaintextU8(category),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealCategoryWinnerCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_category_winner")]
+    pub fn reveal_category_winner_callback(
+        ctx: Context<RevealCategoryWinnerCallback>,
+        output: ComputationOutputs<RevealCategoryWinnerOutput>,
+    ) -> Result<()> {
+        let result = match output {
+            ComputationOutputs::Success(RevealCategoryWinnerOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "reveal_category_winner",
+                    Some(ctx.accounts.round_metadata.current_round),
+                )
+            }
+        };
+
+        emit!(CategoryWinnerEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            category: result.field_3,
+            winning_proposal_id: result.field_0,
+            vote_count: result.field_1,
+            is_tie: result.field_2,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_reveal_total_votes_comp_def(
+        ctx: Context<InitRevealTotalVotesCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reveals the total number of votes tallied in the encrypted counters.
+    ///
+    /// Only the system authority can call this. In one-vote-per-voter systems the revealed
+    /// total can be cross-checked against `round_metadata.total_voters` to detect dropped or
+    /// double-counted votes; weighted, approval and net tallies don't count voters, so the
+    /// check is skipped for them.
+    pub fn reveal_total_votes(
+        ctx: Context<RevealTotalVotes>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        msg!(
+            "Revealing total votes for round {}",
+            ctx.accounts.round_metadata.current_round
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealTotalVotesCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_total_votes")]
+    pub fn reveal_total_votes_callback(
+        ctx: Context<RevealTotalVotesCallback>,
+        output: ComputationOutputs<RevealTotalVotesOutput>,
+    ) -> Result<()> {
+        let total_votes = match output {
+            ComputationOutputs::Success(RevealTotalVotesOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "reveal_total_votes",
+                    Some(ctx.accounts.round_metadata.current_round),
+                )
+            }
+        };
+
+        let total_voters = ctx.accounts.round_metadata.total_voters;
+        let system_acc = &ctx.accounts.system_acc;
+        let one_vote_per_voter = system_acc.governance_mint.is_none()
+            && !system_acc.approval_voting
+            && !system_acc.net_voting;
+        if one_vote_per_voter && total_votes != total_voters as i64 {
+            msg!(
+                "⚠️ Tallied votes ({}) do not match recorded voters ({})",
+                total_votes,
+                total_voters
+            );
+        }
+
+        emit!(TotalVotesRevealedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            total_votes,
+            total_voters,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_reveal_all_tallies_comp_def(
+        ctx: Context<InitRevealAllTalliesCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reveals the vote count of every proposal in the current round.
+    ///
+    /// Gated by `validate_reveal` like `reveal_winning_proposal`, including co-authority
+    /// approvals, and only before the winner is revealed, so partial tallies never leak
+    /// mid-round.
+    pub fn reveal_all_tallies(
+        ctx: Context<RevealAllTallies>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            Some(&ctx.accounts.payer.key()),
+        )?;
+
+        msg!(
+            "Revealing all tallies for round {}",
+            ctx.accounts.round_metadata.current_round
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealAllTalliesCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_all_tallies")]
+    pub fn reveal_all_tallies_callback(
+        ctx: Context<RevealAllTalliesCallback>,
+        output: ComputationOutputs<RevealAllTalliesOutput>,
+    ) -> Result<()> {
+        let counts = match output {
+            ComputationOutputs::Success(RevealAllTalliesOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "reveal_all_tallies",
+                    Some(
+                        ctx.accounts
+                            .round_metadata
+                            .tally_round(ctx.accounts.system_acc.winning_proposal_id.is_some()),
+                    ),
+                )
+            }
+        };
+
+        let round_id = ctx
+            .accounts
+            .round_metadata
+            .tally_round(ctx.accounts.system_acc.winning_proposal_id.is_some());
+
+        ctx.accounts.round_metadata.revealed_tallies = Some(counts);
+        ctx.accounts.round_metadata.revealed_tallies_round = round_id;
+
+        emit!(AllTalliesRevealedEvent { counts, round_id });
+
+        Ok(())
+    }
+
+    pub fn init_reveal_single_proposal_count_comp_def(
+        ctx: Context<InitRevealSingleProposalCountCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reveals the vote count of one proposal without revealing any other counter.
+    ///
+    /// Supports pass/fail proposals that only need to reach a fixed number of votes.
+    /// Gated like `reveal_all_tallies`.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal in the tallied round
+    pub fn reveal_single_proposal_count(
+        ctx: Context<RevealSingleProposalCount>,
+        computation_offset: u64,
+        proposal_id: u8,
+    ) -> Result<()> {
+        validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            Some(&ctx.accounts.payer.key()),
+        )?;
+
+        msg!(
+            "Revealing vote count of proposal {} for round {}",
+            proposal_id,
+            ctx.accounts.proposal_acc.round_id
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU8(proposal_id),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealSingleProposalCountCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.proposal_acc.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_single_proposal_count")]
+    pub fn reveal_single_proposal_count_callback(
+        ctx: Context<RevealSingleProposalCountCallback>,
+        output: ComputationOutputs<RevealSingleProposalCountOutput>,
+    ) -> Result<()> {
+        let count = match output {
+            ComputationOutputs::Success(RevealSingleProposalCountOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "reveal_single_proposal_count",
+                    Some(ctx.accounts.proposal_acc.round_id),
+                )
+            }
+        };
+
+        emit!(SingleCountRevealedEvent {
+            proposal_id: ctx.accounts.proposal_acc.id,
+            count,
+            round_id: ctx.accounts.proposal_acc.round_id,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_reveal_vote_concentration_comp_def(
+        ctx: Context<InitRevealVoteConcentrationCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reveals how concentrated the tallied round's votes are, as a scaled Herfindahl
+    /// index (10_000 = every vote went to one proposal, 0 = no votes).
+    ///
+    /// A fairness metric for research that exposes no individual counter. Gated like
+    /// `reveal_single_proposal_count`.
+    pub fn reveal_vote_concentration(
+        ctx: Context<RevealVoteConcentration>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            Some(&ctx.accounts.payer.key()),
+        )?;
+
+        msg!(
+            "Revealing vote concentration for round {}",
+            ctx.accounts
+                .round_metadata
+                .tally_round(ctx.accounts.system_acc.winning_proposal_id.is_some())
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealVoteConcentrationCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.system_acc.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_metadata.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_vote_concentration")]
+    pub fn reveal_vote_concentration_callback(
+        ctx: Context<RevealVoteConcentrationCallback>,
+        output: ComputationOutputs<RevealVoteConcentrationOutput>,
+    ) -> Result<()> {
+        let concentration = match output {
+            ComputationOutputs::Success(RevealVoteConcentrationOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "reveal_vote_concentration",
+                    Some(
+                        ctx.accounts
+                            .round_metadata
+                            .tally_round(ctx.accounts.system_acc.winning_proposal_id.is_some()),
+                    ),
+                )
+            }
+        };
+
+        let round_id = ctx
+            .accounts
+            .round_metadata
+            .tally_round(ctx.accounts.system_acc.winning_proposal_id.is_some());
+
+        msg!("Round {} vote concentration: {}", round_id, concentration);
+
+        emit!(ConcentrationRevealedEvent {
+            round_id,
+            concentration,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_reveal_winner_to_recipient_comp_def(
+        ctx: Context<InitRevealWinnerToRecipientCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Determines the round winner but encrypts it to a designated recipient instead of
+    /// revealing it.
+    ///
+    /// For semi-private governance: the winner and its count are stored on the round's
+    /// `SharedWinnerAccount` as ciphertexts only the holder of `recipient_pubkey` (e.g.
+    /// an oversight committee) can decrypt. The round is not advanced and nothing is
+    /// paid out. Gated by `validate_reveal` like `reveal_winning_proposal`, including
+    /// co-authority approvals.
+    ///
+    /// # Arguments
+    /// * `recipient_pubkey` - Recipient's x25519 public key
+    /// * `recipient_nonce` - Nonce the result is encrypted under
+    pub fn reveal_winner_to_recipient(
+        ctx: Context<RevealWinnerToRecipient>,
+        computation_offset: u64,
+        recipient_pubkey: [u8; 32],
+        recipient_nonce: u128,
+    ) -> Result<()> {
+        validate_reveal(
+            &ctx.accounts.system_acc,
+            &ctx.accounts.round_metadata,
+            ctx.accounts.reveal_approval.as_deref(),
+            Some(&ctx.accounts.payer.key()),
+        )?;
+
+        let round_id = ctx.accounts.round_metadata.current_round;
+        msg!(
+            "Encrypting winner of round {} to the designated recipient",
+            round_id
+        );
+
+        let shared_winner = &mut ctx.accounts.shared_winner;
+        shared_winner.bump = ctx.bumps.shared_winner;
+        shared_winner.round_id = round_id;
+        shared_winner.recipient_pubkey = recipient_pubkey;
+        shared_winner.nonce = 0;
+        shared_winner.ciphertexts = [[0; 32]; 2];
+        shared_winner.revealed_at = None;
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.system_acc.nonce),
+            Argument::Account(
+                ctx.accounts.system_acc.key(),
+                PROPOSAL_VOTES_OFFSET,
+                PROPOSAL_VOTES_LEN,
+            ),
+            Argument::PlaintextU8(ctx.accounts.round_metadata.proposals_in_current_round),
+            Argument::ArcisPubkey(recipient_pubkey),
+            Argument::PlaintextU128(recipient_nonce),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealWinnerToRecipientCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.shared_winner.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_winner_to_recipient")]
+    pub fn reveal_winner_to_recipient_callback(
+        ctx: Context<RevealWinnerToRecipientCallback>,
+        output: ComputationOutputs<RevealWinnerToRecipientOutput>,
+    ) -> Result<()> {
+        let o = match output {
+            ComputationOutputs::Success(RevealWinnerToRecipientOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "reveal_winner_to_recipient",
+                    Some(ctx.accounts.shared_winner.round_id),
+                )
+            }
+        };
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let shared_winner = &mut ctx.accounts.shared_winner;
+        shared_winner.ciphertexts = o.ciphertexts;
+        shared_winner.nonce = o.nonce;
+        shared_winner.revealed_at = Some(timestamp);
+
+        emit!(WinnerSharedEvent {
+            round_id: shared_winner.round_id,
+            recipient_pubkey: shared_winner.recipient_pubkey,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Copies a revealed vote count onto its public `ProposalAccount`.
+    ///
+    /// Only the system authority can call this, and the count must match the one
+    /// stored by the latest `reveal_all_tallies` for the proposal's round.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal within the revealed round
+    /// * `vote_count` - Revealed vote count for the proposal
+    pub fn sync_proposal_vote_count(
+        ctx: Context<SyncProposalVoteCount>,
+        proposal_id: u8,
+        vote_count: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        let revealed_tallies = ctx
+            .accounts
+            .round_metadata
+            .revealed_tallies
+            .ok_or(ErrorCode::NoTalliesRevealed)?;
+        require!(
+            (proposal_id as usize) < revealed_tallies.len(),
+            ErrorCode::InvalidProposalId
+        );
+        require!(
+            revealed_tallies[proposal_id as usize] == vote_count as i64,
+            ErrorCode::RevealedCountMismatch
+        );
+
+        ctx.accounts.proposal_acc.vote_count = vote_count;
+        ctx.accounts.proposal_acc.count_synced = true;
+
+        emit!(ProposalCountSyncedEvent {
+            proposal_id,
+            round_id: ctx.accounts.round_metadata.revealed_tallies_round,
+            vote_count,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_reveal_ranked_winner_comp_def(
+        ctx: Context<InitRevealRankedWinnerCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Runs instant-runoff elimination over the round's ranked ballots.
+    ///
+    /// Only the system authority can call this, once voting has closed and before the
+    /// plurality winner is revealed (which advances the round). Each elimination round
+    /// counts every ballot toward its highest-ranked remaining proposal; exhausted
+    /// ballots drop out of the count. A proposal with a strict majority of the
+    /// remaining ballots wins, otherwise the proposal with the fewest votes is
+    /// eliminated, ties going against the highest proposal ID.
+    pub fn reveal_ranked_winner(
+        ctx: Context<RevealRankedWinner>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        let voting_deadline = ctx
+            .accounts
+            .round_metadata
+            .voting_deadline(ctx.accounts.system_acc.voting_duration);
+        require!(
+            Clock::get()?.unix_timestamp > voting_deadline,
+            ErrorCode::VotingStillOpen
+        );
+
+        require!(
+            ctx.accounts.ranked_votes.ballot_count > 0,
+            ErrorCode::NoRankedBallots
+        );
+
+        msg!(
+            "Revealing ranked-choice winner for round {} from {} ballots",
+            ctx.accounts.ranked_votes.round_id,
+            ctx.accounts.ranked_votes.ballot_count
+        );
+
+        let args = vec![
+            Argument::PlaintextU128(ctx.accounts.ranked_votes.nonce),
+            Argument::Account(
+                ctx.accounts.ranked_votes.key(),
+                RANKED_BALLOTS_OFFSET,
+                32 * RANKED_BALLOT_CIPHERTEXTS as u32,
+            ),
+            Argument::PlaintextU8(ctx.accounts.ranked_votes.ballot_count),
+            Argument::PlaintextU8(ctx.accounts.round_metadata.proposals_in_current_round),
+        ];
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealRankedWinnerCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.ranked_votes.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_ranked_winner")]
+    pub fn reveal_ranked_winner_callback(
+        ctx: Context<RevealRankedWinnerCallback>,
+        output: ComputationOutputs<RevealRankedWinnerOutput>,
+    ) -> Result<()> {
+        let result = match output {
+            ComputationOutputs::Success(RevealRankedWinnerOutput { field_0 }) => field_0,
+            _ => {
+                return computation_aborted(
+                    "reveal_ranked_winner",
+                    Some(ctx.accounts.ranked_votes.round_id),
+                )
+            }
+        };
+
+        let winning_proposal_id = result.field_0;
+        let elimination_rounds = result.field_1;
+
+        msg!(
+            "Ranked-choice winner for round {}: Proposal {} after {} rounds",
+            ctx.accounts.ranked_votes.round_id,
+            winning_proposal_id,
+            elimination_rounds
+        );
+
+        emit!(RankedWinnerEvent {
+            round_id: ctx.accounts.ranked_votes.round_id,
+            winning_proposal_id,
+            elimination_rounds,
+        });
+
+        Ok(())
+    }
+
+    /// Creates a voting round history account after a winner has been revealed.
+    /// This is called separately from the reveal callback to avoid MXE complexity.
+    /// All data is read from the system state to prevent tampering.
+    ///
+    /// The next round's zeroed tally is encrypted through MPC under `new_nonce`, and
+    /// votes open once the `init_proposal_votes` callback stores it.
+    ///
+    /// # Arguments
+    /// * `computation_offset` - Unique MPC computation offset for the tally reset
+    /// * `new_nonce` - Nonce the next round's zeroed tally is encrypted under; must
+    ///   differ from the current nonce so no ciphertext context is ever reused
+    pub fn create_round_history(
+        ctx: Context<CreateRoundHistory>,
+        computation_offset: u64,
+        new_nonce: u128,
+    ) -> Result<()> {
+        // Verify that the caller is the system authority
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        require!(
+            new_nonce != ctx.accounts.system_acc.nonce,
+            ErrorCode::NonceNotRotated
+        );
+
+        // History is recorded for the round before current_round; there is none yet
+        require!(
+            ctx.accounts.round_metadata.current_round > 0,
+            ErrorCode::NoCompletedRound
+        );
+
+        // Verify that a winner has been revealed
+        require!(
+            ctx.accounts.system_acc.winning_proposal_id.is_some(),
+            ErrorCode::NoWinnerRevealed
+        );
+
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp;
+
+        // Read all data from system state (not from parameters)
+        let round_id = ctx.accounts.round_metadata.current_round - 1; // Previous round (since current_round was incremented)
+        let winning_proposal_id = ctx.accounts.system_acc.winning_proposal_id.unwrap();
+        let winning_vote_count = ctx.accounts.system_acc.winning_vote_count.unwrap_or(0);
+        let total_proposals = ctx.accounts.system_acc.next_proposal_id;
+
+        // Initialize the round history account with verified data
+        ctx.accounts.round_history.bump = ctx.bumps.round_history;
+        ctx.accounts.round_history.round_id = round_id;
+        ctx.accounts.round_history.winning_proposal_id = winning_proposal_id;
+        ctx.accounts.round_history.revealed_at = current_timestamp;
+        ctx.accounts.round_history.revealed_at_slot = clock.slot;
+        ctx.accounts.round_history.revealed_by = ctx.accounts.payer.key();
+        ctx.accounts.round_history.total_proposals = total_proposals;
+        ctx.accounts.round_history.winning_vote_count = winning_vote_count;
+        // Snapshot the round's totals before the live counters are reset below. Rounds
+        // without proposals never created an escrow, so they collected no fees
+        let had_proposals = ctx.accounts.round_metadata.proposals_in_current_round > 0;
+        ctx.accounts.round_history.total_fees_collected = if had_proposals {
+            ctx.accounts
+                .round_escrow
+                .as_ref()
+                .ok_or(ErrorCode::InvalidEscrowRoundId)?
+                .total_collected
+        } else {
+            0
+        };
+        ctx.accounts.round_history.total_voters = ctx.accounts.round_metadata.total_voters;
+        ctx.accounts.round_history.winning_category = ctx
+            .accounts
+            .round_metadata
+            .proposal_categories
+            .get(winning_proposal_id as usize)
+            .copied()
+            .unwrap_or_default();
+        ctx.accounts.round_history.was_tie = ctx.accounts.system_acc.winner_was_tie;
+        ctx.accounts.round_history.tied_mask = ctx.accounts.system_acc.winner_tied_mask;
+        ctx.accounts.round_history.distribution_delay = ctx.accounts.system_acc.distribution_delay;
+
+        // Credit the win to the winning submitter's reputation
+        let winner_stats = &mut ctx.accounts.winner_stats;
+        winner_stats.bump = ctx.bumps.winner_stats;
+        winner_stats.submitter = ctx.accounts.winning_proposal.submitter;
+        winner_stats.total_wins = winner_stats
+            .total_wins
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(SubmitterStatsUpdatedEvent {
+            submitter: winner_stats.submitter,
+            total_submitted: winner_stats.total_submitted,
+            total_wins: winner_stats.total_wins,
+        });
+
+        // Record the winner in the bounded index, evicting the oldest entry when full
+        let winner_index = &mut ctx.accounts.winner_index;
+        winner_index.bump = ctx.bumps.winner_index;
+        if winner_index.entries.len() >= MAX_WINNER_INDEX_ENTRIES {
+            winner_index.entries.remove(0);
+        }
+        winner_index.entries.push(WinnerIndexEntry {
+            round_id,
+            winning_proposal_id,
+            revealed_at: current_timestamp,
+        });
+
+        // Close fee collection for the round so the escrow can be paid out
+        if let Some(round_escrow) = ctx.accounts.round_escrow.as_mut() {
+            round_escrow.round_status = RoundStatus::Completed;
+        }
+
+        // Reset system state for the next voting round
+        // Note: We don't reset next_proposal_id to 0 because proposal accounts still exist
+        // Instead, we keep the counter and let new proposals get new IDs
+        ctx.accounts.system_acc.winning_proposal_id = None; // Clear winner
+        ctx.accounts.system_acc.winning_vote_count = None; // Clear vote count
+        ctx.accounts.system_acc.winner_was_tie = false; // Clear tie flag
+        ctx.accounts.system_acc.winner_tied_mask = 0;
+        ctx.accounts.system_acc.winning_margin = None; // Clear margin
+        ctx.accounts.system_acc.all_scores_negative = false; // Clear negative-score flag
+        ctx.accounts.system_acc.supermajority_met = None; // Clear threshold outcome
+
+        // Votes stay closed until the re-encrypted counters land
+        ctx.accounts.system_acc.tally_reset_pending = true;
+        ctx.accounts.system_acc.tally_reset_failed = false;
+
+        // Reset the round proposal counter for the next round
+        ctx.accounts.round_metadata.proposals_in_current_round = 0;
+        // Forget the finished round's URLs and titles so they can be proposed again
+        ctx.accounts.round_metadata.url_hashes.clear();
+        ctx.accounts.round_metadata.title_hashes.clear();
+        // Reset the voter counter for the next round
+        ctx.accounts.round_metadata.total_voters = 0;
+        ctx.accounts.round_metadata.total_voting_power = 0;
+
+        msg!(
+            "Created round history for round {} - Winner: Proposal {}",
+            round_id,
+            winning_proposal_id
+        );
+        msg!(
+            "System state reset for next round - Proposals: 0, Winner: None, Nonce: {}",
+            new_nonce
+        );
+
+        emit!(RoundNonceRotatedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            nonce: new_nonce,
+        });
+
+        // Counters are reset, so the new round is now ready for proposals
+        emit!(RoundStartedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            round_started: ctx.accounts.round_metadata.round_started,
+        });
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Re-encrypt zeroed counters for the next round under the new nonce
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            vec![Argument::PlaintextU128(new_nonce)],
+            None,
+            vec![InitProposalVotesCallback::callback_ix(&[CallbackAccount {
+                pubkey: ctx.accounts.system_acc.key(),
+                is_writable: true,
+            }])],
+        )?;
+
+        Ok(())
+    }
+
+    /// Starts a runoff between the proposals that tied for first place in `from_round`.
+    ///
+    /// Only allowed before anyone submits to the current round. Entrants must be in the
+    /// tie recorded in the round history and must not have been slashed. They are
+    /// copied into the current round without a submission fee, and the round's proposal
+    /// limit is locked to the runoff field so nothing else can join it.
+    ///
+    /// `remaining_accounts` holds one pair per id, in order: the tied proposal from
+    /// `from_round`, then the uninitialized current-round proposal PDA that receives it.
+    ///
+    /// # Arguments
+    /// * `from_round` - The completed round whose reveal ended in a tie
+    /// * `proposal_ids` - IDs of the tied proposals in `from_round`
+    pub fn create_runoff_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateRunoffRound<'info>>,
+        from_round: u64,
+        proposal_ids: Vec<u8>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(!ctx.accounts.system_acc.paused, ErrorCode::SystemPaused);
+        require!(!ctx.accounts.system_acc.finished, ErrorCode::SystemFinished);
+
+        let round_history = &ctx.accounts.round_history;
+        require!(round_history.was_tie, ErrorCode::RoundNotTied);
+
+        require!(
+            proposal_ids.len() >= 2 && proposal_ids.len() <= MAX_PROPOSALS,
+            ErrorCode::InvalidRunoffProposals
+        );
+        require!(
+            ctx.remaining_accounts.len() == proposal_ids.len() * 2,
+            ErrorCode::InvalidRunoffProposals
+        );
+        for (i, proposal_id) in proposal_ids.iter().enumerate() {
+            require!(
+                !proposal_ids[..i].contains(proposal_id),
+                ErrorCode::InvalidRunoffProposals
+            );
+        }
+        // Only proposals the reveal reported as sharing the top score may enter
+        for proposal_id in &proposal_ids {
+            require!(
+                (*proposal_id as usize) < MAX_PROPOSALS
+                    && round_history.tied_mask & (1 << *proposal_id) != 0,
+                ErrorCode::InvalidRunoffProposals
+            );
+        }
+
+        let current_round = ctx.accounts.round_metadata.current_round;
+        require!(from_round < current_round, ErrorCode::InvalidRoundId);
+        require!(
+            ctx.accounts.round_metadata.proposals_in_current_round == 0,
+            ErrorCode::ProposalsAlreadySubmitted
+        );
+
+        let fee_mint = ctx.accounts.system_acc.fee_mint;
+        if !ctx.accounts.round_escrow.is_initialized {
+            init_round_escrow(
+                &mut ctx.accounts.round_escrow,
+                ctx.bumps.round_escrow,
+                current_round,
+                fee_mint,
+            )?;
+        }
+        require!(
+            ctx.accounts.round_escrow.round_id == current_round,
+            ErrorCode::InvalidEscrowRoundId
+        );
+        require!(
+            ctx.accounts.round_escrow.round_status == RoundStatus::Active,
+            ErrorCode::RoundEscrowNotActive
+        );
+
+        let system_acc_key = ctx.accounts.system_acc.key();
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let from_round_bytes = from_round.to_le_bytes();
+
+        for (new_id, (proposal_id, accounts)) in proposal_ids
+            .iter()
+            .zip(ctx.remaining_accounts.chunks(2))
+            .enumerate()
+        {
+            let (expected_source, _) = Pubkey::find_program_address(
+                &[
+                    b"proposal",
+                    system_acc_key.as_ref(),
+                    &from_round_bytes,
+                    &proposal_id.to_le_bytes(),
+                ],
+                &crate::ID,
+            );
+            require!(
+                accounts[0].key() == expected_source,
+                ErrorCode::InvalidRunoffProposals
+            );
+            let source = Account::<ProposalAccount>::try_from(&accounts[0])?;
+            // A slashed proposal forfeited its place, tie or not
+            require!(!source.slashed, ErrorCode::ProposalSlashed);
+            let new_id = new_id as u8;
+
+            create_runoff_proposal(
+                &system_acc_key,
+                &payer,
+                &accounts[1],
+                &system_program,
+                &source,
+                current_round,
+                new_id,
+            )?;
+
+            let round_metadata = &mut ctx.accounts.round_metadata;
+            round_metadata
+                .url_hashes
+                .push(hash(source.url.as_bytes()).to_bytes());
+            round_metadata
+                .title_hashes
+                .push(normalized_title_hash(&source.title));
+            round_metadata.proposal_categories[new_id as usize] = source.category;
+            round_metadata.proposal_seconds[new_id as usize] = source.seconds;
+
+            emit!(ProposalSubmittedEvent {
+                proposal_id: new_id,
+                round_id: current_round,
+                submitter: source.submitter,
+                category: source.category,
+                content_hash: source.content_hash,
+                co_submitters: source.co_submitters.clone(),
+            });
+        }
+
+        let runoff_size = proposal_ids.len() as u8;
+        ctx.accounts.round_metadata.proposals_in_current_round = runoff_size;
+        ctx.accounts.round_metadata.proposal_limit = runoff_size;
+        ctx.accounts.system_acc.next_proposal_id += runoff_size;
+
+        msg!(
+            "Runoff created in round {} between {} proposals tied in round {}",
+            current_round,
+            runoff_size,
+            from_round
+        );
+
+        emit!(RunoffCreatedEvent {
+            from_round,
+            proposal_ids,
+        });
+
+        Ok(())
+    }
+
+    /// Pays the collected submission fees of a completed round to the submitter
+    /// of the winning proposal.
+    ///
+    /// The winner receives `system_acc.winner_bps` of the balance and the system's
+    /// treasury the rest (see `set_fee_split`); by default the winner takes it all.
+    /// The winner's share is split equally between the submitter and the proposal's
+    /// co-submitters, which must be passed as writable remaining accounts in the order
+    /// stored on the proposal; the submitter keeps any indivisible remainder.
+    /// Can only run once `create_round_history` has recorded the winner (which
+    /// moves the escrow to `Completed`) and the `distribution_delay` recorded with it
+    /// has passed since then, and closes the escrow afterwards so the same round can
+    /// never be paid out twice. Losing proposals can still be refunded or slashed
+    /// during the delay; clients can compute when it ends from the round history's
+    /// `revealed_at`. The unpaid voter reward pool is left in the escrow for
+    /// `collect_winning_vote_reward`, and the refunds losing proposers haven't claimed
+    /// yet for `refund_losing_proposal`.
+    ///
+    /// # Arguments
+    /// * `round_id` - The completed round whose escrow is distributed
+    pub fn distribute_escrow_to_winner(
+        ctx: Context<DistributeEscrowToWinner>,
+        round_id: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.round_escrow.round_status == RoundStatus::Completed,
+            ErrorCode::RoundEscrowNotCompleted
+        );
+
+        // Payouts wait out the dispute window opened when the round was recorded, using
+        // the delay in force then so the authority can't shorten it afterwards
+        let round_history = &ctx.accounts.round_history;
+        let unlocks_at = round_history
+            .revealed_at
+            .saturating_add(round_history.distribution_delay);
+        require!(
+            Clock::get()?.unix_timestamp >= unlocks_at,
+            ErrorCode::DistributionLocked
+        );
+
+        // Votes are encrypted, so a slashed proposal can still win the tally; it just
+        // can't be paid
+        require!(
+            !ctx.accounts.winning_proposal.slashed,
+            ErrorCode::ProposalSlashed
+        );
+
+        // Co-submitters are passed as remaining accounts, in the order stored on the
+        // proposal; token-fee rounds pass their fee token accounts instead, which
+        // `pay_from_escrow` checks belong to them
+        let co_submitters = &ctx.accounts.winning_proposal.co_submitters;
+        let token_fees = ctx.accounts.round_escrow.fee_mint.is_some();
+        require!(
+            ctx.remaining_accounts.len() == co_submitters.len(),
+            ErrorCode::InvalidRecipient
+        );
+        for (account, co_submitter) in ctx.remaining_accounts.iter().zip(co_submitters) {
+            require!(
+                (token_fees || account.key() == *co_submitter) && account.is_writable,
+                ErrorCode::InvalidRecipient
+            );
+        }
+
+        // Winning voters are paid from the pool after registration closes and losing
+        // proposers claim their refunds whenever they like, so both stay in the escrow
+        let registration_open =
+            Clock::get()?.unix_timestamp <= reward_registration_end(&ctx.accounts.round_history);
+        let round_escrow = &ctx.accounts.round_escrow;
+        let reserved = round_escrow
+            .unpaid_voter_rewards(ctx.accounts.system_acc.voter_reward_bps, registration_open)
+            .saturating_add(round_escrow.unclaimed_refunds(
+                ctx.accounts.system_acc.refund_bps,
+                ctx.accounts.winning_proposal.fee_paid,
+            ));
+        let amount = ctx
+            .accounts
+            .round_escrow
+            .fee_balance()
+            .saturating_sub(reserved);
+        let winner_amount =
+            ((amount as u128) * (ctx.accounts.system_acc.winner_bps as u128) / 10_000) as u64;
+        let treasury_amount = amount - winner_amount;
+
+        // The team splits the winner's share equally; the submitter keeps any remainder
+        let team_size = co_submitters.len() as u64 + 1;
+        let co_submitter_amount = winner_amount / team_size;
+        let submitter_amount = winner_amount - co_submitter_amount * (team_size - 1);
+
+        let system_key = ctx.accounts.system_acc.key();
+        let winner = escrow_recipient(
+            &ctx.accounts.round_escrow,
+            &ctx.accounts.winner.to_account_info(),
+            ctx.accounts.winner_token_account.as_ref(),
+        )?;
+        pay_from_escrow(
+            &system_key,
+            &ctx.accounts.round_escrow,
+            &winner,
+            &ctx.accounts.winner.key(),
+            ctx.accounts.escrow_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            submitter_amount,
+        )?;
+        for (recipient, co_submitter) in ctx.remaining_accounts.iter().zip(co_submitters) {
+            pay_from_escrow(
+                &system_key,
+                &ctx.accounts.round_escrow,
+                recipient,
+                co_submitter,
+                ctx.accounts.escrow_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+                co_submitter_amount,
+            )?;
+        }
+        if treasury_amount > 0 {
+            let treasury = escrow_recipient(
+                &ctx.accounts.round_escrow,
+                &ctx.accounts.treasury.to_account_info(),
+                ctx.accounts.treasury_token_account.as_ref(),
+            )?;
+            pay_from_escrow(
+                &system_key,
+                &ctx.accounts.round_escrow,
+                &treasury,
+                &ctx.accounts.treasury.key(),
+                ctx.accounts.escrow_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+                treasury_amount,
+            )?;
+        }
+
+        let round_escrow = &mut ctx.accounts.round_escrow;
+        round_escrow.record_payout(amount)?;
+        // The winner's fee was never refundable; only the losers' claims remain
+        round_escrow.refundable_fees = round_escrow
+            .refundable_fees
+            .saturating_sub(ctx.accounts.winning_proposal.fee_paid);
+        ctx.accounts.round_escrow.round_status = RoundStatus::Closed;
+
+        msg!(
+            "Distributed {} in fees from round {} escrow: {} to winner {}, {} to each of {} co-submitters, {} to treasury",
+            amount,
+            round_id,
+            submitter_amount,
+            ctx.accounts.winner.key(),
+            co_submitter_amount,
+            ctx.remaining_accounts.len(),
+            treasury_amount
+        );
+
+        emit!(EscrowDistributedEvent {
+            round_id,
+            winning_proposal_id: ctx.accounts.round_history.winning_proposal_id,
+            winner: ctx.accounts.winner.key(),
+            amount,
+        });
+
+        emit!(EscrowSplitEvent {
+            round_id,
+            winner_amount,
+            treasury_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Sweeps residual funds from a finished round's escrow to the authority.
+    ///
+    /// Recovery path for fees that can no longer be paid out through the normal
+    /// distribution instructions, e.g. a completed round whose winner was slashed. Only
+    /// the system authority can call it, only once the escrow is `Completed` or
+    /// `Closed`, and only after `EMERGENCY_WITHDRAW_GRACE_PERIOD` has passed since the
+    /// escrow was created. Refunds submitters can still claim are never swept: the full
+    /// fee of every unrefunded proposal in a cancelled round, otherwise `refund_bps` of
+    /// each refundable fee (including the winner's until the escrow is distributed).
+    ///
+    /// # Arguments
+    /// * `round_id` - The round whose escrow is swept
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>, round_id: u64) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        require!(
+            ctx.accounts.round_escrow.round_status != RoundStatus::Active,
+            ErrorCode::RoundEscrowNotCompleted
+        );
+
+        let grace_period_end = ctx
+            .accounts
+            .round_escrow
+            .created_at
+            .checked_add(EMERGENCY_WITHDRAW_GRACE_PERIOD)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            Clock::get()?.unix_timestamp > grace_period_end,
+            ErrorCode::GracePeriodNotElapsed
+        );
+
+        let round_escrow = &ctx.accounts.round_escrow;
+        let owed_refunds = if round_escrow.cancelled {
+            round_escrow.refundable_fees
+        } else {
+            round_escrow.unclaimed_refunds(ctx.accounts.system_acc.refund_bps, 0)
+        };
+        let amount = round_escrow.fee_balance().saturating_sub(owed_refunds);
+        require!(amount > 0, ErrorCode::InsufficientEscrowBalance);
+
+        let recipient = escrow_recipient(
+            &ctx.accounts.round_escrow,
+            &ctx.accounts.payer.to_account_info(),
+            ctx.accounts.payer_token_account.as_ref(),
+        )?;
+        pay_from_escrow(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.round_escrow,
+            &recipient,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.escrow_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            amount,
+        )?;
+
+        ctx.accounts.round_escrow.record_payout(amount)?;
+
+        msg!(
+            "Emergency withdrew {} in fees from round {} escrow",
+            amount,
+            round_id
+        );
+
+        emit!(EmergencyWithdrawEvent { round_id, amount });
+
+        Ok(())
+    }
+
+    /// Refunds part of the submission fee to the submitter of a losing proposal.
+    ///
+    /// The round must be completed and its history recorded, and the proposal must not
+    /// be the recorded winner. The refunded share is `system_acc.refund_bps` of the fee
+    /// the proposal originally paid. Distribution leaves unclaimed refunds in the escrow,
+    /// so refunds stay possible after the winner has been paid.
+    ///
+    /// # Arguments
+    /// * `round_id` - The completed round the proposal belongs to
+    /// * `proposal_id` - ID of the proposal within that round
+    pub fn refund_losing_proposal(
+        ctx: Context<RefundLosingProposal>,
+        round_id: u64,
+        proposal_id: u8,
+    ) -> Result<()> {
+        require!(
+            round_id < ctx.accounts.round_metadata.current_round,
+            ErrorCode::InvalidRoundId
+        );
+
+        require!(
+            proposal_id != ctx.accounts.round_history.winning_proposal_id,
+            ErrorCode::CannotRefundWinner
+        );
+
+        require!(
+            !ctx.accounts.proposal_acc.refunded,
+            ErrorCode::AlreadyRefunded
+        );
+
+        require!(
+            !ctx.accounts.proposal_acc.slashed,
+            ErrorCode::ProposalSlashed
+        );
+
+        // Cancelled rounds refund the full fee through `refund_cancelled_proposal`
+        require!(
+            ctx.accounts.round_escrow.round_status != RoundStatus::Active
+                && !ctx.accounts.round_escrow.cancelled,
+            ErrorCode::RoundEscrowNotCompleted
+        );
+
+        let refund_amount = (ctx.accounts.proposal_acc.fee_paid as u128
+            * ctx.accounts.system_acc.refund_bps as u128
+            / 10_000) as u64;
+
+        require!(
+            refund_amount <= ctx.accounts.round_escrow.fee_balance(),
+            ErrorCode::InsufficientEscrowBalance
+        );
+
+        let recipient = escrow_recipient(
+            &ctx.accounts.round_escrow,
+            &ctx.accounts.submitter.to_account_info(),
+            ctx.accounts.submitter_token_account.as_ref(),
+        )?;
+        pay_from_escrow(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.round_escrow,
+            &recipient,
+            &ctx.accounts.submitter.key(),
+            ctx.accounts.escrow_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            refund_amount,
+        )?;
+
+        let round_escrow = &mut ctx.accounts.round_escrow;
+        round_escrow.record_payout(refund_amount)?;
+        round_escrow.refundable_fees = round_escrow
+            .refundable_fees
+            .checked_sub(ctx.accounts.proposal_acc.fee_paid)
+            .ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.proposal_acc.refunded = true;
+
+        msg!(
+            "Refunded {} lamports to submitter of proposal {} in round {}",
+            refund_amount,
+            proposal_id,
+            round_id
+        );
+
+        emit!(ProposalRefundedEvent {
+            round_id,
+            proposal_id,
+            submitter: ctx.accounts.submitter.key(),
+            amount: refund_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Refunds the full submission fee of a proposal in a cancelled round.
+    ///
+    /// Anyone can trigger the refund; the lamports always go to the proposal's submitter.
+    ///
+    /// # Arguments
+    /// * `round_id` - The cancelled round the proposal belongs to
+    /// * `proposal_id` - ID of the proposal within that round
+    pub fn refund_cancelled_proposal(
+        ctx: Context<RefundCancelledProposal>,
+        round_id: u64,
+        proposal_id: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.round_escrow.cancelled,
+            ErrorCode::RoundNotCancelled
+        );
+
+        require!(
+            !ctx.accounts.proposal_acc.refunded,
+            ErrorCode::AlreadyRefunded
+        );
+
+        require!(
+            !ctx.accounts.proposal_acc.slashed,
+            ErrorCode::ProposalSlashed
+        );
+
+        let refund_amount = ctx.accounts.proposal_acc.fee_paid;
+
+        require!(
+            refund_amount <= ctx.accounts.round_escrow.fee_balance(),
+            ErrorCode::InsufficientEscrowBalance
+        );
+
+        let recipient = escrow_recipient(
+            &ctx.accounts.round_escrow,
+            &ctx.accounts.submitter.to_account_info(),
+            ctx.accounts.submitter_token_account.as_ref(),
+        )?;
+        pay_from_escrow(
+            &ctx.accounts.system_acc.key(),
+            &ctx.accounts.round_escrow,
+            &recipient,
+            &ctx.accounts.submitter.key(),
+            ctx.accounts.escrow_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            refund_amount,
+        )?;
+
+        let round_escrow = &mut ctx.accounts.round_escrow;
+        round_escrow.record_payout(refund_amount)?;
+        round_escrow.refundable_fees = round_escrow
+            .refundable_fees
+            .checked_sub(ctx.accounts.proposal_acc.fee_paid)
+            .ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.proposal_acc.refunded = true;
+
+        msg!(
+            "Refunded {} lamports to submitter of proposal {} in cancelled round {}",
+            refund_amount,
+            proposal_id,
+            round_id
+        );
+
+        emit!(ProposalRefundedEvent {
+            round_id,
+            proposal_id,
+            submitter: ctx.accounts.submitter.key(),
+            amount: refund_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Seconds a proposal in the current round.
+    ///
+    /// Each key can second a proposal once, and submitters can't second their own.
+    /// Once a proposal has `system_acc.required_seconds` seconds it can receive votes.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - ID of the proposal within the current round
+    pub fn second_proposal(ctx: Context<SecondProposal>, proposal_id: u8) -> Result<()> {
+        require!(!ctx.accounts.system_acc.paused, ErrorCode::SystemPaused);
+        require!(
+            ctx.accounts.payer.key() != ctx.accounts.proposal_acc.submitter,
+            ErrorCode::CannotSecondOwnProposal
+        );
+
+        let second = &mut ctx.accounts.second;
+        second.bump = ctx.bumps.second;
+        second.proposal = ctx.accounts.proposal_acc.key();
+        second.seconder = ctx.accounts.payer.key();
+
+        let seconds = ctx
+            .accounts
+            .proposal_acc
+            .seconds
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.proposal_acc.seconds = seconds;
+        ctx.accounts.round_metadata.proposal_seconds[proposal_id as usize] = seconds;
+
+        emit!(ProposalSecondedEvent {
+            proposal_id,
+            round_id: ctx.accounts.round_metadata.current_round,
+            seconder: ctx.accounts.payer.key(),
+            seconds,
+        });
+
+        Ok(())
+    }
+
+    /// Sets how many seconds a proposal needs before it can receive votes.
+    ///
+    /// Only the system authority can change it; 0 disables seconding.
+    ///
+    /// # Arguments
+    /// * `required_seconds` - Distinct seconders each proposal needs
+    pub fn set_required_seconds(
+        ctx: Context<SetRequiredSeconds>,
+        required_seconds: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        ctx.accounts.system_acc.required_seconds = required_seconds;
+
+        msg!("Proposals now need {} seconds", required_seconds);
+
+        Ok(())
+    }
+
+    /// Flags a proposal as spam and forfeits its submission fee.
+    ///
+    /// Only the system authority can slash. The fee stays in the round's escrow, no longer
+    /// reserved for a refund, and the proposal can no longer be refunded. Votes are
+    /// encrypted, so slashing can't remove the proposal from the tally; instead the
+    /// escrow is never paid out to a slashed winner.
+    ///
+    /// # Arguments
+    /// * `round_id` - The round the proposal belongs to
+    /// * `proposal_id` - ID of the proposal within that round
+    pub fn slash_proposal(
+        ctx: Context<SlashProposal>,
+        round_id: u64,
+        proposal_id: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        require!(
+            !ctx.accounts.proposal_acc.slashed,
+            ErrorCode::ProposalSlashed
+        );
+
+        // A refunded fee has already left the escrow
+        require!(
+            !ctx.accounts.proposal_acc.refunded,
+            ErrorCode::AlreadyRefunded
+        );
+
+        ctx.accounts.proposal_acc.slashed = true;
+        let round_escrow = &mut ctx.accounts.round_escrow;
+        round_escrow.refundable_fees = round_escrow
+            .refundable_fees
+            .checked_sub(ctx.accounts.proposal_acc.fee_paid)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("Slashed proposal {} in round {}", proposal_id, round_id);
+
+        emit!(ProposalSlashedEvent {
+            proposal_id,
+            round_id,
+        });
+
+        Ok(())
+    }
+
+    /// Updates the fee charged for submitting a proposal.
+    ///
+    /// Only the system authority can change the fee. A SOL fee may not drop below the
+    /// rent-exempt minimum of a `ProposalAccount`, so submissions always cost at least
+    /// the storage they occupy. A token fee is in the fee mint's base units, which
+    /// can't be compared with lamports, so it has no floor.
+    ///
+    /// # Arguments
+    /// * `new_fee` - New submission fee (in lamports, or base units of the fee mint)
+    pub fn set_submission_fee(ctx: Context<SetSubmissionFee>, new_fee: u64) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        if ctx.accounts.system_acc.fee_mint.is_none() {
+            check_sol_fee_floor(new_fee)?;
+        }
+
+        let old_fee = ctx.accounts.system_acc.proposal_submission_fee;
+        ctx.accounts.system_acc.proposal_submission_fee = new_fee;
+
+        msg!(
+            "Proposal submission fee updated: {} -> {}",
+            old_fee,
+            new_fee
+        );
+
+        emit!(FeeUpdatedEvent { old_fee, new_fee });
+
+        Ok(())
+    }
+
+    /// Adds a voter to the system's allowlist.
+    ///
+    /// Only the system authority can manage the allowlist. The first addition creates the
+    /// allowlist account and turns on allowlist enforcement, after which every vote must
+    /// come from an allowlisted key.
+    ///
+    /// # Arguments
+    /// * `voter` - Public key of the voter to allow
+    pub fn add_voter(ctx: Context<AddVoter>, voter: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        let allowlist = &mut ctx.accounts.allowlist;
+        allowlist.bump = ctx.bumps.allowlist;
+
+        require!(
+            !allowlist.voters.contains(&voter),
+            ErrorCode::VoterAlreadyAllowlisted
+        );
+        require!(
+            allowlist.voters.len() < MAX_ALLOWLISTED_VOTERS,
+            ErrorCode::AllowlistFull
+        );
+
+        allowlist.voters.push(voter);
+        ctx.accounts.system_acc.allowlist_enabled = true;
+
+        msg!(
+            "Voter {} added to allowlist ({} total)",
+            voter,
+            allowlist.voters.len()
+        );
+
+        emit!(VoterAllowlistedEvent { voter });
+
+        Ok(())
+    }
+
+    /// Removes a voter from the system's allowlist.
+    ///
+    /// Only the system authority can manage the allowlist. Enforcement stays on even if
+    /// the list becomes empty.
+    ///
+    /// # Arguments
+    /// * `voter` - Public key of the voter to remove
+    pub fn remove_voter(ctx: Context<RemoveVoter>, voter: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        let allowlist = &mut ctx.accounts.allowlist;
+        let index = allowlist
+            .voters
+            .iter()
+            .position(|allowed| *allowed == voter)
+            .ok_or(ErrorCode::VoterNotAllowlisted)?;
+        allowlist.voters.swap_remove(index);
+
+        msg!(
+            "Voter {} removed from allowlist ({} total)",
+            voter,
+            allowlist.voters.len()
+        );
+
+        Ok(())
+    }
+
+    /// Bans a key from voting and submitting proposals in every future round.
+    ///
+    /// Only the system authority can blacklist. The ban is a `BlacklistAccount` PDA for
+    /// the key, which every voting and submission instruction requires and checks, for
+    /// batched voters and delegators too; votes already cast are unaffected.
+    ///
+    /// # Arguments
+    /// * `voter` - Public key to ban
+    pub fn blacklist_voter(ctx: Context<BlacklistVoter>, voter: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        let blacklist = &mut ctx.accounts.blacklist;
+        blacklist.bump = ctx.bumps.blacklist;
+        blacklist.voter = voter;
+        blacklist.blacklisted_at = Clock::get()?.unix_timestamp;
+
+        msg!("Voter {} blacklisted", voter);
+
+        emit!(VoterBlacklistedEvent {
+            voter,
+            blacklisted: true,
+        });
+
+        Ok(())
+    }
+
+    /// Lifts a ban placed by `blacklist_voter`, returning the account's rent to the authority.
+    ///
+    /// # Arguments
+    /// * `voter` - Public key to unban
+    pub fn unblacklist_voter(ctx: Context<UnblacklistVoter>, voter: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        msg!("Voter {} removed from blacklist", voter);
+
+        emit!(VoterBlacklistedEvent {
+            voter,
+            blacklisted: false,
+        });
+
+        Ok(())
+    }
+
+    /// Starts handing system authority over to a new key.
+    ///
+    /// Only the current authority can nominate a successor. Control does not change until
+    /// the nominated key calls `accept_authority`, so a mistyped address can simply be
+    /// replaced by proposing again.
+    ///
+    /// # Arguments
+    /// * `new_authority` - Public key nominated as the next authority
+    pub fn propose_new_authority(
+        ctx: Context<ProposeNewAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        ctx.accounts.system_acc.pending_authority = Some(new_authority);
+
+        msg!("Proposed {} as the new system authority", new_authority);
+
+        Ok(())
+    }
+
+    /// Completes an authority handoff started by `propose_new_authority`.
+    ///
+    /// Must be signed by the pending authority.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let pending_authority = ctx
+            .accounts
+            .system_acc
+            .pending_authority
+            .ok_or(ErrorCode::NoPendingAuthority)?;
+
+        require!(
+            ctx.accounts.payer.key() == pending_authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        let old_authority = ctx.accounts.system_acc.authority;
+        ctx.accounts.system_acc.authority = pending_authority;
+        ctx.accounts.system_acc.pending_authority = None;
+
+        msg!(
+            "System authority transferred: {} -> {}",
+            old_authority,
+            pending_authority
+        );
+
+        emit!(AuthorityTransferredEvent {
+            old: old_authority,
+            new: pending_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Pauses proposal submission and voting in the current round.
+    ///
+    /// Only the system authority can pause the system. Reveals remain possible while
+    /// paused so a compromised round can still be closed out.
+    pub fn pause_voting(ctx: Context<SetPaused>) -> Result<()> {
+        set_paused(ctx, true)
+    }
+
+    /// Resumes proposal submission and voting after `pause_voting`.
+    pub fn resume_voting(ctx: Context<SetPaused>) -> Result<()> {
+        set_paused(ctx, false)
+    }
+
+    /// Aborts the current round without revealing a winner.
+    ///
+    /// Only the system authority can cancel, and only before a winner has been revealed,
+    /// while no reveal is queued and once the round's own tally reset has landed. The
+    /// round's escrow is closed and marked cancelled so every submitter can recover their
+    /// full fee through `refund_cancelled_proposal`. The next round starts immediately,
+    /// and its zeroed tally is encrypted through MPC under `new_nonce`, as in
+    /// `create_round_history`; votes open once it lands.
+    ///
+    /// # Arguments
+    /// * `computation_offset` - Unique MPC computation offset for the tally reset
+    /// * `reason_code` - Application-defined reason for the cancellation
+    /// * `new_nonce` - Nonce the next round's zeroed tally is encrypted under; must
+    ///   differ from the current nonce
+    pub fn cancel_round(
+        ctx: Context<CancelRound>,
+        computation_offset: u64,
+        reason_code: u8,
+        new_nonce: u128,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            new_nonce != ctx.accounts.system_acc.nonce,
+            ErrorCode::NonceNotRotated
+        );
+        require!(
+            ctx.accounts.system_acc.winning_proposal_id.is_none(),
+            ErrorCode::RoundAlreadyRevealed
+        );
+        // A queued reveal's callback would record a winner for the next round's tally
+        require!(
+            !ctx.accounts.system_acc.reveal_in_progress,
+            ErrorCode::RevealAlreadyInProgress
+        );
+        // A second reset could land after the first reopened voting and wipe its votes
+        require!(
+            !ctx.accounts.system_acc.tally_reset_pending,
+            ErrorCode::TallyResetPending
+        );
+
+        let round_id = ctx.accounts.round_metadata.current_round;
+
+        // Rounds without proposals never created an escrow
+        if ctx.accounts.round_metadata.proposals_in_current_round > 0 {
+            let round_escrow = ctx
+                .accounts
+                .round_escrow
+                .as_mut()
+                .ok_or(ErrorCode::InvalidEscrowRoundId)?;
+            round_escrow.round_status = RoundStatus::Closed;
+            round_escrow.cancelled = true;
+        }
+
+        // Votes stay closed until the re-encrypted counters land
+        ctx.accounts.system_acc.tally_reset_pending = true;
+        ctx.accounts.system_acc.tally_reset_failed = false;
+
+        ctx.accounts.round_metadata.proposals_in_current_round = 0;
+        ctx.accounts.round_metadata.url_hashes.clear();
+        ctx.accounts.round_metadata.title_hashes.clear();
+        ctx.accounts.round_metadata.total_voters = 0;
+        ctx.accounts.round_metadata.total_voting_power = 0;
+        ctx.accounts.round_metadata.current_round += 1;
+        ctx.accounts.round_metadata.round_started = Clock::get()?.unix_timestamp;
+        ctx.accounts.round_metadata.round_started_slot = Clock::get()?.slot;
+        ctx.accounts.round_metadata.round_extension = 0;
+        ctx.accounts.round_metadata.commit_deadline = None;
+        ctx.accounts.round_metadata.reveal_deadline = None;
+
+        msg!("Round {} cancelled (reason {})", round_id, reason_code);
+
+        emit!(RoundNonceRotatedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            nonce: new_nonce,
+        });
+
+        emit!(RoundCancelledEvent {
+            round_id,
+            reason_code,
+        });
+
+        emit!(RoundStartedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            round_started: ctx.accounts.round_metadata.round_started,
+        });
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Re-encrypt zeroed counters for the next round under the new nonce
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            vec![Argument::PlaintextU128(new_nonce)],
+            None,
+            vec![InitProposalVotesCallback::callback_ix(&[CallbackAccount {
+                pubkey: ctx.accounts.system_acc.key(),
+                is_writable: true,
+            }])],
+        )?;
+
+        Ok(())
+    }
+
+    /// Clears a winner reveal whose callback never arrived.
+    ///
+    /// A reveal sets `reveal_in_progress` until its callback lands, which blocks further
+    /// reveals and `cancel_round`. If the cluster drops the computation the flag would
+    /// stay set forever, so once `REVEAL_RECOVERY_TIMEOUT` has passed since the reveal
+    /// was queued the system authority can clear it and reveal or cancel again.
+    pub fn clear_stuck_reveal(ctx: Context<ClearStuckReveal>) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            ctx.accounts.system_acc.reveal_in_progress,
+            ErrorCode::NoRevealInProgress
+        );
+
+        let queued_at = ctx.accounts.system_acc.reveal_queued_at;
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            current_timestamp >= queued_at.saturating_add(REVEAL_RECOVERY_TIMEOUT),
+            ErrorCode::RevealRecoveryTooEarly
+        );
+
+        ctx.accounts.system_acc.reveal_in_progress = false;
+
+        msg!("Cleared reveal queued at {}", queued_at);
+
+        emit!(StuckRevealClearedEvent {
+            queued_at,
+            cleared_at: current_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Gives voters more time in the current round.
+    ///
+    /// Only the system authority can extend a round, and only while it is still open.
+    /// Extensions accumulate per round but may not exceed one extra `voting_duration`
+    /// in total, so a round can never be kept open indefinitely.
+    ///
+    /// # Arguments
+    /// * `additional_seconds` - Seconds to add to the current deadline
+    pub fn extend_round(ctx: Context<ExtendRound>, additional_seconds: i64) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(additional_seconds > 0, ErrorCode::InvalidVotingDuration);
+
+        let voting_duration = ctx.accounts.system_acc.voting_duration;
+        require!(
+            Clock::get()?.unix_timestamp
+                <= ctx.accounts.round_metadata.voting_deadline(voting_duration),
+            ErrorCode::VotingClosed
+        );
+
+        let round_extension = ctx
+            .accounts
+            .round_metadata
+            .round_extension
+            .checked_add(additional_seconds)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            round_extension <= voting_duration,
+            ErrorCode::ExtensionTooLong
+        );
+
+        ctx.accounts.round_metadata.round_extension = round_extension;
+
+        let new_deadline = ctx.accounts.round_metadata.voting_deadline(voting_duration);
+
+        msg!(
+            "Round {} extended by {} seconds - new deadline {}",
+            ctx.accounts.round_metadata.current_round,
+            additional_seconds,
+            new_deadline
+        );
+
+        emit!(RoundExtendedEvent {
+            round_id: ctx.accounts.round_metadata.current_round,
+            new_deadline,
+        });
+
+        Ok(())
+    }
+
+    /// Returns up to `n` of the most recent winners, newest first.
+    ///
+    /// Read-only view over the winner index; clients can simulate it to page through
+    /// past winners without knowing each round ID.
+    pub fn get_recent_winners(
+        ctx: Context<GetRecentWinners>,
+        n: u8,
+    ) -> Result<Vec<WinnerIndexEntry>> {
+        Ok(ctx
+            .accounts
+            .winner_index
+            .entries
+            .iter()
+            .rev()
+            .take(n as usize)
+            .cloned()
+            .collect())
+    }
+
+    /// Sets or clears the cap on any single proposal's vote count.
+    ///
+    /// Only the system authority can change the cap. The cap is enforced inside the
+    /// `vote_for_proposal` circuit: a vote that would push a proposal past it leaves the
+    /// tally unchanged and is reported as rejected. This is a coarse defense against
+    /// ballot stuffing; it bounds the damage but cannot tell sybil voters apart.
+    ///
+    /// # Arguments
+    /// * `max_votes_per_proposal` - New cap, or None to remove it
+    pub fn set_max_votes_per_proposal(
+        ctx: Context<SetMaxVotesPerProposal>,
+        max_votes_per_proposal: Option<u64>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        ctx.accounts.system_acc.max_votes_per_proposal = max_votes_per_proposal;
+
+        msg!("Per-proposal vote cap set to {:?}", max_votes_per_proposal);
+
+        emit!(VoteCapUpdatedEvent {
+            max_votes_per_proposal,
+        });
+
+        Ok(())
+    }
+
+    /// Sets the voting power quorum for weighted systems.
+    ///
+    /// Only the system authority can change it. When a governance mint is configured,
+    /// `reveal_winning_proposal` compares the round's summed voting power against this
+    /// value instead of comparing the voter headcount against `min_quorum`.
+    ///
+    /// # Arguments
+    /// * `min_voting_power` - Minimum total voting power (in governance token base units)
+    pub fn set_min_voting_power(
+        ctx: Context<SetMinVotingPower>,
+        min_voting_power: u128,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        ctx.accounts.system_acc.min_voting_power = min_voting_power;
+
+        msg!("Voting power quorum set to {}", min_voting_power);
+
+        emit!(VotingPowerQuorumUpdatedEvent { min_voting_power });
+
+        Ok(())
+    }
+
+    /// Configures how distributed escrows are split between the winner and a treasury.
+    ///
+    /// `distribute_escrow_to_winner` pays `winner_bps / 10000` of the escrow balance to
+    /// the winning submitter and the remainder to `treasury`. Only the system authority
+    /// can change the split.
+    ///
+    /// # Arguments
+    /// * `treasury` - Recipient of the non-winner share
+    /// * `winner_bps` - Winner's share in basis points (at most 10000)
+    pub fn set_fee_split(
+        ctx: Context<SetFeeSplit>,
+        treasury: Pubkey,
+        winner_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(winner_bps <= 10_000, ErrorCode::InvalidBasisPoints);
+
+        ctx.accounts.system_acc.treasury = treasury;
+        ctx.accounts.system_acc.winner_bps = winner_bps;
+
+        msg!(
+            "Escrow split set: {} bps to winner, rest to {}",
+            winner_bps,
+            treasury
+        );
+
+        Ok(())
+    }
+
+    /// Sets how many proposals a round needs before its winner can be revealed.
+    ///
+    /// Only the system authority can change it. Defaults to 2.
+    ///
+    /// # Arguments
+    /// * `min_proposals` - Minimum proposals per revealable round (at most `MAX_PROPOSALS`)
+    pub fn set_min_proposals(ctx: Context<SetMinProposals>, min_proposals: u8) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            min_proposals as usize <= MAX_PROPOSALS,
+            ErrorCode::InvalidProposalLimit
+        );
+
+        ctx.accounts.system_acc.min_proposals = min_proposals;
+
+        msg!("Minimum proposals per round set to {}", min_proposals);
+
+        emit!(MinProposalsUpdatedEvent { min_proposals });
+
+        Ok(())
+    }
+
+    /// Caps the number of rounds the system runs for.
+    ///
+    /// Once the winner of round `max_rounds - 1` is revealed the system is marked
+    /// finished and rejects further proposals and votes. Only the system authority can
+    /// change it, the cap can't fall at or below the rounds already completed, and a
+    /// finished system stays finished.
+    ///
+    /// # Arguments
+    /// * `max_rounds` - Total rounds to run, or None to run indefinitely
+    pub fn set_max_rounds(ctx: Context<SetMaxRounds>, max_rounds: Option<u64>) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(!ctx.accounts.system_acc.finished, ErrorCode::SystemFinished);
+        if let Some(max_rounds) = max_rounds {
+            require!(
+                max_rounds > ctx.accounts.round_metadata.current_round,
+                ErrorCode::InvalidMaxRounds
+            );
+        }
+
+        ctx.accounts.system_acc.max_rounds = max_rounds;
+
+        msg!("Maximum rounds set to {:?}", max_rounds);
+
+        emit!(MaxRoundsUpdatedEvent { max_rounds });
+
+        Ok(())
+    }
+
+    /// Sets the dispute window between recording a round and paying out its escrow.
+    ///
+    /// Applies to rounds recorded from now on: each round history keeps the delay in
+    /// force when it was created, so a pending dispute window can't be shortened. Only
+    /// the system authority can change it.
+    ///
+    /// # Arguments
+    /// * `distribution_delay` - Seconds to wait before `distribute_escrow_to_winner` (0 = none)
+    pub fn set_distribution_delay(
+        ctx: Context<SetDistributionDelay>,
+        distribution_delay: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(distribution_delay >= 0, ErrorCode::InvalidDistributionDelay);
+
+        ctx.accounts.system_acc.distribution_delay = distribution_delay;
+
+        msg!("Distribution delay set to {} seconds", distribution_delay);
+
+        emit!(DistributionDelayUpdatedEvent { distribution_delay });
+
+        Ok(())
+    }
+
+    /// Sets the Merkle root of the off-chain voter weight snapshot.
+    ///
+    /// `weighted_vote_for_proposal` takes each voter's weight from a proof against it,
+    /// so weights are fixed when the snapshot is taken and tokens moved afterwards
+    /// can't vote twice. Leaves are
+    /// `keccak(voter || weight as 8 little-endian bytes)` and inner nodes hash their
+    /// two children in sorted order. Only the system authority can change it, and only
+    /// on systems with a governance mint.
+    ///
+    /// # Arguments
+    /// * `root` - Snapshot root, or None to suspend weighted voting
+    pub fn set_voter_snapshot(
+        ctx: Context<SetVoterSnapshot>,
+        root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            root.is_none() || ctx.accounts.system_acc.governance_mint.is_some(),
+            ErrorCode::WeightedVotingDisabled
+        );
+
+        ctx.accounts.system_acc.voter_snapshot_root = root;
+
+        msg!("Voter snapshot root set to {:?}", root);
+
+        emit!(VoterSnapshotUpdatedEvent { root });
+
+        Ok(())
+    }
+
+    /// Returns the parameters needed to work with the current encrypted tally.
+    ///
+    /// Read-only view; the nonce changes after every tally update and is rotated when
+    /// a round is reset (see `RoundNonceRotatedEvent`).
+    pub fn get_tally_encryption_params(
+        ctx: Context<GetTallyEncryptionParams>,
+    ) -> Result<TallyEncryptionParams> {
+        Ok(TallyEncryptionParams {
+            system_id: ctx.accounts.system_acc.system_id,
+            round_id: ctx.accounts.round_metadata.current_round,
+            nonce: ctx.accounts.system_acc.nonce,
+        })
+    }
+
+    /// Emits a snapshot of the current round's status in a single read-only call.
+    ///
+    /// Saves clients from fetching the system, round metadata and escrow accounts
+    /// separately, and guarantees all values come from the same slot. The escrow is
+    /// optional because it only exists once the round's first proposal is submitted.
+    pub fn get_round_status(ctx: Context<GetRoundStatus>) -> Result<()> {
+        let round_metadata = &ctx.accounts.round_metadata;
+
+        emit!(RoundStatusEvent {
+            current_round: round_metadata.current_round,
+            proposals_in_current_round: round_metadata.proposals_in_current_round,
+            total_voters: round_metadata.total_voters,
+            winning_proposal_id: ctx.accounts.system_acc.winning_proposal_id,
+            round_status: ctx
+                .accounts
+                .round_escrow
+                .as_ref()
+                .map(|escrow| escrow.round_status.clone()),
+            voting_deadline: round_metadata
+                .voting_deadline(ctx.accounts.system_acc.voting_duration),
+        });
+
+        Ok(())
+    }
+
+    /// Emits a proposal's public metadata, including its vote count only once that is
+    /// safe to show.
+    ///
+    /// The count is reported only after the proposal's round has ended and its revealed
+    /// count was copied over by `sync_proposal_vote_count`; before that `vote_count` is
+    /// `None`, so frontends never have to decide on their own whether a count is final.
+    ///
+    /// # Arguments
+    /// * `round_id` - Round the proposal was submitted in
+    /// * `proposal_id` - ID of the proposal within that round
+    pub fn get_proposal(ctx: Context<GetProposal>, round_id: u64, proposal_id: u8) -> Result<()> {
+        let proposal = &ctx.accounts.proposal_acc;
+        let round_complete = proposal.round_id < ctx.accounts.round_metadata.current_round;
+
+        emit!(ProposalViewEvent {
+            proposal_id,
+            round_id,
+            submitter: proposal.submitter,
+            title: proposal.title.clone(),
+            vote_count: (round_complete && proposal.count_synced).then_some(proposal.vote_count),
+        });
+
+        Ok(())
+    }
+
+    /// Estimates the lamports a caller would pay to queue `reveal_winning_proposal` now.
+    ///
+    /// Read-only: nothing is queued or written. The computation fee is the reveal
+    /// circuit's compute units priced at the cluster's current CU price; the signer PDA
+    /// rent is only counted when that account has not been created yet. Simulate it
+    /// before revealing to fund the payer.
+    pub fn preview_reveal_cost(ctx: Context<PreviewRevealCost>) -> Result<()> {
+        let cu_amount = ctx.accounts.comp_def_account.cu_amount;
+        let cu_price = ctx.accounts.cluster_account.cu_price;
+        let computation_fee = cu_amount
+            .checked_mul(cu_price)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let sign_pda_rent = if ctx.accounts.sign_pda_account.data_is_empty() {
+            Rent::get()?.minimum_balance(9)
+        } else {
+            0
+        };
+
+        let total = computation_fee
+            .checked_add(sign_pda_rent)
+            .and_then(|v| v.checked_add(BASE_SIGNATURE_FEE_LAMPORTS))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "Reveal cost estimate: {} CU x {} = {} lamports, sign PDA rent {}, signature fee {}, total {}",
+            cu_amount,
+            cu_price,
+            computation_fee,
+            sign_pda_rent,
+            BASE_SIGNATURE_FEE_LAMPORTS,
+            total
+        );
+        msg!(
+            "Fee pool balance: {} lamports (epoch {})",
+            ctx.accounts.pool_account.to_account_info().lamports(),
+            Clock::get()?.epoch
+        );
+
+        emit!(RevealCostEstimateEvent {
+            cu_amount,
+            cu_price,
+            computation_fee,
+            sign_pda_rent,
+            total,
+        });
+
+        Ok(())
+    }
+
+    /// Derives a voter's vote receipt PDA exactly as the voting instructions do.
+    ///
+    /// Read-only correctness aid: SDKs can simulate it to cross-check their own
+    /// derivation, since a wrongly encoded seed surfaces as `InvalidAuthority` when
+    /// voting. Logs every seed in the order used.
+    ///
+    /// # Arguments
+    /// * `voter` - Key the receipt belongs to
+    /// * `round_id` - Round the receipt is for (seeded as 8 little-endian bytes)
+    pub fn derive_vote_receipt_pda(
+        ctx: Context<DeriveVoteReceiptPda>,
+        voter: Pubkey,
+        round_id: u64,
+    ) -> Result<()> {
+        let system_acc_key = ctx.accounts.system_acc.key();
+        let round_id_bytes = round_id.to_le_bytes();
+        let (vote_receipt, bump) = Pubkey::find_program_address(
+            &[
+                b"vote_receipt",
+                system_acc_key.as_ref(),
+                voter.as_ref(),
+                &round_id_bytes,
+            ],
+            &crate::ID,
+        );
+
+        msg!("Seed 0: \"vote_receipt\" {:?}", b"vote_receipt");
+        msg!(
+            "Seed 1: system {} {:?}",
+            system_acc_key,
+            system_acc_key.to_bytes()
+        );
+        msg!("Seed 2: voter {} {:?}", voter, voter.to_bytes());
+        msg!("Seed 3: round_id {} {:?}", round_id, round_id_bytes);
+        msg!("Vote receipt PDA: {} (bump {})", vote_receipt, bump);
+
+        emit!(VoteReceiptPdaDerivedEvent {
+            voter,
+            round_id,
+            vote_receipt,
+            bump,
+        });
+
+        Ok(())
+    }
+
+    /// Closes the caller's vote receipt for a finished round and refunds its rent.
+    ///
+    /// Only the voter recorded in the receipt can close it, and only once the round is
+    /// over. The rent goes back to whoever paid it, which for a delegated vote is the
+    /// delegate rather than the voter. Closing is irreversible: the vote can no longer be proven with
+    /// `verify_winning_vote`/`verify_vote_for_proposal` or used to claim a reward.
+    ///
+    /// # Arguments
+    /// * `round_id` - The finished round the receipt belongs to
+    pub fn close_vote_receipt(ctx: Context<CloseVoteReceipt>, round_id: u64) -> Result<()> {
+        require!(
+            round_id < ctx.accounts.round_metadata.current_round,
+            ErrorCode::InvalidRoundId
+        );
+
+        let round_id_bytes = round_id.to_le_bytes();
+        let (expected_vote_receipt_pda, _) = Pubkey::find_program_address(
+            &[
+                b"vote_receipt",
+                ctx.accounts.system_acc.key().as_ref(),
+                ctx.accounts.voter.key().as_ref(),
+                &round_id_bytes,
+            ],
+            &crate::ID,
+        );
+        require!(
+            ctx.accounts.vote_receipt.key() == expected_vote_receipt_pda,
+            ErrorCode::InvalidVoteReceipt
+        );
+
+        let vote_receipt_account = VoteReceiptAccount::load(&ctx.accounts.vote_receipt)?;
+        require!(
+            vote_receipt_account.voter == ctx.accounts.voter.key(),
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            vote_receipt_account.rent_payer == ctx.accounts.rent_payer.key(),
+            ErrorCode::InvalidRecipient
+        );
+
+        // Receipts have no discriminator, so close by hand: drain, wipe and hand back
+        let vote_receipt = ctx.accounts.vote_receipt.to_account_info();
+        let rent_recovered = vote_receipt.lamports();
+        let rent_payer_lamports = ctx
+            .accounts
+            .rent_payer
+            .lamports()
+            .checked_add(rent_recovered)
+            .ok_or(ErrorCode::MathOverflow)?;
+        **vote_receipt.try_borrow_mut_lamports()? = 0;
+        **ctx.accounts.rent_payer.try_borrow_mut_lamports()? = rent_payer_lamports;
+        vote_receipt.assign(&anchor_lang::system_program::ID);
+        vote_receipt.realloc(0, false)?;
+
+        msg!(
+            "Closed vote receipt for round {} - this vote can no longer be proven",
+            round_id
+        );
+
+        emit!(VoteReceiptClosedEvent {
+            voter: ctx.accounts.voter.key(),
+            round_id,
+            rent_recovered,
+        });
+
+        Ok(())
+    }
+
+    /// Tears down the proposal system and returns the rent held by the system
+    /// and round metadata accounts to the authority.
+    ///
+    /// Only callable by the system authority, and only when no proposals are
+    /// pending in the current round and no reveal is queued, so an in-flight vote is
+    /// never destroyed.
+    ///
+    /// Every payout instruction needs the system account, so fees left in a past round's
+    /// escrow would be stranded once it is gone. The remaining accounts must hold the
+    /// round escrow PDA of every past round, in round order, and each escrow that exists
+    /// must have paid out all of its fees (refunds, voter rewards, the winner's share, or
+    /// an emergency withdrawal). A system with more rounds than fit in one transaction
+    /// can't be closed.
+    pub fn close_proposal_system(ctx: Context<CloseProposalSystem>) -> Result<()> {
+        require!(
+            ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+            ErrorCode::InvalidAuthority
+        );
+
+        // Refuse to close while a round still has proposals waiting on a reveal
+        require!(
+            ctx.accounts.round_metadata.proposals_in_current_round == 0,
+            ErrorCode::RoundStillActive
+        );
+        require!(
+            !ctx.accounts.system_acc.reveal_in_progress,
+            ErrorCode::RevealAlreadyInProgress
+        );
+
+        let total_rounds = ctx.accounts.round_metadata.current_round;
+        require!(
+            ctx.remaining_accounts.len() as u64 == total_rounds,
+            ErrorCode::InvalidEscrowRoundId
+        );
+
+        let system_acc_key = ctx.accounts.system_acc.key();
+        for (round_id, round_escrow) in ctx.remaining_accounts.iter().enumerate() {
+            let (expected_round_escrow, _) = Pubkey::find_program_address(
+                &[
+                    b"round_escrow",
+                    system_acc_key.as_ref(),
+                    &(round_id as u64).to_le_bytes(),
+                ],
+                &crate::ID,
+            );
+            require!(
+                round_escrow.key() == expected_round_escrow,
+                ErrorCode::InvalidEscrowRoundId
+            );
+
+            // A round that ended without proposals never opened an escrow
+            if round_escrow.owner != &crate::ID || round_escrow.data_is_empty() {
+                continue;
+            }
+            let data = round_escrow.try_borrow_data()?;
+            let round_escrow = RoundEscrowAccount::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(ErrorCode::InvalidEscrowRoundId))?;
+            require!(round_escrow.fee_balance() == 0, ErrorCode::EscrowNotEmpty);
+        }
+
+        msg!("Closing proposal system after {} rounds", total_rounds);
+
+        emit!(SystemClosedEvent {
+            authority: ctx.accounts.payer.key(),
+            total_rounds,
+        });
+
+        Ok(())
+    }
+}
+
+/// Checks proposal text against the `ProposalAccount` size bounds.
+///
+/// Anchor's `max_len` only sizes the account, so oversized strings would otherwise fail
+/// during serialization with an opaque error.
+fn validate_proposal_fields(title: &str, description: &str, url: &str) -> Result<()> {
+    require!(!title.trim().is_empty(), ErrorCode::EmptyTitle);
+    require!(!description.trim().is_empty(), ErrorCode::EmptyDescription);
+    require!(title.len() <= MAX_TITLE_LEN, ErrorCode::FieldTooLong);
+    require!(
+        description.len() <= MAX_DESCRIPTION_LEN,
+        ErrorCode::FieldTooLong
+    );
+    require!(url.len() <= MAX_URL_LEN, ErrorCode::FieldTooLong);
+
+    Ok(())
+}
+
+/// Checks a proposal's co-submitter list: bounded, distinct, and not the submitter.
+fn validate_co_submitters(co_submitters: &[Pubkey], submitter: &Pubkey) -> Result<()> {
+    require!(
+        co_submitters.len() <= MAX_CO_SUBMITTERS,
+        ErrorCode::TooManyCoSubmitters
+    );
+    for (i, co_submitter) in co_submitters.iter().enumerate() {
+        require!(
+            co_submitter != submitter && !co_submitters[..i].contains(co_submitter),
+            ErrorCode::DuplicateCoSubmitter
+        );
+    }
+
+    Ok(())
+}
+
+/// Collects the submission fee and creates a proposal in the current round.
+///
+/// Shared by `submit_proposal` and `reveal_proposal`, which differ only in how the
+/// proposal text is committed to.
+fn create_proposal(
+    accounts: &mut SubmitProposal,
+    bumps: &SubmitProposalBumps,
+    title: String,
+    description: String,
+    url: String,
+    category: u8,
+    content_hash: [u8; 32],
+    co_submitters: Vec<Pubkey>,
+) -> Result<()> {
+    require!(!accounts.system_acc.paused, ErrorCode::SystemPaused);
+    require!(!accounts.system_acc.finished, ErrorCode::SystemFinished);
+
+    // Reject blank or oversized text before it reaches account serialization
+    validate_proposal_fields(&title, &description, &url)?;
+    validate_co_submitters(&co_submitters, &accounts.payer.key())?;
+    check_blacklist(&accounts.blacklist)?;
+
+    if let Some(submission_deadline) = accounts
+        .round_metadata
+        .submission_deadline(accounts.system_acc.submission_duration)
+    {
+        require!(
+            Clock::get()?.unix_timestamp <= submission_deadline,
+            ErrorCode::SubmissionClosed
+        );
+    }
+
+    let url_hash = hash(url.as_bytes()).to_bytes();
+
+    // The escrow is created (and flagged) by the round's first proposal
+    let is_first_proposal = !accounts.round_escrow.is_initialized;
+
+    // The round's limit is locked in by its first proposal
+    if is_first_proposal {
+        accounts.round_metadata.proposal_limit = accounts.round_metadata.next_proposal_limit;
+    }
+
+    // Check if we can add more proposals to this round
+    require!(
+        accounts.round_metadata.proposals_in_current_round < accounts.round_metadata.proposal_limit,
+        ErrorCode::MaxProposalsReached
+    );
+
+    check_duplicate_url(&accounts.round_metadata, &url, &url_hash, None)?;
+
+    let title_hash = normalized_title_hash(&title);
+    check_duplicate_title(&accounts.round_metadata, &title_hash, None)?;
+
+    let proposal_id_in_round = accounts.round_metadata.proposals_in_current_round;
+    let current_round = accounts.round_metadata.current_round;
+    let fee = accounts.system_acc.proposal_submission_fee;
+    let fee_mint = accounts.system_acc.fee_mint;
+
+    // Initialize round escrow if this is the first proposal in the round
+    if is_first_proposal {
+        init_round_escrow(
+            &mut accounts.round_escrow,
+            bumps.round_escrow,
+            current_round,
+            fee_mint,
+        )?;
+    }
+
+    // A round's fees are all paid in the currency it started with
+    require!(
+        accounts.round_escrow.fee_mint == fee_mint,
+        ErrorCode::FeeMintMismatch
+    );
+
+    // Validate escrow is for the correct round
+    require!(
+        accounts.round_escrow.round_id == current_round,
+        ErrorCode::InvalidEscrowRoundId
+    );
+
+    // Validate escrow is in active status
+    require!(
+        accounts.round_escrow.round_status == RoundStatus::Active,
+        ErrorCode::RoundEscrowNotActive
+    );
+
+    if let Some(fee_mint) = fee_mint {
+        collect_token_fee(accounts, fee_mint, fee)?;
+
+        msg!(
+            "Proposal submission fee collected: {} tokens of mint {} for round {}",
+            fee,
+            fee_mint,
+            current_round
+        );
+    } else {
+        // Check if payer has enough SOL for the fee
+        require!(
+            accounts.payer.lamports() >= fee,
+            ErrorCode::InsufficientFunds
+        );
+
+        // Snapshot the escrow so the transfer can be checked before any accounting
+        let escrow_lamports_before = accounts.round_escrow.to_account_info().lamports();
+
+        // REAL SOL TRANSFER: Payer → Round Escrow Account
+        let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
+            &accounts.payer.key(),
+            &accounts.round_escrow.key(),
+            fee,
+        );
+
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                accounts.payer.to_account_info(),
+                accounts.round_escrow.to_account_info(),
+                accounts.system_program.to_account_info(),
+            ],
+        )?;
+
+        // Only record the fee once the escrow provably received exactly that amount
+        let escrow_lamports_after = accounts.round_escrow.to_account_info().lamports();
+        require!(
+            escrow_lamports_after.checked_sub(escrow_lamports_before) == Some(fee),
+            ErrorCode::EscrowTransferMismatch
+        );
+
+        // Update escrow balance
+        let round_escrow = &mut accounts.round_escrow;
+        round_escrow.total_collected = round_escrow
+            .total_collected
+            .checked_add(fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        round_escrow.current_balance = round_escrow
+            .current_balance
+            .checked_add(fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "Proposal submission fee collected: {} lamports ({} SOL) for round {}",
+            fee,
+            fee as f64 / 1_000_000_000.0,
+            current_round
+        );
+    }
+
+    accounts.round_escrow.refundable_fees = accounts
+        .round_escrow
+        .refundable_fees
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    // Initialize the proposal account
+    accounts.proposal_acc.bump = bumps.proposal_acc;
+    accounts.proposal_acc.id = proposal_id_in_round;
+    accounts.proposal_acc.round_id = current_round;
+    accounts.proposal_acc.title = title;
+    accounts.proposal_acc.description = description;
+    accounts.proposal_acc.url = url;
+    accounts.proposal_acc.submitter = accounts.payer.key();
+    accounts.proposal_acc.vote_count = 0;
+    accounts.proposal_acc.fee_paid = fee;
+    accounts.proposal_acc.refunded = false;
+    accounts.proposal_acc.slashed = false;
+    accounts.proposal_acc.category = category;
+    accounts.proposal_acc.content_hash = content_hash;
+    accounts.proposal_acc.seconds = 0;
+    accounts.proposal_acc.co_submitters = co_submitters.clone();
+    accounts.proposal_acc.count_synced = false;
+
+    // Increment the round-specific proposal counter
+    accounts.round_metadata.url_hashes.push(url_hash);
+    accounts.round_metadata.title_hashes.push(title_hash);
+    accounts.round_metadata.proposal_categories[proposal_id_in_round as usize] = category;
+    accounts.round_metadata.proposal_seconds[proposal_id_in_round as usize] = 0;
+    accounts.round_metadata.proposals_in_current_round += 1;
+    
+    // Also increment global counter for tracking
+    accounts.system_acc.next_proposal_id += 1;
+
+    let submitter_stats = &mut accounts.submitter_stats;
+    submitter_stats.bump = bumps.submitter_stats;
+    submitter_stats.submitter = accounts.payer.key();
+    submitter_stats.total_submitted = submitter_stats
+        .total_submitted
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(SubmitterStatsUpdatedEvent {
+        submitter: submitter_stats.submitter,
+        total_submitted: submitter_stats.total_submitted,
+        total_wins: submitter_stats.total_wins,
+    });
+
+    emit!(ProposalSubmittedEvent {
+        proposal_id: proposal_id_in_round,
+        round_id: current_round,
+        submitter: accounts.payer.key(),
+        category,
+        content_hash,
+        co_submitters,
+    });
+
+    Ok(())
+}
+
+/// Opens a fresh escrow for `round_id`; called when the round gets its first proposal.
+fn init_round_escrow(
+    round_escrow: &mut RoundEscrowAccount,
+    bump: u8,
+    round_id: u64,
+    fee_mint: Option<Pubkey>,
+) -> Result<()> {
+    round_escrow.bump = bump;
+    round_escrow.round_id = round_id;
+    round_escrow.total_collected = 0;
+    round_escrow.total_distributed = 0;
+    round_escrow.current_balance = 0;
+    round_escrow.round_status = RoundStatus::Active;
+    round_escrow.created_at = Clock::get()?.unix_timestamp;
+    round_escrow.cancelled = false;
+    round_escrow.fee_mint = fee_mint;
+    round_escrow.token_collected = 0;
+    round_escrow.token_balance = 0;
+    round_escrow.is_initialized = true;
+    round_escrow.refundable_fees = 0;
+    Ok(())
+}
+
+/// Copies a tied proposal into the current round as part of a runoff.
+///
+/// `target` must be the uninitialized `["proposal", system_acc, round_id, id]` PDA; it is
+/// created here and written with the source's content. No fee is recorded, so the copy is
+/// never refundable or slashable for a fee it did not pay.
+fn create_runoff_proposal<'info>(
+    system_acc: &Pubkey,
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    source: &ProposalAccount,
+    round_id: u64,
+    id: u8,
+) -> Result<()> {
+    let round_id_bytes = round_id.to_le_bytes();
+    let id_bytes = id.to_le_bytes();
+    let (expected_pda, bump) = Pubkey::find_program_address(
+        &[b"proposal", system_acc.as_ref(), &round_id_bytes, &id_bytes],
+        &crate::ID,
+    );
+    require!(
+        target.key() == expected_pda,
+        ErrorCode::InvalidRunoffProposals
+    );
+    require!(target.data_is_empty(), ErrorCode::AccountAlreadyInitialized);
+
+    let space = 8 + ProposalAccount::INIT_SPACE;
+    let lamports = Rent::get()?.minimum_balance(space);
+    let create_account_ix = anchor_lang::solana_program::system_instruction::create_account(
+        &payer.key(),
+        &expected_pda,
+        lamports,
+        space as u64,
+        &crate::ID,
+    );
+    anchor_lang::solana_program::program::invoke_signed(
+        &create_account_ix,
+        &[payer.clone(), target.clone(), system_program.clone()],
+        &[&[
+            b"proposal",
+            system_acc.as_ref(),
+            &round_id_bytes,
+            &id_bytes,
+            &[bump],
+        ]],
+    )?;
+
+    let proposal = ProposalAccount {
+        bump,
+        id,
+        round_id,
+        submitter: source.submitter,
+        vote_count: 0,
+        title: source.title.clone(),
+        description: source.description.clone(),
+        url: source.url.clone(),
+        fee_paid: 0,
+        refunded: false,
+        slashed: false,
+        category: source.category,
+        content_hash: source.content_hash,
+        // The proposal already cleared seconding in the round it tied in
+        seconds: source.seconds,
+        co_submitters: source.co_submitters.clone(),
+        count_synced: false,
+    };
+    let mut data = target.try_borrow_mut_data()?;
+    proposal.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Resolves the computation offset a queueing instruction will use.
+///
+/// `AUTO_COMPUTATION_OFFSET` selects the system's counter, namespaced by system id in
+/// the high 32 bits since every system in the program shares one MXE; any other value
+/// is the caller's explicit offset and is used as given.
+fn resolve_computation_offset(system_acc: &ProposalSystemAccount, computation_offset: u64) -> u64 {
+    if computation_offset == AUTO_COMPUTATION_OFFSET {
+        ((system_acc.system_id as u64) << 32)
+            | (system_acc.next_computation_offset & u32::MAX as u64)
+    } else {
+        computation_offset
+    }
+}
+
+/// Resolves the computation offset and, in auto mode, advances the system's counter
+/// so the next auto-assigned computation gets a fresh PDA.
+fn consume_computation_offset(
+    system_acc: &mut ProposalSystemAccount,
+    computation_offset: u64,
+) -> Result<u64> {
+    let resolved = resolve_computation_offset(system_acc, computation_offset);
+    if computation_offset == AUTO_COMPUTATION_OFFSET {
+        system_acc.next_computation_offset = system_acc
+            .next_computation_offset
+            .checked_add(1)
+            .filter(|next| *next <= u32::MAX as u64)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    Ok(resolved)
+}
+
+/// Moves a token submission fee from the submitter into the round's escrow token account.
+///
+/// The escrow token account must hold `fee_mint` and be owned by the round escrow PDA,
+/// so only the program can move the tokens back out.
+fn collect_token_fee(accounts: &mut SubmitProposal, fee_mint: Pubkey, fee: u64) -> Result<()> {
+    let submitter_token_account = accounts
+        .submitter_token_account
+        .as_ref()
+        .ok_or(ErrorCode::MissingTokenAccounts)?;
+    let escrow_token_account = accounts
+        .escrow_token_account
+        .as_ref()
+        .ok_or(ErrorCode::MissingTokenAccounts)?;
+    let token_program = accounts
+        .token_program
+        .as_ref()
+        .ok_or(ErrorCode::MissingTokenAccounts)?;
+
+    require!(
+        submitter_token_account.mint == fee_mint
+            && submitter_token_account.owner == accounts.payer.key(),
+        ErrorCode::InvalidFeeTokenAccount
+    );
+    require!(
+        escrow_token_account.mint == fee_mint
+            && escrow_token_account.owner == accounts.round_escrow.key(),
+        ErrorCode::InvalidFeeTokenAccount
+    );
+    require!(
+        submitter_token_account.amount >= fee,
+        ErrorCode::InsufficientFunds
+    );
+
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: submitter_token_account.to_account_info(),
+                to: escrow_token_account.to_account_info(),
+                authority: accounts.payer.to_account_info(),
+            },
+        ),
+        fee,
+    )?;
+
+    let round_escrow = &mut accounts.round_escrow;
+    round_escrow.token_collected = round_escrow
+        .token_collected
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    round_escrow.token_balance = round_escrow
+        .token_balance
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
+
+/// Rejects a non-empty URL already used by another proposal in the current round.
+///
+/// `exclude` skips the proposal being edited, so it can keep its own URL.
+fn check_duplicate_url(
+    round_metadata: &RoundMetadataAccount,
+    url: &str,
+    url_hash: &[u8; 32],
+    exclude: Option<u8>,
+) -> Result<()> {
+    // Proposals without a link can't be duplicates of each other
+    if url.is_empty() {
+        return Ok(());
+    }
+
+    let duplicate = round_metadata
+        .url_hashes
+        .iter()
+        .enumerate()
+        .any(|(i, existing)| Some(i as u8) != exclude && existing == url_hash);
+    require!(!duplicate, ErrorCode::DuplicateProposalUrl);
+
+    Ok(())
+}
+
+/// sha256 of a title after trimming and lowercasing, so "Build X" and "build x " match.
+fn normalized_title_hash(title: &str) -> [u8; 32] {
+    hash(title.trim().to_lowercase().as_bytes()).to_bytes()
+}
+
+/// Rejects a title already used (after normalization) by another proposal in the
+/// current round.
+///
+/// `exclude` skips the proposal being edited, so it can keep its own title.
+fn check_duplicate_title(
+    round_metadata: &RoundMetadataAccount,
+    title_hash: &[u8; 32],
+    exclude: Option<u8>,
+) -> Result<()> {
+    let duplicate = round_metadata
+        .title_hashes
+        .iter()
+        .enumerate()
+        .any(|(i, existing)| Some(i as u8) != exclude && existing == title_hash);
+    require!(!duplicate, ErrorCode::DuplicateProposalTitle);
+
+    Ok(())
+}
+
+/// Checks that the authority may reveal the current round's winner now.
+///
+/// With a reveal threshold set, any authority or co-authority may reveal once enough
+/// current co-authorities have approved the round. `payer` is None for permissionless
+/// reveals, which skip the caller check but still need the approvals. Returns the
+/// round's voting deadline.
+fn validate_reveal(
+    system_acc: &ProposalSystemAccount,
+    round_metadata: &RoundMetadataAccount,
+    reveal_approval: Option<&RevealApprovalAccount>,
+    payer: Option<&Pubkey>,
+) -> Result<i64> {
+    if system_acc.reveal_threshold == 0 {
+        if let Some(payer) = payer {
+            require!(*payer == system_acc.authority, ErrorCode::InvalidAuthority);
+        }
+    } else {
+        if let Some(payer) = payer {
+            require!(
+                *payer == system_acc.authority || system_acc.authorities.contains(payer),
+                ErrorCode::InvalidAuthority
+            );
+        }
+
+        let reveal_approval = reveal_approval.ok_or(ErrorCode::RevealNotApproved)?;
+        require!(
+            reveal_approval.round_id == round_metadata.current_round,
+            ErrorCode::RevealNotApproved
+        );
+        require!(
+            count_reveal_approvals(system_acc, reveal_approval)
+                >= system_acc.reveal_threshold as usize,
+            ErrorCode::RevealNotApproved
+        );
+    }
+
+    validate_round_revealable(system_acc, round_metadata)
+}
+
+/// Checks that the current round has votes and enough proposals, has closed and has
+/// met quorum, so its tally may be revealed. Returns the round's voting deadline.
+fn validate_round_revealable(
+    system_acc: &ProposalSystemAccount,
+    round_metadata: &RoundMetadataAccount,
+) -> Result<i64> {
+    // A queued reveal will advance the round when its callback lands
+    require!(
+        !system_acc.reveal_in_progress,
+        ErrorCode::RevealAlreadyInProgress
+    );
+
+    require!(round_metadata.total_voters > 0, ErrorCode::NoVotesCast);
+
+    require!(
+        round_metadata.proposals_in_current_round >= system_acc.min_proposals,
+        ErrorCode::NotEnoughProposals
+    );
+
+    // The tally can only be revealed once voting has closed
+    let voting_deadline = round_metadata.voting_deadline(system_acc.voting_duration);
+    require!(
+        Clock::get()?.unix_timestamp > voting_deadline,
+        ErrorCode::VotingStillOpen
+    );
+
+    // Weighted systems measure quorum in voting power; the headcount would let a
+    // handful of dust holders meet it
+    if system_acc.governance_mint.is_some() {
+        require!(
+            round_metadata.total_voting_power >= system_acc.min_voting_power,
+            ErrorCode::QuorumNotMet
+        );
+    } else {
+        require!(
+            round_metadata.total_voters >= system_acc.min_quorum,
+            ErrorCode::QuorumNotMet
+        );
+    }
+
+    Ok(voting_deadline)
+}
+
+/// Counts approvals from keys that are still co-authorities, so removing an
+/// authority also withdraws its approval.
+fn count_reveal_approvals(
+    system_acc: &ProposalSystemAccount,
+    reveal_approval: &RevealApprovalAccount,
+) -> usize {
+    reveal_approval
+        .approvers
+        .iter()
+        .filter(|approver| system_acc.authorities.contains(approver))
+        .count()
+}
+
+/// Stores a revealed winner and advances to the next round.
+fn record_winner(
+    system_acc: &mut ProposalSystemAccount,
+    round_metadata: &mut RoundMetadataAccount,
+    winning_proposal_id: u8,
+    net_score: i64,
+    tied_mask: u32,
+    margin: u64,
+    all_negative: bool,
+) -> Result<()> {
+    // Counts are only negative under net voting, where a losing score has no votes to reward
+    let winning_vote_count = net_score.max(0) as u64;
+    let is_tie = tied_mask.count_ones() > 1;
+
+    // Store the winning proposal ID and vote count on-chain in the system account
+    system_acc.winning_proposal_id = Some(winning_proposal_id);
+    system_acc.winning_vote_count = Some(winning_vote_count);
+    system_acc.winner_was_tie = is_tie;
+    system_acc.winner_tied_mask = tied_mask;
+    system_acc.winning_margin = Some(margin);
+    system_acc.all_scores_negative = all_negative;
+
+    // Get current round before incrementing
+    let current_round_id = round_metadata.current_round;
+
+    // Note: Round history account will be created in a separate instruction
+
+    // Increment the round counter for the next voting round
+    round_metadata.current_round += 1;
+    
+    // Update the round start timestamp
+    round_metadata.round_started = Clock::get()?.unix_timestamp;
+    round_metadata.round_started_slot = Clock::get()?.slot;
+    round_metadata.round_extension = 0;
+    round_metadata.commit_deadline = None;
+    round_metadata.reveal_deadline = None;
+
+    // Time-boxed systems stop once their last round has a winner
+    let finished = system_acc
+        .max_rounds
+        .is_some_and(|max_rounds| round_metadata.current_round >= max_rounds);
+    if finished {
+        system_acc.finished = true;
+    }
+
+    msg!(
+        "Round {} completed - Winner: Proposal {} with {} votes",
+        current_round_id,
+        winning_proposal_id,
+        winning_vote_count
+    );
+
+    emit!(WinningProposalEvent {
+        winning_proposal_id,
+        winning_vote_count,
+        round_id: current_round_id,
+        is_tie,
+        margin,
+        net_score,
+        all_negative,
+    });
+
+    if finished {
+        msg!(
+            "System finished after {} rounds",
+            round_metadata.current_round
+        );
+
+        emit!(SystemFinishedEvent {
+            total_rounds: round_metadata.current_round,
+        });
+    } else {
+        emit!(RoundStartedEvent {
+            round_id: round_metadata.current_round,
+            round_started: round_metadata.round_started,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks a Merkle proof that `(voter, weight)` is a leaf of the voter snapshot.
+///
+/// Each step hashes the running node with the next sibling in sorted order, so the
+/// proof needs no left/right flags.
+fn verify_weight_proof(root: &[u8; 32], voter: &Pubkey, weight: u64, proof: &[[u8; 32]]) -> bool {
+    if proof.len() > MAX_WEIGHT_PROOF_DEPTH {
+        return false;
+    }
+
+    let mut node = keccak::hashv(&[voter.as_ref(), &weight.to_le_bytes()]).to_bytes();
+    for sibling in proof {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+
+    node == *root
+}
+
+/// Checks that a vote targets an existing proposal in the current, still-open round.
+fn validate_vote(
+    system_acc: &ProposalSystemAccount,
+    round_metadata: &RoundMetadataAccount,
+    round_id: u64,
+    proposal_id: u8,
+) -> Result<()> {
+    // Single-choice ballots can't be mixed into an approval or net tally
+    require!(
+        !system_acc.approval_voting,
+        ErrorCode::ApprovalVotingEnabled
+    );
+    require!(!system_acc.net_voting, ErrorCode::NetVotingEnabled);
+
+    validate_voting_open(system_acc, round_metadata, round_id)?;
+
+    // For round-based proposals, we need to check if the proposal exists in the current round
+    // We'll validate this by checking if the proposal_id is less than the proposals in current round
+    require!(
+        proposal_id < round_metadata.proposals_in_current_round,
+        ErrorCode::InvalidProposalId
+    );
+
+    // Proposals only reach the ballot once they have been seconded enough times
+    require!(
+        round_metadata.proposal_seconds[proposal_id as usize] >= system_acc.required_seconds,
+        ErrorCode::ProposalNotSeconded
+    );
+
+    Ok(())
+}
+
+/// Bitmask of the current round's proposals that may receive votes: bit `i` is set
+/// when proposal `i` exists and has been seconded `required_seconds` times.
+///
+/// The plaintext `proposal_id` checks only cover what the voter claims; the tally
+/// circuits take this mask so the encrypted ballot itself can't reach an unseconded
+/// proposal.
+fn seconded_mask(system_acc: &ProposalSystemAccount, round_metadata: &RoundMetadataAccount) -> u32 {
+    round_metadata
+        .proposal_seconds
+        .iter()
+        .take(round_metadata.proposals_in_current_round as usize)
+        .enumerate()
+        .filter(|(_, &seconds)| seconds >= system_acc.required_seconds)
+        .fold(0u32, |mask, (i, _)| mask | (1 << i))
+}
+
+/// Checks that the system accepts votes for `round_id` right now.
+fn validate_voting_open(
+    system_acc: &ProposalSystemAccount,
+    round_metadata: &RoundMetadataAccount,
+    round_id: u64,
+) -> Result<()> {
+    require!(!system_acc.paused, ErrorCode::SystemPaused);
+    require!(!system_acc.finished, ErrorCode::SystemFinished);
+    // The tally has no valid ciphertext until its reset computation lands
+    require!(
+        !system_acc.tally_reset_pending,
+        ErrorCode::TallyResetPending
+    );
+
+    // Validate that the round_id matches the current active round
+    require!(
+        round_id == round_metadata.current_round,
+        ErrorCode::InvalidRoundId
+    );
+
+    // Votes only open once submissions have closed
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    if let Some(submission_deadline) =
+        round_metadata.submission_deadline(system_acc.submission_duration)
+    {
+        require!(
+            current_timestamp > submission_deadline,
+            ErrorCode::VotingNotOpen
+        );
+    }
+
+    // Reject votes once the round's voting window has elapsed
+    let voting_deadline = round_metadata.voting_deadline(system_acc.voting_duration);
+    require!(
+        current_timestamp <= voting_deadline,
+        ErrorCode::VotingClosed
+    );
+
+    Ok(())
+}
+
+/// Shared body of `pause_voting` and `resume_voting`.
+fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    require!(
+        ctx.accounts.payer.key() == ctx.accounts.system_acc.authority,
+        ErrorCode::InvalidAuthority
+    );
+
+    ctx.accounts.system_acc.paused = paused;
+
+    msg!("Voting {}", if paused { "paused" } else { "resumed" });
+
+    emit!(PauseStateChangedEvent { paused });
+
+    Ok(())
+}
+
+/// Rejects votes once the round's escrow has stopped collecting: it must belong to the
+/// current round and still be `Active`, i.e. not yet completed or cancelled.
+fn check_round_escrow_open(
+    round_escrow: &RoundEscrowAccount,
+    round_metadata: &RoundMetadataAccount,
+) -> Result<()> {
+    require!(
+        round_escrow.round_id == round_metadata.current_round,
+        ErrorCode::InvalidEscrowRoundId
+    );
+    require!(
+        round_escrow.round_status == RoundStatus::Active,
+        ErrorCode::RoundEscrowNotActive
+    );
+    Ok(())
+}
+
+/// Checks the voter against the allowlist once allowlist enforcement is enabled.
+fn check_allowlist(
+    system_acc: &ProposalSystemAccount,
+    allowlist: &Option<Account<AllowlistAccount>>,
+    voter: &Pubkey,
+) -> Result<()> {
+    if !system_acc.allowlist_enabled {
+        return Ok(());
+    }
+
+    let allowlist = allowlist.as_ref().ok_or(ErrorCode::VoterNotAllowlisted)?;
+    require!(
+        allowlist.voters.contains(voter),
+        ErrorCode::VoterNotAllowlisted
+    );
+
+    Ok(())
+}
+
+/// Rejects a SOL submission fee below the rent-exempt minimum of a `ProposalAccount`.
+fn check_sol_fee_floor(fee: u64) -> Result<()> {
+    let min_fee = Rent::get()?.minimum_balance(8 + ProposalAccount::INIT_SPACE);
+    require!(fee >= min_fee, ErrorCode::FeeTooLow);
+
+    Ok(())
+}
+
+/// Rejects a key the authority has blacklisted.
+///
+/// `blacklist` is the key's `["blacklist", system_acc, key]` PDA (checked by the account
+/// constraints); the key is banned exactly while that account exists.
+fn check_blacklist(blacklist: &AccountInfo) -> Result<()> {
+    require!(
+        blacklist.owner != &crate::ID || blacklist.data_is_empty(),
+        ErrorCode::VoterBlacklisted
+    );
+
+    Ok(())
+}
+
+/// Rejects `voter` if blacklisted, for voters whose blacklist PDA is passed as a
+/// remaining account instead of through the account constraints.
+fn check_blacklist_for(system_acc: &Pubkey, voter: &Pubkey, blacklist: &AccountInfo) -> Result<()> {
+    let (expected_blacklist, _) = Pubkey::find_program_address(
+        &[b"blacklist", system_acc.as_ref(), voter.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        blacklist.key() == expected_blacklist,
+        ErrorCode::InvalidBlacklistAccount
+    );
+
+    check_blacklist(blacklist)
+}
+
+/// Rejects a vote arriving within the system's cooldown of the same voter's previous
+/// vote in the round, then records this vote's slot.
+fn enforce_vote_rate_limit(
+    system_acc: &ProposalSystemAccount,
+    voter_rate: &mut VoterRateAccount,
+    bump: u8,
+    voter: Pubkey,
+    round_id: u64,
+) -> Result<()> {
+    let current_slot = Clock::get()?.slot;
+    let cooldown_slots = system_acc.rate_limit.cooldown_slots;
+
+    // A fresh account or a record from an earlier round never throttles
+    if cooldown_slots > 0 && voter_rate.voter == voter && voter_rate.round_id == round_id {
+        let next_allowed_slot = voter_rate.last_vote_slot.saturating_add(cooldown_slots);
+        if current_slot < next_allowed_slot {
+            emit!(RateLimitedEvent {
+                voter,
+                round_id,
+                last_vote_slot: voter_rate.last_vote_slot,
+                next_allowed_slot,
+            });
+            return err!(ErrorCode::VoteRateLimited);
+        }
+    }
+
+    voter_rate.bump = bump;
+    voter_rate.voter = voter;
+    voter_rate.round_id = round_id;
+    voter_rate.last_vote_slot = current_slot;
+
+    Ok(())
+}
+
+/// Rejects votes from the system authority unless `authority_can_vote` is set.
+///
+/// The authority can reveal tallies, so by default it shouldn't also vote in secret.
+fn check_authority_vote(system_acc: &ProposalSystemAccount, voter: &Pubkey) -> Result<()> {
+    require!(
+        system_acc.authority_can_vote || *voter != system_acc.authority,
+        ErrorCode::AuthorityCannotVote
+    );
+    Ok(())
+}
+
+/// Creates and writes the voter's receipt PDA for a round.
+///
+/// The receipt is derived from `["vote_receipt", system_acc, voter, round_id]`, so a second vote
+/// in the same round fails because the account already exists. Returns the timestamp
+/// recorded in the receipt.
+///
+/// The stored ciphertext must be the ballot being tallied (`vote`), and its nonce is kept
+/// alongside it, so later proofs about the receipt are proofs about the counted vote.
+#[allow(clippy::too_many_arguments)]
+fn create_vote_receipt<'info>(
+    system_acc: &Pubkey,
+    payer: &AccountInfo<'info>,
+    vote_receipt: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    round_id: u64,
+    encrypted_proposal_id: [u8; 32],
+    vote: [u8; 32],
+    vote_encryption_pubkey: [u8; 32],
+    vote_nonce: u128,
+) -> Result<i64> {
+    create_vote_receipt_for(
+        system_acc,
+        &payer.key(),
+        payer,
+        vote_receipt,
+        system_program,
+        round_id,
+        encrypted_proposal_id,
+        vote,
+        vote_encryption_pubkey,
+        vote_nonce,
+        false,
+    )
+}
+
+/// Creates the vote receipt of `voter`, funded by `payer`.
+///
+/// Used directly when a delegate votes on behalf of delegators, so each delegator is
+/// marked as having voted in the round. `delegated` is recorded on the receipt.
+#[allow(clippy::too_many_arguments)]
+fn create_vote_receipt_for<'info>(
+    system_acc: &Pubkey,
+    voter: &Pubkey,
+    payer: &AccountInfo<'info>,
+    vote_receipt: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    round_id: u64,
+    encrypted_proposal_id: [u8; 32],
+    vote: [u8; 32],
+    vote_encryption_pubkey: [u8; 32],
+    vote_nonce: u128,
+    delegated: bool,
+) -> Result<i64> {
+    // A receipt holding a different ciphertext than the tallied one could never be
+    // verified against the count
+    require!(encrypted_proposal_id == vote, ErrorCode::VoteMismatch);
+
+    // Manually derive the vote_receipt PDA
+    let round_id_bytes = round_id.to_le_bytes();
+    let (expected_vote_receipt_pda, vote_receipt_bump) = Pubkey::find_program_address(
+        &[
+            b"vote_receipt",
+            system_acc.as_ref(),
+            voter.as_ref(),
+            &round_id_bytes,
+        ],
+        &crate::ID,
+    );
+
+    // Manually verify the vote_receipt account
+    require!(
+        vote_receipt.key() == expected_vote_receipt_pda,
+        ErrorCode::InvalidAuthority
+    );
+
+    // Check if the account is already initialized
+    require!(
+        vote_receipt.data_is_empty(),
+        ErrorCode::AccountAlreadyInitialized
+    );
+
+    // Get current timestamp for the vote receipt
+    let clock = Clock::get()?;
+    let current_timestamp = clock.unix_timestamp;
+
+    // Create the vote receipt account using system program
+    let space = 8 + VoteReceiptAccount::INIT_SPACE;
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(space);
+
+    // Fail with a clear error rather than an opaque system program failure. The
+    // transaction fee has already been deducted; the MPC fee is charged afterwards
+    // by Arcium and is checked there.
+    require!(payer.lamports() >= lamports, ErrorCode::InsufficientFunds);
+
+    let create_account_ix = anchor_lang::solana_program::system_instruction::create_account(
+        &payer.key(),
+        &expected_vote_receipt_pda,
+        lamports,
+        space as u64,
+        &crate::ID,
+    );
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &create_account_ix,
+        &[payer.clone(), vote_receipt.clone(), system_program.clone()],
+        &[&[
+            b"vote_receipt",
+            system_acc.as_ref(),
+            voter.as_ref(),
+            &round_id_bytes,
+            &[vote_receipt_bump],
+        ]],
+    )?;
+
+    // Manually initialize the vote receipt account
+    let vote_receipt_account = VoteReceiptAccount {
+        bump: vote_receipt_bump,
+        voter: *voter,
+        encrypted_proposal_id,
+        timestamp: current_timestamp,
+        vote_encryption_pubkey,
+        reward_claimed: false,
+        awaiting_tally: false,
+        vote_nonce,
+        reward_registered: false,
+        tally_computation_offset: 0,
+        tally_failed: false,
+        rent_payer: payer.key(),
+        change_pending: false,
+        pending_proposal_id: [0; 32],
+        pending_encryption_pubkey: [0; 32],
+        pending_nonce: 0,
+        delegated,
+    };
+
+    // Serialize and write the account data
+    vote_receipt_account.store(vote_receipt)?;
+
+    Ok(current_timestamp)
+}
+
+/// Flags or clears a receipt's outstanding `vote_for_proposal` tally.
+fn set_awaiting_tally(vote_receipt: &AccountInfo, awaiting_tally: bool) -> Result<()> {
+    let mut vote_receipt_account = VoteReceiptAccount::load(vote_receipt)?;
+    vote_receipt_account.awaiting_tally = awaiting_tally;
+    vote_receipt_account.store(vote_receipt)
+}
+
+/// Records that the receipt's ballot was queued for tallying under `computation_offset`.
+fn record_tally_queued(vote_receipt: &AccountInfo, computation_offset: u64) -> Result<()> {
+    let mut vote_receipt_account = VoteReceiptAccount::load(vote_receipt)?;
+    vote_receipt_account.awaiting_tally = true;
+    vote_receipt_account.tally_failed = false;
+    vote_receipt_account.tally_computation_offset = computation_offset;
+    vote_receipt_account.store(vote_receipt)
+}
+
+/// Marks the receipt's outstanding tally as aborted, from the vote callback.
+fn record_tally_failed(vote_receipt: &AccountInfo) -> Result<()> {
+    let mut vote_receipt_account = VoteReceiptAccount::load(vote_receipt)?;
+    vote_receipt_account.tally_failed = true;
+    vote_receipt_account.store(vote_receipt)
+}
+
+/// Adds a weighted vote's power to the current round's total.
+fn record_voting_power(round_metadata: &mut RoundMetadataAccount, weight: u64) -> Result<()> {
+    round_metadata.total_voting_power = round_metadata
+        .total_voting_power
+        .checked_add(weight as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+/// Counts a new voter in the current round and signals when quorum is first reached.
+fn record_voter(round_metadata: &mut RoundMetadataAccount, min_quorum: u64, round_id: u64) {
+    // Increment total voter count for this round
+    round_metadata.total_voters += 1;
+
+    // Let clients know as soon as the round becomes revealable
+    if round_metadata.total_voters == min_quorum {
+        emit!(QuorumStatusEvent {
+            round_id,
+            total_voters: round_metadata.total_voters,
+            min_quorum,
+        });
+    }
+}
+
+/// Unix timestamp after which winning voters can no longer register for a round's
+/// voter reward pool.
+fn reward_registration_end(round_history: &VotingRoundHistoryAccount) -> i64 {
+    round_history
+        .revealed_at
+        .saturating_add(REWARD_REGISTRATION_PERIOD)
+}
+
+/// Checks that `vote_nonce` is the nonce the receipt's ballot was cast with.
+///
+/// The ciphertext alone doesn't pin the choice: the same bytes decrypt to a different
+/// proposal under another nonce, so MPC checks on a receipt must use the stored one.
+fn check_vote_nonce(vote_receipt: &VoteReceiptAccount, vote_nonce: u128) -> Result<()> {
+    require!(
+        vote_receipt.vote_nonce == vote_nonce,
+        ErrorCode::VoteNonceMismatch
+    );
+    Ok(())
+}
+
+/// Reads a round history passed as an unchecked account, rejecting foreign, truncated
+/// or mistyped data with `InvalidRoundId` instead of panicking.
+fn load_round_history(info: &AccountInfo) -> Result<VotingRoundHistoryAccount> {
+    require!(info.owner == &crate::ID, ErrorCode::InvalidRoundId);
+
+    let data = info.try_borrow_data()?;
+    VotingRoundHistoryAccount::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(ErrorCode::InvalidRoundId))
+}
+
+/// Loads the caller's vote receipt for a round and checks it records `vote`.
+///
+/// Verifies the receipt sits at `["vote_receipt", system_acc, voter, round_id]` and that
+/// its stored ciphertext, encryption key and nonce equal the ones being checked, so a
+/// proof can only ever be produced for the vote that was actually cast.
+fn load_matching_vote_receipt(
+    system_acc: &Pubkey,
+    voter: &Pubkey,
+    vote_receipt: &AccountInfo,
+    round_id: u64,
+    vote: [u8; 32],
+    vote_encryption_pubkey: [u8; 32],
+    vote_nonce: u128,
+) -> Result<VoteReceiptAccount> {
+    let round_id_bytes = round_id.to_le_bytes();
+    let (expected_vote_receipt_pda, _) = Pubkey::find_program_address(
+        &[
+            b"vote_receipt",
+            system_acc.as_ref(),
+            voter.as_ref(),
+            &round_id_bytes,
+        ],
+        &crate::ID,
+    );
+    require!(
+        vote_receipt.key() == expected_vote_receipt_pda,
+        ErrorCode::InvalidVoteReceipt
+    );
+
+    let vote_receipt_account = VoteReceiptAccount::load(vote_receipt)?;
+
+    // The vote being checked must be the one that was actually cast
+    require!(
+        vote_receipt_account.encrypted_proposal_id == vote,
+        ErrorCode::VoteMismatch
+    );
+
+    // The ciphertext only decrypts to the cast choice under the key and nonce it was
+    // cast with, so substitutes must not be accepted
+    require!(
+        vote_receipt_account.vote_encryption_pubkey == vote_encryption_pubkey,
+        ErrorCode::VoteEncryptionKeyMismatch
+    );
+    check_vote_nonce(&vote_receipt_account, vote_nonce)?;
+
+    Ok(vote_receipt_account)
+}
+
+/// Reports an aborted MPC computation from its callback.
+///
+/// Returns `Ok` so the `ComputationAbortedEvent` isn't rolled back with the
+/// transaction. Callbacks call this before touching any state, so the aborted
+/// instruction can simply be queued again.
+fn computation_aborted(instruction_name: &str, round_id: Option<u64>) -> Result<()> {
+    msg!("Computation for {} aborted", instruction_name);
+
+    emit!(ComputationAbortedEvent {
+        instruction_name: instruction_name.to_string(),
+        round_id,
+    });
+
+    Ok(())
+}
+
+/// Moves lamports out of a round escrow PDA.
+///
+/// The escrow is owned by this program and carries data, so the system program
+/// refuses to transfer from it; lamports are debited directly instead. Only the
+/// fee balance is ever moved, which leaves the escrow's rent-exempt reserve intact.
+fn transfer_from_escrow<'info>(
+    escrow: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        escrow.lamports() >= amount,
+        ErrorCode::InsufficientEscrowBalance
+    );
+
+    let recipient_lamports = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    **escrow.try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? = recipient_lamports;
+
+    Ok(())
+}
+
+/// Picks the account an escrow payout is sent to: the wallet itself for SOL fees, the
+/// wallet's fee token account for token fees.
+fn escrow_recipient<'info>(
+    round_escrow: &RoundEscrowAccount,
+    wallet: &AccountInfo<'info>,
+    token_account: Option<&Account<'info, TokenAccount>>,
+) -> Result<AccountInfo<'info>> {
+    if round_escrow.fee_mint.is_none() {
+        return Ok(wallet.clone());
+    }
+    token_account
+        .map(|account| account.to_account_info())
+        .ok_or(error!(ErrorCode::MissingTokenAccounts))
+}
+
+/// Pays `amount` of a round's fees out of its escrow.
+///
+/// SOL fees are debited from the escrow's lamports (see `transfer_from_escrow`). Token
+/// fees move from the escrow token account to `recipient`, which must then be a token
+/// account of the fee mint belonging to `owner`; the escrow PDA owns the escrow token
+/// account, so it signs the transfer. The caller records the payout with
+/// `RoundEscrowAccount::record_payout`.
+fn pay_from_escrow<'info>(
+    system_acc: &Pubkey,
+    round_escrow: &Account<'info, RoundEscrowAccount>,
+    recipient: &AccountInfo<'info>,
+    owner: &Pubkey,
+    escrow_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    amount: u64,
+) -> Result<()> {
+    let Some(fee_mint) = round_escrow.fee_mint else {
+        return transfer_from_escrow(&round_escrow.to_account_info(), recipient, amount);
+    };
+
+    let escrow_token_account = escrow_token_account.ok_or(ErrorCode::MissingTokenAccounts)?;
+    let token_program = token_program.ok_or(ErrorCode::MissingTokenAccounts)?;
+
+    require!(
+        escrow_token_account.mint == fee_mint && escrow_token_account.owner == round_escrow.key(),
+        ErrorCode::InvalidFeeTokenAccount
+    );
+    require!(
+        escrow_token_account.amount >= amount,
+        ErrorCode::InsufficientEscrowBalance
+    );
+    require!(
+        recipient.owner == &token::ID,
+        ErrorCode::InvalidFeeTokenAccount
+    );
+    let recipient_token_account =
+        TokenAccount::try_deserialize(&mut &recipient.try_borrow_data()?[..])?;
+    require!(
+        recipient_token_account.mint == fee_mint && recipient_token_account.owner == *owner,
+        ErrorCode::InvalidFeeTokenAccount
+    );
+
+    let round_id_bytes = round_escrow.round_id.to_le_bytes();
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: escrow_token_account.to_account_info(),
+                to: recipient.clone(),
+                authority: round_escrow.to_account_info(),
+            },
+            &[&[
+                b"round_escrow",
+                system_acc.as_ref(),
+                &round_id_bytes,
+                &[round_escrow.bump],
+            ]],
+        ),
+        amount,
+    )
+}
+
+#[queue_computation_accounts("init_proposal_votes", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, system_id: u32)]
+pub struct InitProposalSystem<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PROPOSAL_VOTES)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProposalSystemAccount::INIT_SPACE,
+        seeds = [b"proposal_system", system_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RoundMetadataAccount::INIT_SPACE,
+        seeds = [b"round_metadata", system_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[callback_accounts("init_proposal_votes")]
+#[derive(Accounts)]
+pub struct InitProposalVotesCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PROPOSAL_VOTES)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: system_acc, checked by the callback account key passed in queue_computation
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    
+}
+
+#[queue_computation_accounts("init_proposal_votes", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RetryTallyReset<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PROPOSAL_VOTES)
+    )]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[init_computation_definition_accounts("init_proposal_votes", payer)]
+#[derive(Accounts)]
+pub struct InitProposalVotesCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProposalAccount::INIT_SPACE,
+        seeds = [
+            b"proposal", 
+            system_acc.key().as_ref(), 
+            round_metadata.current_round.to_le_bytes().as_ref(),
+            round_metadata.proposals_in_current_round.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RoundEscrowAccount::INIT_SPACE,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SubmitterStatsAccount::INIT_SPACE,
+        seeds = [b"submitter", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub submitter_stats: Box<Account<'info, SubmitterStatsAccount>>,
+    pub system_program: Program<'info, System>,
+    /// Submitter's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub submitter_token_account: Option<Account<'info, TokenAccount>>,
+    /// Round escrow's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetNextRoundLimit<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetCommitDeadline<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[derive(Accounts)]
+pub struct CommitProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProposalCommitAccount::INIT_SPACE,
+        seeds = [
+            b"proposal_commit",
+            system_acc.key().as_ref(),
+            round_metadata.current_round.to_le_bytes().as_ref(),
+            payer.key().as_ref()
+        ],
+        bump,
+    )]
+    pub proposal_commit: Account<'info, ProposalCommitAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealProposal<'info> {
+    pub submit: SubmitProposal<'info>,
+    #[account(
+        mut,
+        close = submitter,
+        seeds = [
+            b"proposal_commit",
+            submit.system_acc.key().as_ref(),
+            submit.round_metadata.current_round.to_le_bytes().as_ref(),
+            submit.payer.key().as_ref()
+        ],
+        bump = proposal_commit.bump,
+    )]
+    pub proposal_commit: Account<'info, ProposalCommitAccount>,
+    /// CHECK: submitter, the same key as `submit.payer`; receives the commit rent
+    #[account(mut, address = submit.payer.key())]
+    pub submitter: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u8)]
+pub struct WithdrawProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_metadata.current_round.to_le_bytes().as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal_acc.bump,
+        constraint = proposal_acc.submitter == payer.key() @ ErrorCode::InvalidAuthority,
+        close = payer,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    pub system_program: Program<'info, System>,
+    /// Round escrow's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    /// Payer's fee token account the refund is paid to (token-fee mode only)
+    #[account(mut)]
+    pub payer_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    #[account(
+        mut,
+        seeds = [b"submitter", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump = submitter_stats.bump,
+    )]
+    pub submitter_stats: Box<Account<'info, SubmitterStatsAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u8)]
+pub struct EditProposal<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_metadata.current_round.to_le_bytes().as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal_acc.bump,
+        constraint = proposal_acc.submitter == payer.key() @ ErrorCode::InvalidAuthority,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+}
+
+#[queue_computation_accounts("vote_for_proposal", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, proposal_id: u8)]
+pub struct VoteForProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(resolve_computation_offset(&system_acc, computation_offset))
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_FOR_PROPOSAL)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// CHECK: Manually verified vote_receipt PDA
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+    /// CHECK: Manually verified round_metadata PDA
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, AllowlistAccount>>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VoterRateAccount::INIT_SPACE,
+        seeds = [b"voter_rate", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub voter_rate: Account<'info, VoterRateAccount>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PrepareVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, AllowlistAccount>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PendingVoteAccount::INIT_SPACE,
+        seeds = [b"pending_vote", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub pending_vote: Account<'info, PendingVoteAccount>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[queue_computation_accounts("vote_for_proposal", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct ConfirmVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(resolve_computation_offset(&system_acc, computation_offset))
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_FOR_PROPOSAL)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// CHECK: Manually verified vote_receipt PDA
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, AllowlistAccount>>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VoterRateAccount::INIT_SPACE,
+        seeds = [b"voter_rate", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub voter_rate: Account<'info, VoterRateAccount>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"pending_vote", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump = pending_vote.bump,
+    )]
+    pub pending_vote: Box<Account<'info, PendingVoteAccount>>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[queue_computation_accounts("vote_for_proposal", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, vote: [u8; 32], vote_encryption_pubkey: [u8; 32], vote_nonce: u128, round_id: u64)]
+pub struct ReissueVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_FOR_PROPOSAL)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: the payer's vote receipt for the round, checked by seeds and read manually
+    #[account(
+        mut,
+        seeds = [b"vote_receipt", system_acc.key().as_ref(), payer.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vote_receipt: UncheckedAccount<'info>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClearExpiredPendingVote<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// CHECK: voter who prepared the vote; only receives the reclaimed rent
+    #[account(mut, address = pending_vote.voter)]
+    pub voter: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = voter,
+        seeds = [b"pending_vote", system_acc.key().as_ref(), pending_vote.voter.as_ref()],
+        bump = pending_vote.bump,
+    )]
+    pub pending_vote: Account<'info, PendingVoteAccount>,
+}
+
+#[callback_accounts("vote_for_proposal")]
+#[derive(Accounts)]
+pub struct VoteForProposalCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_FOR_PROPOSAL)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// Round metadata, read to attribute the vote to its round and report turnout
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: voter, the signer who queued the vote; only its key is reported
+    pub voter: UncheckedAccount<'info>,
+    /// CHECK: vote_receipt, the voter's receipt, passed by the queuing instruction which
+    /// verified its PDA
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("vote_for_proposal", payer)]
+#[derive(Accounts)]
+pub struct InitVoteForProposalCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_winning_proposal", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, system_id: u32)]
+pub struct RevealWinningProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[callback_accounts("reveal_winning_proposal")]
+#[derive(Accounts)]
+pub struct RevealWinningProposalCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(mut)]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[queue_computation_accounts("init_proposal_votes", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct CreateRoundHistory<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PROPOSAL_VOTES)
+    )]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    // Seeds saturate so round 0 reaches the NoCompletedRound check instead of panicking
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VotingRoundHistoryAccount::INIT_SPACE,
+        seeds = [b"voting_round_history", system_acc.key().as_ref(), round_metadata.current_round.saturating_sub(1).to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub round_history: Account<'info, VotingRoundHistoryAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.saturating_sub(1).to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Option<Account<'info, RoundEscrowAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + WinnerIndexAccount::INIT_SPACE,
+        seeds = [b"winner_index", system_acc.key().as_ref()],
+        bump,
+    )]
+    pub winner_index: Box<Account<'info, WinnerIndexAccount>>,
+    #[account(
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_metadata.current_round.saturating_sub(1).to_le_bytes().as_ref(),
+            system_acc.winning_proposal_id.unwrap_or_default().to_le_bytes().as_ref()
+        ],
+        bump = winning_proposal.bump,
+    )]
+    pub winning_proposal: Box<Account<'info, ProposalAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SubmitterStatsAccount::INIT_SPACE,
+        seeds = [b"submitter", system_acc.key().as_ref(), winning_proposal.submitter.as_ref()],
+        bump,
+    )]
+    pub winner_stats: Box<Account<'info, SubmitterStatsAccount>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(from_round: u64)]
+pub struct CreateRunoffRound<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"voting_round_history", system_acc.key().as_ref(), from_round.to_le_bytes().as_ref()],
+        bump = round_history.bump,
+    )]
+    pub round_history: Account<'info, VotingRoundHistoryAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RoundEscrowAccount::INIT_SPACE,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct DistributeEscrowToWinner<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"voting_round_history", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_history.bump,
+    )]
+    pub round_history: Account<'info, VotingRoundHistoryAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    #[account(
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+            round_history.winning_proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = winning_proposal.bump,
+    )]
+    pub winning_proposal: Account<'info, ProposalAccount>,
+    /// CHECK: winner, checked against the winning proposal's submitter
+    #[account(
+        mut,
+        address = winning_proposal.submitter @ ErrorCode::InvalidRecipient
+    )]
+    pub winner: UncheckedAccount<'info>,
+    /// CHECK: treasury, checked against the system's configured treasury
+    #[account(
+        mut,
+        address = system_acc.treasury @ ErrorCode::InvalidRecipient
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// Round escrow's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    /// Winner's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub winner_token_account: Option<Account<'info, TokenAccount>>,
+    /// Treasury's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    /// Round escrow's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    /// Authority's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub payer_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64, proposal_id: u8)]
+pub struct RefundLosingProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"voting_round_history", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_history.bump,
+    )]
+    pub round_history: Account<'info, VotingRoundHistoryAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal_acc.bump,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+    /// CHECK: submitter, checked against the proposal's stored submitter
+    #[account(
+        mut,
+        address = proposal_acc.submitter @ ErrorCode::InvalidRecipient
+    )]
+    pub submitter: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// Round escrow's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    /// Submitter's fee token account the refund is paid to (token-fee mode only)
+    #[account(mut)]
+    pub submitter_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u8)]
+pub struct SecondProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_metadata.current_round.to_le_bytes().as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal_acc.bump,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SecondAccount::INIT_SPACE,
+        seeds = [b"second", proposal_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub second: Account<'info, SecondAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequiredSeconds<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64, proposal_id: u8)]
+pub struct SlashProposal<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal_acc.bump,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetSubmissionFee<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct AddVoter<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AllowlistAccount::INIT_SPACE,
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump,
+    )]
+    pub allowlist: Account<'info, AllowlistAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveVoter<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Account<'info, AllowlistAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(voter: Pubkey)]
+pub struct BlacklistVoter<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BlacklistAccount::INIT_SPACE,
+        seeds = [b"blacklist", system_acc.key().as_ref(), voter.as_ref()],
+        bump,
+    )]
+    pub blacklist: Account<'info, BlacklistAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(voter: Pubkey)]
+pub struct UnblacklistVoter<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"blacklist", system_acc.key().as_ref(), voter.as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Account<'info, BlacklistAccount>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeNewAuthority<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct ClearStuckReveal<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProposalSystem<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump,
+        close = payer,
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump,
+        close = payer,
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_winning_proposal", payer)]
+#[derive(Accounts)]
+pub struct InitRevealWinnerCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Represents the proposal voting system with encrypted vote tallies for all proposals.
+/// NOTE: This account is passed to MXE - DO NOT modify its structure! `proposal_votes`
+/// is read at `PROPOSAL_VOTES_OFFSET`, so fields may only be appended after it.
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalSystemAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Public key of the system authority (only they can reveal results)
+    pub authority: Pubkey,
+    /// Cryptographic nonce for the encrypted vote counters
+    pub nonce: u128,
+    /// Next proposal ID to be assigned
+    pub next_proposal_id: u8,
+    /// Encrypted vote counters for all proposals (up to `MAX_PROPOSALS`) as 32-byte ciphertexts
+    pub proposal_votes: [[u8; 32]; MAX_PROPOSALS],
+    /// Winning proposal ID after reveal (None = not revealed yet)
+    pub winning_proposal_id: Option<u8>,
+    /// Number of votes the winning proposal received (None = not revealed yet)
+    pub winning_vote_count: Option<u64>,
+    /// Fixed fee for proposal submission (in lamports)
+    pub proposal_submission_fee: u64,
+    /// Share of the submission fee refunded to losing proposers (basis points)
+    pub refund_bps: u16,
+    /// Length of each round's voting window, counted from `round_started` (in seconds)
+    pub voting_duration: i64,
+    /// Whether the last revealed winner shared the maximum vote count with another proposal
+    pub winner_was_tie: bool,
+    /// Minimum number of voters a round needs before its winner can be revealed
+    pub min_quorum: u64,
+    /// Governance token used to weight votes (None = one vote per voter)
+    pub governance_mint: Option<Pubkey>,
+    /// Whether votes are restricted to keys on the allowlist
+    pub allowlist_enabled: bool,
+    /// Authority nominated by the current authority, awaiting acceptance
+    pub pending_authority: Option<Pubkey>,
+    /// Whether proposal submission and voting are temporarily suspended
+    pub paused: bool,
+    /// Share of each round's fees reserved for voters who backed the winner (basis points)
+    pub voter_reward_bps: u16,
+    /// Whether voters approve any number of proposals instead of picking one
+    pub approval_voting: bool,
+    /// Identifier that seeds this system's PDAs, so independent systems can coexist
+    pub system_id: u32,
+    /// Votes separating the last revealed winner from the runner-up
+    pub winning_margin: Option<u64>,
+    /// Cap on any single proposal's tally, enforced inside the vote circuit (None = no cap)
+    pub max_votes_per_proposal: Option<u64>,
+    /// Whether voters up- or downvote a proposal and the tally holds net scores
+    pub net_voting: bool,
+    /// Whether every net score was negative when the last winner was revealed
+    pub all_scores_negative: bool,
+    /// Minimum summed voting power before a winner can be revealed (weighted systems only)
+    pub min_voting_power: u128,
+    /// Share of the votes a winner needs in `reveal_winning_proposal_with_threshold` (0 = unset)
+    pub threshold_pct: u8,
+    /// Whether the last threshold reveal reached the supermajority (None = not attempted)
+    pub supermajority_met: Option<bool>,
+    /// Token submission fees are paid in (None = SOL)
+    pub fee_mint: Option<Pubkey>,
+    /// Counter backing auto-assigned computation offsets (see `AUTO_COMPUTATION_OFFSET`)
+    pub next_computation_offset: u64,
+    /// Whether the authority may vote in its own system
+    pub authority_can_vote: bool,
+    /// Co-authorities whose approvals gate reveals
+    #[max_len(5)]
+    pub authorities: Vec<Pubkey>,
+    /// Approvals required before a reveal can be queued (0 = the authority alone reveals)
+    pub reveal_threshold: u8,
+    /// Cooldown between a voter's votes in a round
+    pub rate_limit: RateLimitConfig,
+    /// Minimum number of proposals a round needs before its winner can be revealed
+    pub min_proposals: u8,
+    /// Receives the share of each distributed escrow not paid to the winner
+    pub treasury: Pubkey,
+    /// Share of each distributed escrow paid to the winner, in basis points
+    pub winner_bps: u16,
+    /// Seconds a proposal needs before it can receive votes (0 = no seconding)
+    pub required_seconds: u8,
+    /// Number of rounds after which the system finishes (None = runs indefinitely)
+    pub max_rounds: Option<u64>,
+    /// Set once `max_rounds` rounds have been revealed; no more proposals or votes
+    pub finished: bool,
+    /// Merkle root of the off-chain `(voter, weight)` snapshot weighted votes are proven
+    /// against (None = no weighted votes accepted)
+    pub voter_snapshot_root: Option<[u8; 32]>,
+    /// Set while a winner reveal is queued, so a second reveal can't advance the round again
+    pub reveal_in_progress: bool,
+    /// Length of each round's submission window, counted from `round_started` (in
+    /// seconds); votes open once it closes (0 = proposals and votes interleave)
+    pub submission_duration: i64,
+    /// Dispute window after a round is recorded before its escrow can be paid to the
+    /// winner (in seconds)
+    pub distribution_delay: i64,
+    /// When the outstanding winner reveal was queued (meaningful while `reveal_in_progress`)
+    pub reveal_queued_at: i64,
+    /// Bitmask of the proposals that shared the top score in the latest reveal
+    pub winner_tied_mask: u32,
+    /// Set while an `init_proposal_votes` computation is re-encrypting the zeroed tally;
+    /// votes are refused until its callback stores the fresh ciphertexts and nonce
+    pub tally_reset_pending: bool,
+    /// Set by the `init_proposal_votes` callback when that computation aborted; only
+    /// then can `retry_tally_reset` queue another one
+    pub tally_reset_failed: bool,
+}
+
+/// Represents the escrow account for a specific voting round.
+#[account]
+#[derive(InitSpace)]
+pub struct RoundEscrowAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Round ID this escrow belongs to
+    pub round_id: u64,
+    /// Total fees collected in this round
+    pub total_collected: u64,
+    /// Total distributed from this round
+    pub total_distributed: u64,
+    /// Current available balance
+    pub current_balance: u64,
+    /// Status of this round's escrow
+    pub round_status: RoundStatus,
+    /// Timestamp when this round escrow was created
+    pub created_at: i64,
+    /// Whether the round was cancelled by the authority (every fee becomes refundable)
+    pub cancelled: bool,
+    /// Token the round's fees were paid in (None = SOL)
+    pub fee_mint: Option<Pubkey>,
+    /// Total token fees collected in this round (token-fee mode only)
+    pub token_collected: u64,
+    /// Token fees currently held in the escrow token account (token-fee mode only)
+    pub token_balance: u64,
+    /// Set once the round's first proposal has initialized the escrow; a zeroed
+    /// round-0 escrow is otherwise indistinguishable from a fresh account
+    pub is_initialized: bool,
+    /// Voters who proved they backed the winner during reward registration
+    pub winning_voters: u64,
+    /// Voter rewards paid out of this escrow so far
+    pub voter_rewards_paid: u64,
+    /// Fees of proposals whose submitters can still be refunded, i.e. not yet refunded,
+    /// withdrawn or slashed (the winner's fee is dropped once the escrow is distributed)
+    pub refundable_fees: u64,
+}
+
+impl RoundEscrowAccount {
+    /// The round's voter reward pool: `voter_reward_bps` of the fees collected.
+    pub fn voter_reward_pool(&self, voter_reward_bps: u16) -> u64 {
+        (self.total_collected as u128 * voter_reward_bps as u128 / 10_000) as u64
+    }
+
+    /// Part of the voter reward pool still owed to winning voters, which distribution
+    /// must leave in the escrow. Released once registration has closed with nobody
+    /// registered.
+    pub fn unpaid_voter_rewards(&self, voter_reward_bps: u16, registration_open: bool) -> u64 {
+        if !registration_open && self.winning_voters == 0 {
+            return 0;
+        }
+        self.voter_reward_pool(voter_reward_bps)
+            .saturating_sub(self.voter_rewards_paid)
+    }
+
+    /// Refunds losing proposers can still claim, which distribution must leave in the
+    /// escrow: `refund_bps` of every refundable fee except `winner_fee`. Each refund
+    /// rounds down on its own, so the reserve always covers their sum.
+    pub fn unclaimed_refunds(&self, refund_bps: u16, winner_fee: u64) -> u64 {
+        (self.refundable_fees.saturating_sub(winner_fee) as u128 * refund_bps as u128 / 10_000)
+            as u64
+    }
+
+    /// Fees currently held, in lamports or in `fee_mint` tokens depending on the fee mode.
+    pub fn fee_balance(&self) -> u64 {
+        if self.fee_mint.is_some() {
+            self.token_balance
+        } else {
+            self.current_balance
+        }
+    }
+
+    /// Records `amount` of fees paid out of the escrow, from the balance its fee mode uses.
+    pub fn record_payout(&mut self, amount: u64) -> Result<()> {
+        let balance = if self.fee_mint.is_some() {
+            &mut self.token_balance
+        } else {
+            &mut self.current_balance
+        };
+        *balance = balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientEscrowBalance)?;
+        self.total_distributed = self
+            .total_distributed
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Status of a round's escrow account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum RoundStatus {
+    /// Round is ongoing, collecting fees
+    Active,
+    /// Round ended, escrow available for distribution
+    Completed,
+    /// Escrow fully distributed or closed
+    Closed,
+}
+
+impl anchor_lang::Space for RoundStatus {
+    const INIT_SPACE: usize = 1; // 1 byte for the enum discriminant
+}
+
+/// Represents a single proposal submitted to the system.
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Unique identifier for this proposal within the round
+    pub id: u8,
+    /// Round ID this proposal belongs to
+    pub round_id: u64,
+    /// Public key of the proposal submitter
+    pub submitter: Pubkey,
+    /// Number of votes this proposal has received (public count)
+    pub vote_count: u64,
+    /// Short title of the proposal (max 50 characters)
+    #[max_len(50)]
+    pub title: String,
+    /// Detailed description of the proposal (max 200 characters)
+    #[max_len(200)]
+    pub description: String,
+    /// URL associated with the proposal (max 200 characters)
+    #[max_len(200)]
+    pub url: String,
+    /// Submission fee paid for this proposal (in lamports)
+    pub fee_paid: u64,
+    /// Whether the submission fee has already been refunded
+    pub refunded: bool,
+    /// Whether the authority flagged the proposal as spam, forfeiting its fee
+    pub slashed: bool,
+    /// Track the proposal competes in, chosen by the submitter
+    pub category: u8,
+    /// Hash of the document behind `url` at submission time
+    pub content_hash: [u8; 32],
+    /// Number of distinct keys that seconded the proposal
+    pub seconds: u8,
+    /// Teammates who share the winner payout equally with `submitter`
+    #[max_len(4)]
+    pub co_submitters: Vec<Pubkey>,
+    /// Whether `vote_count` holds a revealed count copied by `sync_proposal_vote_count`
+    pub count_synced: bool,
+}
+
+/// Represents a vote receipt for a voter.
+/// This account stores the details of a vote cast by a specific voter.
+/// The proposal ID is encrypted for ballot secrecy - only the voter (who has the nonce) or MXE can decrypt it.
+/// Note: PDA is derived from voter only - each voter can only vote ONCE total (not once per proposal).
+/// The plaintext proposal_id is NOT stored to maintain complete ballot secrecy.
+#[account]
+#[derive(InitSpace)]
+pub struct VoteReceiptAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Public key of the voter
+    pub voter: Pubkey,
+    /// Encrypted proposal ID - only decryptable by voter (with their nonce) or MXE
+    pub encrypted_proposal_id: [u8; 32],
+    /// Timestamp when the vote was cast
+    pub timestamp: i64,
+    /// Voter's encryption public key used for the vote
+    pub vote_encryption_pubkey: [u8; 32],
+    /// Whether the voter has claimed their reward for backing the winner
+    pub reward_claimed: bool,
+    /// Set while a `vote_for_proposal` tally computation is outstanding; cleared by its
+    /// callback. Still set after the computation aborted, which `reissue_vote` retries.
+    pub awaiting_tally: bool,
+    /// Nonce `encrypted_proposal_id` was encrypted under. Anything that re-submits the
+    /// ballot to MPC must present the same nonce, or it would decrypt to another choice.
+    pub vote_nonce: u128,
+    /// Whether `claim_winning_vote_reward` proved this vote backed the winner, so the
+    /// voter can collect a share of the pool once registration closes
+    pub reward_registered: bool,
+    /// Offset of the latest tally computation queued for this ballot
+    pub tally_computation_offset: u64,
+    /// Set by the vote callback when that computation aborted; only then can the ballot
+    /// be reissued, so a slow computation is never counted twice
+    pub tally_failed: bool,
+    /// Key that funded the receipt's rent (the voter, or their delegate), which gets it
+    /// back when the receipt is closed
+    pub rent_payer: Pubkey,
+    /// Set while a `change_vote` computation is outstanding. The ballot above stays the
+    /// counted one until its callback succeeds and commits the pending ballot below.
+    pub change_pending: bool,
+    /// Encrypted proposal ID the outstanding `change_vote` switches to
+    pub pending_proposal_id: [u8; 32],
+    /// Encryption public key of the pending ballot
+    pub pending_encryption_pubkey: [u8; 32],
+    /// Nonce the pending ballot was encrypted under
+    pub pending_nonce: u128,
+    /// Whether the ballot was cast by `delegated_vote_for_proposal`, for the delegate or
+    /// one of their delegators. Such ballots share one ciphertext carrying the combined
+    /// weight, so none of them can be moved by `change_vote`.
+    pub delegated: bool,
+}
+
+impl VoteReceiptAccount {
+    /// Reads a receipt written by `create_vote_receipt`.
+    ///
+    /// Receipts are written without a discriminator, so the data is deserialized from the
+    /// start of the account and the trailing padding is ignored.
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require!(info.owner == &crate::ID, ErrorCode::InvalidVoteReceipt);
+
+        let data = info.try_borrow_data()?;
+        Self::deserialize(&mut &data[..]).map_err(|_| error!(ErrorCode::InvalidVoteReceipt))
+    }
+
+    /// Writes the receipt back in the same discriminator-less layout.
+    pub fn store(&self, info: &AccountInfo) -> Result<()> {
+        let mut data = info.try_borrow_mut_data()?;
+        let serialized = self.try_to_vec()?;
+        // Fail cleanly instead of panicking if the account was allocated too small
+        require!(
+            serialized.len() <= data.len(),
+            ErrorCode::SerializationOverflow
+        );
+        data[0..serialized.len()].copy_from_slice(&serialized);
+
+        Ok(())
+    }
+}
+
+/// Represents the history of a completed voting round.
+/// This account is created after a winner is revealed and stores the results permanently.
+/// Note: Vote counts are NOT stored here - they can be calculated on the frontend from the state.
+#[account]
+#[derive(InitSpace)]
+pub struct VotingRoundHistoryAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Round identifier (incremented for each voting round)
+    pub round_id: u64,
+    /// ID of the winning proposal
+    pub winning_proposal_id: u8,
+    /// Timestamp when the winner was revealed
+    pub revealed_at: i64,
+    /// Authority who revealed the results
+    pub revealed_by: Pubkey,
+    /// Total number of proposals in this round
+    pub total_proposals: u8,
+    /// Number of votes the winning proposal received
+    pub winning_vote_count: u64,
+    /// Submission fees collected in the round's escrow
+    pub total_fees_collected: u64,
+    /// Number of votes cast in the round
+    pub total_voters: u64,
+    /// Category of the winning proposal
+    pub winning_category: u8,
+    /// Whether the winning count was shared by another proposal
+    pub was_tie: bool,
+    /// Slot the round's history was recorded at
+    pub revealed_at_slot: u64,
+    /// The system's `distribution_delay` when the round was recorded; later changes to
+    /// the setting don't move this round's dispute window
+    pub distribution_delay: i64,
+    /// Bitmask of the proposals that shared the top score (bit `i` = proposal `i`);
+    /// only these may enter a runoff
+    pub tied_mask: u32,
+}
+
+/// A round's winner encrypted to a designated recipient by `reveal_winner_to_recipient`.
+#[account]
+#[derive(InitSpace)]
+pub struct SharedWinnerAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Round the winner was determined for
+    pub round_id: u64,
+    /// x25519 public key the result is encrypted to
+    pub recipient_pubkey: [u8; 32],
+    /// Nonce of the encrypted result
+    pub nonce: u128,
+    /// Encrypted winning proposal ID and vote count, in that order
+    pub ciphertexts: [[u8; 32]; 2],
+    /// When the encrypted result landed (None while the computation is pending)
+    pub revealed_at: Option<i64>,
+}
+
+/// Round-scoped parameters of the encrypted tally, returned by `get_tally_encryption_params`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TallyEncryptionParams {
+    /// System the tally belongs to
+    pub system_id: u32,
+    /// Round the tally is currently counting
+    pub round_id: u64,
+    /// Nonce the tally ciphertexts are currently encrypted under
+    pub nonce: u128,
+}
+
+/// A voter's snapshot weight and the Merkle proof backing it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WeightProof {
+    /// Weight recorded for the voter in the snapshot
+    pub weight: u64,
+    /// Sibling hashes from the voter's leaf up to the root
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// One voter's encrypted vote inside a `batch_vote` call.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchedVote {
+    /// ID of the proposal being voted for (plaintext for validation)
+    pub proposal_id: u8,
+    /// Encrypted proposal ID stored in the voter's receipt (must equal `vote`)
+    pub encrypted_proposal_id: [u8; 32],
+    /// Encrypted vote containing the proposal ID
+    pub vote: [u8; 32],
+    /// Voter's public key for encryption
+    pub vote_encryption_pubkey: [u8; 32],
+    /// Cryptographic nonce for the vote encryption
+    pub vote_nonce: u128,
+}
+
+/// One past vote inside a `verify_winning_votes_batch` call.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchedVerification {
+    /// Completed round the vote was cast in
+    pub round_id: u64,
+    /// Encrypted vote containing the proposal ID (must match the round's receipt)
+    pub vote: [u8; 32],
+    /// Voter's public key used to encrypt the vote
+    pub vote_encryption_pubkey: [u8; 32],
+    /// Cryptographic nonce for the vote encryption
+    pub vote_nonce: u128,
+}
+
+/// A single winner recorded in the winner index.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct WinnerIndexEntry {
+    /// Round the winner was revealed for
+    pub round_id: u64,
+    /// ID of the winning proposal
+    pub winning_proposal_id: u8,
+    /// Timestamp when the round history was recorded
+    pub revealed_at: i64,
+}
+
+/// Bounded index of the most recent winners, appended to by `create_round_history`.
+/// Once full, the oldest entry is evicted to make room for the newest.
+#[account]
+#[derive(InitSpace)]
+pub struct WinnerIndexAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Recorded winners, oldest first
+    #[max_len(32)]
+    pub entries: Vec<WinnerIndexEntry>,
+}
+
+/// Represents a voter's approval ballot receipt.
+#[account]
+#[derive(InitSpace)]
+pub struct ApprovalReceiptAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Public key of the voter
+    pub voter: Pubkey,
+    /// Encrypted approval flags, one per proposal slot
+    pub encrypted_approvals: [[u8; 32]; MAX_PROPOSALS],
+    /// Timestamp when the ballot was cast
+    pub timestamp: i64,
+    /// Voter's encryption public key used for the ballot
+    pub vote_encryption_pubkey: [u8; 32],
+}
+
+/// Encrypted ballot box holding a round's ranked-choice ballots.
+#[account]
+#[derive(InitSpace)]
+pub struct RankedVoteAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Round ID these ballots belong to
+    pub round_id: u64,
+    /// Number of ballot slots used (including ballots still being written)
+    pub ballot_count: u8,
+    /// Nonce of the encrypted ballot box
+    pub nonce: u128,
+    /// Encrypted rankings, `MAX_RANKED_BALLOTS` ballots of `MAX_PROPOSALS` preferences each
+    pub encrypted_ballots: [[u8; 32]; RANKED_BALLOT_CIPHERTEXTS],
+}
+
+/// Represents a voter's ranked-choice ballot receipt.
+#[account]
+#[derive(InitSpace)]
+pub struct RankedReceiptAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Public key of the voter
+    pub voter: Pubkey,
+    /// Encrypted proposal IDs, most preferred first
+    pub encrypted_ranking: [[u8; 32]; MAX_PROPOSALS],
+    /// Timestamp when the ballot was cast
+    pub timestamp: i64,
+    /// Voter's encryption public key used for the ballot
+    pub vote_encryption_pubkey: [u8; 32],
+}
+
+/// Keys allowed to vote when allowlist enforcement is enabled.
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Allowlisted voter keys
+    #[max_len(100)]
+    pub voters: Vec<Pubkey>,
+}
+
+/// Marks a key as banned from voting and submitting; the ban lasts while the account exists.
+#[account]
+#[derive(InitSpace)]
+pub struct BlacklistAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Banned key
+    pub voter: Pubkey,
+    /// Timestamp the ban was placed
+    pub blacklisted_at: i64,
+}
+
+/// A submitter's track record within a system, readable by other programs as a
+/// lightweight reputation signal.
+#[account]
+#[derive(InitSpace)]
+pub struct SubmitterStatsAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Submitter the stats belong to
+    pub submitter: Pubkey,
+    /// Proposals submitted across all rounds
+    pub total_submitted: u64,
+    /// Rounds won
+    pub total_wins: u64,
+}
+
+/// Marks that a key seconded a proposal; its existence prevents seconding twice.
+#[account]
+#[derive(InitSpace)]
+pub struct SecondAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Proposal that was seconded
+    pub proposal: Pubkey,
+    /// Key that seconded it
+    pub seconder: Pubkey,
+}
+
+/// Vote throttling settings of a system.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
+pub struct RateLimitConfig {
+    /// Minimum slots between two votes from the same key in a round (0 = no limit)
+    pub cooldown_slots: u64,
+}
+
+/// Slot of a voter's latest vote, used to throttle automated voting.
+#[account]
+#[derive(InitSpace)]
+pub struct VoterRateAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Voter the record belongs to
+    pub voter: Pubkey,
+    /// Round of the latest vote
+    pub round_id: u64,
+    /// Slot of the latest vote
+    pub last_vote_slot: u64,
+}
+
+/// Encrypted ballot committed by `prepare_vote` and awaiting `confirm_vote`.
+#[account]
+#[derive(InitSpace)]
+pub struct PendingVoteAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Voter who prepared the ballot
+    pub voter: Pubkey,
+    /// Round the ballot is for
+    pub round_id: u64,
+    /// ID of the proposal being voted for (plaintext for validation)
+    pub proposal_id: u8,
+    /// Encrypted proposal ID copied into the vote receipt on confirmation
+    pub encrypted_proposal_id: [u8; 32],
+    /// Encrypted vote fed to the tally computation
+    pub vote: [u8; 32],
+    /// Voter's public key for encryption
+    pub vote_encryption_pubkey: [u8; 32],
+    /// Nonce of the vote encryption
+    pub vote_nonce: u128,
+    /// Slot the ballot was prepared in
+    pub prepared_slot: u64,
+}
+
+impl PendingVoteAccount {
+    /// Last slot in which the ballot can still be confirmed.
+    pub fn expires_at_slot(&self) -> u64 {
+        self.prepared_slot.saturating_add(PENDING_VOTE_EXPIRY_SLOTS)
+    }
+}
+
+/// Co-authority approvals collected for revealing one round's results.
+#[account]
+#[derive(InitSpace)]
+pub struct RevealApprovalAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Round the approvals apply to
+    pub round_id: u64,
+    /// Co-authorities that approved the reveal
+    #[max_len(5)]
+    pub approvers: Vec<Pubkey>,
+}
+
+/// A voter's delegation of their voting power to another key.
+#[account]
+#[derive(InitSpace)]
+pub struct DelegationAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Voter whose power is delegated
+    pub delegator: Pubkey,
+    /// Key allowed to vote with the delegated power
+    pub delegate: Pubkey,
+}
+
+/// A submitter's hidden commitment to a proposal during the commit phase.
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalCommitAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Round the commitment was made in
+    pub round_id: u64,
+    /// Submitter who made the commitment
+    pub submitter: Pubkey,
+    /// sha256 of the borsh-encoded (submitter, title, description, url, category,
+    /// content_hash, co_submitters, salt)
+    pub commitment: [u8; 32],
+    /// Timestamp when the commitment was made
+    pub committed_at: i64,
+}
+
+/// Metadata account for tracking round information.
+/// This is separate from ProposalSystemAccount to avoid modifying accounts passed to MXE.
+#[account]
+#[derive(InitSpace)]
+pub struct RoundMetadataAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Current voting round number (incremented each time a winner is revealed)
+    pub current_round: u64,
+    /// Number of proposals submitted in the current round
+    pub proposals_in_current_round: u8,
+    /// Total number of voters in the current round
+    pub total_voters: u64,
+    /// Unix timestamp when the current round started
+    pub round_started: i64,
+    /// Per-proposal vote counts from the latest `reveal_all_tallies` (None until one lands)
+    pub revealed_tallies: Option<[i64; MAX_PROPOSALS]>,
+    /// Round the revealed tallies belong to
+    pub revealed_tallies_round: u64,
+    /// Extra seconds added to the current round's voting window by `extend_round`
+    pub round_extension: i64,
+    /// End of the proposal commit phase (None = proposals are submitted directly)
+    pub commit_deadline: Option<i64>,
+    /// End of the proposal reveal phase; commitments not revealed by then are void
+    pub reveal_deadline: Option<i64>,
+    /// Maximum number of proposals in the current round
+    pub proposal_limit: u8,
+    /// Limit applied to the next round when its first proposal is submitted
+    pub next_proposal_limit: u8,
+    /// sha256 of each current-round proposal's URL, indexed by proposal ID
+    #[max_len(MAX_PROPOSALS)]
+    pub url_hashes: Vec<[u8; 32]>,
+    /// Sum of the weights of every weighted vote cast in the current round
+    pub total_voting_power: u128,
+    /// Category of each current-round proposal, indexed by proposal ID
+    pub proposal_categories: [u8; MAX_PROPOSALS],
+    /// Seconds each current-round proposal has received, indexed by proposal ID
+    pub proposal_seconds: [u8; MAX_PROPOSALS],
+    /// sha256 of each current-round proposal's normalized title, indexed by proposal ID
+    #[max_len(MAX_PROPOSALS)]
+    pub title_hashes: Vec<[u8; 32]>,
+    /// Slot the current round started at (ordering reference validators can't skew)
+    pub round_started_slot: u64,
+}
+
+impl RoundMetadataAccount {
+    /// Unix timestamp after which the current round no longer accepts votes.
+    pub fn voting_deadline(&self, voting_duration: i64) -> i64 {
+        self.round_started
+            .saturating_add(voting_duration)
+            .saturating_add(self.round_extension)
+    }
+
+    /// Unix timestamp after which the current round no longer accepts proposals and
+    /// starts accepting votes (None when the system has no separate submission window).
+    pub fn submission_deadline(&self, submission_duration: i64) -> Option<i64> {
+        (submission_duration > 0).then(|| self.round_started.saturating_add(submission_duration))
+    }
+
+    /// Round the encrypted tally belongs to. Revealing the winner advances the round
+    /// before the counters are reset, so the tally still belongs to the previous round
+    /// in that window.
+    pub fn tally_round(&self, winner_revealed: bool) -> u64 {
+        if winner_revealed {
+            self.current_round.saturating_sub(1)
+        } else {
+            self.current_round
+        }
+    }
+}
+
+#[queue_computation_accounts("decrypt_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct DecryptVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[callback_accounts("decrypt_vote")]
+#[derive(Accounts)]
+pub struct DecryptVoteCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[init_computation_definition_accounts("decrypt_vote", payer)]
+#[derive(Accounts)]
+pub struct InitDecryptVoteCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("verify_winning_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, round_id: u64)]
+pub struct VerifyWinningVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_WINNING_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: round_history, manually verified in the function
+    pub round_history: UncheckedAccount<'info>,
+    /// CHECK: vote_receipt, manually verified in the function
+    pub vote_receipt: UncheckedAccount<'info>,
+    /// CHECK: round_escrow, manually verified in the function
+    #[account(mut)]
+    pub round_escrow: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("verify_winning_vote")]
+#[derive(Accounts)]
+pub struct VerifyWinningVoteCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_WINNING_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[init_computation_definition_accounts("verify_winning_vote", payer)]
+#[derive(Accounts)]
+pub struct InitVerifyWinningVoteCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("verify_not_winning_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, vote: [u8; 32], vote_encryption_pubkey: [u8; 32], vote_nonce: u128, round_id: u64)]
+pub struct VerifyNotWinningVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_NOT_WINNING_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"voting_round_history", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_history.bump
+    )]
+    pub round_history: Account<'info, VotingRoundHistoryAccount>,
+    /// CHECK: vote_receipt, manually verified in the function
+    pub vote_receipt: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("verify_not_winning_vote")]
+#[derive(Accounts)]
+pub struct VerifyNotWinningVoteCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_NOT_WINNING_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+#[init_computation_definition_accounts("verify_not_winning_vote", payer)]
+#[derive(Accounts)]
+pub struct InitVerifyNotWinningVoteCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_category_winner", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealCategoryWinner<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_CATEGORY_WINNER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[callback_accounts("reveal_category_winner")]
+#[derive(Accounts)]
+pub struct RevealCategoryWinnerCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_CATEGORY_WINNER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_category_winner", payer)]
+#[derive(Accounts)]
+pub struct InitRevealCategoryWinnerCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("decrypt_vote_standalone", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct DecryptVoteStandalone<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_VOTE_STANDALONE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("decrypt_vote_standalone")]
+#[derive(Accounts)]
+pub struct DecryptVoteStandaloneCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_VOTE_STANDALONE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+#[init_computation_definition_accounts("decrypt_vote_standalone", payer)]
+#[derive(Accounts)]
+pub struct InitDecryptVoteStandaloneCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_winning_proposal", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct FinalizeExpiredRound<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[queue_computation_accounts("reveal_single_proposal_count", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, proposal_id: u8)]
+pub struct RevealSingleProposalCount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_SINGLE_PROPOSAL_COUNT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// Proposal whose count is revealed; must belong to the round the tally is for
+    #[account(
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_metadata
+                .tally_round(system_acc.winning_proposal_id.is_some())
+                .to_le_bytes()
+                .as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal_acc.bump,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[callback_accounts("reveal_single_proposal_count")]
+#[derive(Accounts)]
+pub struct RevealSingleProposalCountCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_SINGLE_PROPOSAL_COUNT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub proposal_acc: Account<'info, ProposalAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_single_proposal_count", payer)]
+#[derive(Accounts)]
+pub struct InitRevealSingleProposalCountCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("verify_vote_in_set", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct VerifyVoteInSet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_VOTE_IN_SET)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: vote_receipt, manually verified in the function
+    pub vote_receipt: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("verify_vote_in_set")]
+#[derive(Accounts)]
+pub struct VerifyVoteInSetCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_VOTE_IN_SET)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+#[init_computation_definition_accounts("verify_vote_in_set", payer)]
+#[derive(Accounts)]
+pub struct InitVerifyVoteInSetCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_vote_concentration", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealVoteConcentration<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_VOTE_CONCENTRATION)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[callback_accounts("reveal_vote_concentration")]
+#[derive(Accounts)]
+pub struct RevealVoteConcentrationCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_VOTE_CONCENTRATION)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_vote_concentration", payer)]
+#[derive(Accounts)]
+pub struct InitRevealVoteConcentrationCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("verify_winning_votes_batch", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct VerifyWinningVotesBatch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_WINNING_VOTES_BATCH)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[callback_accounts("verify_winning_votes_batch")]
+#[derive(Accounts)]
+pub struct VerifyWinningVotesBatchCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_WINNING_VOTES_BATCH)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: voter, the signer whose votes were checked; only its key is reported
+    pub voter: UncheckedAccount<'info>,
+    // The checked rounds' history accounts follow as remaining accounts
+}
+
+#[init_computation_definition_accounts("verify_winning_votes_batch", payer)]
+#[derive(Accounts)]
+pub struct InitVerifyWinningVotesBatchCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_winner_to_recipient", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealWinnerToRecipient<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER_TO_RECIPIENT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SharedWinnerAccount::INIT_SPACE,
+        seeds = [b"shared_winner", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub shared_winner: Account<'info, SharedWinnerAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[callback_accounts("reveal_winner_to_recipient")]
+#[derive(Accounts)]
+pub struct RevealWinnerToRecipientCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER_TO_RECIPIENT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub shared_winner: Account<'info, SharedWinnerAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_winner_to_recipient", payer)]
+#[derive(Accounts)]
+pub struct InitRevealWinnerToRecipientCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_top_n", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealTopProposals<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TOP_N)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[callback_accounts("reveal_top_n")]
+#[derive(Accounts)]
+pub struct RevealTopNCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TOP_N)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_top_n", payer)]
+#[derive(Accounts)]
+pub struct InitRevealTopNCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("change_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct ChangeVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHANGE_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// CHECK: vote_receipt, manually verified in the function
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VoterRateAccount::INIT_SPACE,
+        seeds = [b"voter_rate", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub voter_rate: Account<'info, VoterRateAccount>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("change_vote")]
+#[derive(Accounts)]
+pub struct ChangeVoteCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHANGE_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// Round metadata, read to attribute the vote to its round and report turnout
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: voter, the signer who queued the vote; only its key is reported
+    pub voter: UncheckedAccount<'info>,
+    /// CHECK: vote_receipt, the voter's receipt holding the pending ballot, passed by
+    /// change_vote which verified its PDA
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("change_vote", payer)]
+#[derive(Accounts)]
+pub struct InitChangeVoteCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("weighted_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct WeightedVoteForProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(resolve_computation_offset(&system_acc, computation_offset))
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_WEIGHTED_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// CHECK: Manually verified vote_receipt PDA
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, AllowlistAccount>>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("weighted_vote")]
+#[derive(Accounts)]
+pub struct WeightedVoteCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_WEIGHTED_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// Round metadata, read to attribute the vote to its round and report turnout
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: voter, the signer who queued the vote; only its key is reported
+    pub voter: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("weighted_vote", payer)]
+#[derive(Accounts)]
+pub struct InitWeightedVoteCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("claim_winning_vote_reward", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, vote: [u8; 32], vote_encryption_pubkey: [u8; 32], vote_nonce: u128, round_id: u64)]
+pub struct ClaimWinningVoteReward<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CLAIM_WINNING_VOTE_REWARD)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"voting_round_history", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_history.bump,
+    )]
+    pub round_history: Account<'info, VotingRoundHistoryAccount>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    /// CHECK: vote_receipt, manually verified in the function
+    pub vote_receipt: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("claim_winning_vote_reward")]
+#[derive(Accounts)]
+pub struct ClaimWinningVoteRewardCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CLAIM_WINNING_VOTE_REWARD)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    pub round_history: Account<'info, VotingRoundHistoryAccount>,
+    #[account(mut)]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    /// CHECK: vote_receipt, checked by the callback account key passed in queue_computation
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+    /// CHECK: claimant, checked against the receipt's voter in the callback
+    pub claimant: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct CollectWinningVoteReward<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"voting_round_history", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_history.bump,
+    )]
+    pub round_history: Account<'info, VotingRoundHistoryAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    /// CHECK: vote_receipt, PDA checked in the instruction; written without a discriminator
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("claim_winning_vote_reward", payer)]
+#[derive(Accounts)]
+pub struct InitClaimWinningVoteRewardCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_total_votes", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealTotalVotes<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TOTAL_VOTES)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[callback_accounts("reveal_total_votes")]
+#[derive(Accounts)]
+pub struct RevealTotalVotesCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TOTAL_VOTES)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_total_votes", payer)]
+#[derive(Accounts)]
+pub struct InitRevealTotalVotesCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_all_tallies", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealAllTallies<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_ALL_TALLIES)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[callback_accounts("reveal_all_tallies")]
+#[derive(Accounts)]
+pub struct RevealAllTalliesCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_ALL_TALLIES)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(mut)]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_all_tallies", payer)]
+#[derive(Accounts)]
+pub struct InitRevealAllTalliesCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u8)]
+pub struct SyncProposalVoteCount<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_metadata.revealed_tallies_round.to_le_bytes().as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal_acc.bump,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetApprovalVoting<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[queue_computation_accounts("approval_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, approvals: [[u8; 32]; MAX_PROPOSALS], vote_encryption_pubkey: [u8; 32], vote_nonce: u128, round_id: u64)]
+pub struct ApprovalVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_APPROVAL_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ApprovalReceiptAccount::INIT_SPACE,
+        seeds = [b"approval_receipt", system_acc.key().as_ref(), payer.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub approval_receipt: Account<'info, ApprovalReceiptAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, AllowlistAccount>>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("approval_vote")]
+#[derive(Accounts)]
+pub struct ApprovalVoteCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_APPROVAL_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// Round metadata, read to attribute the vote to its round and report turnout
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: voter, the signer who queued the vote; only its key is reported
+    pub voter: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("approval_vote", payer)]
+#[derive(Accounts)]
+pub struct InitApprovalVoteCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("cast_ranked_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, ranking: [[u8; 32]; MAX_PROPOSALS], vote_encryption_pubkey: [u8; 32], vote_nonce: u128, round_id: u64)]
+pub struct CastRankedVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CAST_RANKED_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RankedVoteAccount::INIT_SPACE,
+        seeds = [b"ranked_votes", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub ranked_votes: Box<Account<'info, RankedVoteAccount>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RankedReceiptAccount::INIT_SPACE,
+        seeds = [b"ranked_receipt", system_acc.key().as_ref(), payer.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub ranked_receipt: Account<'info, RankedReceiptAccount>,
+    #[account(
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, AllowlistAccount>>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("cast_ranked_vote")]
+#[derive(Accounts)]
+pub struct CastRankedVoteCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CAST_RANKED_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub ranked_votes: Box<Account<'info, RankedVoteAccount>>,
+}
+
+#[init_computation_definition_accounts("cast_ranked_vote", payer)]
+#[derive(Accounts)]
+pub struct InitCastRankedVoteCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_ranked_winner", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealRankedWinner<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_RANKED_WINNER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"ranked_votes", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = ranked_votes.bump,
+    )]
+    pub ranked_votes: Box<Account<'info, RankedVoteAccount>>,
+}
+
+#[callback_accounts("reveal_ranked_winner")]
+#[derive(Accounts)]
+pub struct RevealRankedWinnerCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_RANKED_WINNER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub ranked_votes: Box<Account<'info, RankedVoteAccount>>,
+}
+
+#[init_computation_definition_accounts("reveal_ranked_winner", payer)]
+#[derive(Accounts)]
+pub struct InitRevealRankedWinnerCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
 
 #[queue_computation_accounts("init_proposal_votes", payer)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64)]
-pub struct InitProposalSystem<'info> {
+pub struct CancelRound<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PROPOSAL_VOTES)
+    )]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Option<Account<'info, RoundEscrowAccount>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64, proposal_id: u8)]
+pub struct RefundCancelledProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal_acc.bump,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+    /// CHECK: submitter, checked against the proposal's stored submitter
+    #[account(
+        mut,
+        address = proposal_acc.submitter @ ErrorCode::InvalidRecipient
+    )]
+    pub submitter: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// Round escrow's fee token account (token-fee mode only)
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    /// Submitter's fee token account the refund is paid to (token-fee mode only)
+    #[account(mut)]
+    pub submitter_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendRound<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[derive(Accounts)]
+pub struct GetRecentWinners<'info> {
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"winner_index", system_acc.key().as_ref()],
+        bump = winner_index.bump,
+    )]
+    pub winner_index: Account<'info, WinnerIndexAccount>,
+}
+
+#[queue_computation_accounts("verify_vote_for_proposal", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct VerifyVoteForProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_VOTE_FOR_PROPOSAL)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: vote_receipt, manually verified in the function
+    pub vote_receipt: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("verify_vote_for_proposal")]
+#[derive(Accounts)]
+pub struct VerifyVoteForProposalCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_VOTE_FOR_PROPOSAL)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+#[init_computation_definition_accounts("verify_vote_for_proposal", payer)]
+#[derive(Accounts)]
+pub struct InitVerifyVoteForProposalCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxVotesPerProposal<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinProposals<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetVoterSnapshot<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxRounds<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetDistributionDelay<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinVotingPower<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct GetTallyEncryptionParams<'info> {
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64, proposal_id: u8)]
+pub struct GetProposal<'info> {
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [
+            b"proposal",
+            system_acc.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal_acc.bump,
+    )]
+    pub proposal_acc: Account<'info, ProposalAccount>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewRevealCost<'info> {
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        address = derive_sign_pda!(),
+    )]
+    /// CHECK: sign_pda_account, only inspected to see whether it still needs rent
+    pub sign_pda_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+}
+
+#[derive(Accounts)]
+pub struct DeriveVoteReceiptPda<'info> {
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct GetRoundStatus<'info> {
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Option<Account<'info, RoundEscrowAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVoteReceipt<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: vote_receipt, manually verified in the function
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+    /// CHECK: rent_payer, checked against the receipt's recorded rent payer
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("batch_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct BatchVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_BATCH_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, AllowlistAccount>>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+}
+
+#[callback_accounts("batch_vote")]
+#[derive(Accounts)]
+pub struct BatchVoteCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_BATCH_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[init_computation_definition_accounts("batch_vote", payer)]
+#[derive(Accounts)]
+pub struct InitBatchVoteCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("net_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct NetVoteForProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_NET_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// CHECK: Manually verified vote_receipt PDA
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, AllowlistAccount>>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[callback_accounts("net_vote")]
+#[derive(Accounts)]
+pub struct NetVoteCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_NET_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// Round metadata, read to attribute the vote to its round and report turnout
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    /// CHECK: voter, the signer who queued the vote; only its key is reported
+    pub voter: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("net_vote", payer)]
+#[derive(Accounts)]
+pub struct InitNetVoteCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("weighted_vote", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct DelegatedVoteForProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_WEIGHTED_VOTE)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    /// CHECK: Manually verified vote_receipt PDA
+    #[account(mut)]
+    pub vote_receipt: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"allowlist", system_acc.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, AllowlistAccount>>,
+    #[account(
+        seeds = [b"round_escrow", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = round_escrow.bump,
+    )]
+    pub round_escrow: Account<'info, RoundEscrowAccount>,
+    /// CHECK: blacklist PDA of the payer, checked by seeds; participation is rejected if it exists
+    #[account(
+        seeds = [b"blacklist", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DelegationAccount::INIT_SPACE,
+        seeds = [b"delegation", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub delegation: Account<'info, DelegationAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"delegation", system_acc.key().as_ref(), payer.key().as_ref()],
+        bump = delegation.bump,
+    )]
+    pub delegation: Account<'info, DelegationAccount>,
+}
+
+#[queue_computation_accounts("reveal_winner_with_threshold", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealWinningProposalWithThreshold<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -823,7 +13386,82 @@ pub struct InitProposalSystem<'info> {
     /// CHECK: computation_account, checked by the arcium program.
     pub computation_account: UncheckedAccount<'info>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PROPOSAL_VOTES)
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER_WITH_THRESHOLD)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[queue_computation_accounts("reveal_winner_with_threshold", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct FinalizeExpiredRoundWithThreshold<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER_WITH_THRESHOLD)
     )]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(
@@ -837,764 +13475,1298 @@ pub struct InitProposalSystem<'info> {
     )]
     pub pool_account: Account<'info, FeePool>,
     #[account(
-        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[callback_accounts("reveal_winner_with_threshold")]
+#[derive(Accounts)]
+pub struct RevealWinnerWithThresholdCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER_WITH_THRESHOLD)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(mut)]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_winner_with_threshold", payer)]
+#[derive(Accounts)]
+pub struct InitRevealWinnerWithThresholdCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResetSignPda<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeMint<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetRevealAuthorities<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    #[account(
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
+        bump = reveal_approval.bump
+    )]
+    pub reveal_approval: Option<Account<'info, RevealApprovalAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveReveal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
+    pub system_acc: Account<'info, ProposalSystemAccount>,
+    #[account(
+        seeds = [b"round_metadata", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = round_metadata.bump
     )]
-    pub clock_account: Account<'info, ClockAccount>,
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
+    pub round_metadata: Account<'info, RoundMetadataAccount>,
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
-        space = 8 + ProposalSystemAccount::INIT_SPACE,
-        seeds = [b"proposal_system"],
+        space = 8 + RevealApprovalAccount::INIT_SPACE,
+        seeds = [b"reveal_approval", system_acc.key().as_ref(), round_metadata.current_round.to_le_bytes().as_ref()],
         bump,
     )]
+    pub reveal_approval: Account<'info, RevealApprovalAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRateLimit<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
+    )]
     pub system_acc: Account<'info, ProposalSystemAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetAuthorityCanVote<'info> {
+    pub payer: Signer<'info>,
     #[account(
-        init,
-        payer = payer,
-        space = 8 + RoundMetadataAccount::INIT_SPACE,
-        seeds = [b"round_metadata"],
-        bump,
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
     )]
-    pub round_metadata: Account<'info, RoundMetadataAccount>,
+    pub system_acc: Account<'info, ProposalSystemAccount>,
 }
 
-#[callback_accounts("init_proposal_votes")]
 #[derive(Accounts)]
-pub struct InitProposalVotesCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
+pub struct SetThresholdPct<'info> {
+    pub payer: Signer<'info>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PROPOSAL_VOTES)
+        mut,
+        seeds = [b"proposal_system", system_acc.system_id.to_le_bytes().as_ref()],
+        bump = system_acc.bump
     )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar, checked by the account constraint
-    pub instructions_sysvar: AccountInfo<'info>,
-    /// CHECK: system_acc, checked by the callback account key passed in queue_computation
-    #[account(mut)]
     pub system_acc: Account<'info, ProposalSystemAccount>,
-    
 }
 
-#[init_computation_definition_accounts("init_proposal_votes", payer)]
-#[derive(Accounts)]
-pub struct InitProposalVotesCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    /// Can't check it here as it's not initialized yet.
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Invalid authority")]
+    InvalidAuthority,
+    #[msg("The computation was aborted")]
+    AbortedComputation,
+    #[msg("Cluster not set")]
+    ClusterNotSet,
+    #[msg("Maximum number of proposals reached")]
+    MaxProposalsReached,
+    #[msg("Invalid proposal ID")]
+    InvalidProposalId,
+    #[msg("Voter has already voted - only one vote per voter allowed")]
+    AlreadyVoted,
+    #[msg("Account is already initialized")]
+    AccountAlreadyInitialized,
+    #[msg("Invalid round ID - can only vote in current active round")]
+    InvalidRoundId,
+    #[msg("No winner has been revealed yet")]
+    NoWinnerRevealed,
+    #[msg("Invalid vote receipt - must provide actual vote receipt account")]
+    InvalidVoteReceipt,
+    #[msg("Vote does not match stored vote in receipt")]
+    VoteMismatch,
+    #[msg("Payer has insufficient funds to cover the required fee or account rent")]
+    InsufficientFunds,
+    #[msg("Round escrow is not in active status")]
+    RoundEscrowNotActive,
+    #[msg("Invalid escrow round ID")]
+    InvalidEscrowRoundId,
+    #[msg("Current round still has pending proposals")]
+    RoundStillActive,
+    #[msg("Round escrow is not in completed status")]
+    RoundEscrowNotCompleted,
+    #[msg("Recipient does not match the expected account")]
+    InvalidRecipient,
+    #[msg("Round escrow does not hold enough lamports")]
+    InsufficientEscrowBalance,
+    #[msg("Basis points must not exceed 10000")]
+    InvalidBasisPoints,
+    #[msg("The winning proposal cannot be refunded")]
+    CannotRefundWinner,
+    #[msg("Proposal has already been refunded")]
+    AlreadyRefunded,
+    #[msg("Submission fee is below the rent-exempt minimum for a proposal")]
+    FeeTooLow,
+    #[msg("Voting period for this round has ended")]
+    VotingClosed,
+    #[msg("Voting period for this round has not ended yet")]
+    VotingStillOpen,
+    #[msg("Voting duration must be positive and at most MAX_VOTING_DURATION")]
+    InvalidVotingDuration,
+    #[msg("The round has already been revealed")]
+    RoundAlreadyRevealed,
+    #[msg("Minimum voter quorum has not been reached")]
+    QuorumNotMet,
+    #[msg("This system uses token-weighted voting")]
+    WeightedVotingRequired,
+    #[msg("Token-weighted voting is not enabled for this system")]
+    WeightedVotingDisabled,
+    #[msg("Token account does not hold the governance token for this voter")]
+    InvalidGovernanceTokenAccount,
+    #[msg("Voter has no voting power")]
+    NoVotingPower,
+    #[msg("Voter is not on the allowlist")]
+    VoterNotAllowlisted,
+    #[msg("Voter is already on the allowlist")]
+    VoterAlreadyAllowlisted,
+    #[msg("Allowlist is full")]
+    AllowlistFull,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("The proposal system is paused")]
+    SystemPaused,
+    #[msg("Voting has already started in this round")]
+    VotingAlreadyStarted,
+    #[msg("Only the most recently submitted proposal can be withdrawn")]
+    OnlyLatestProposalWithdrawable,
+    #[msg("Proposal title must not be empty")]
+    EmptyTitle,
+    #[msg("Proposal field exceeds its maximum length")]
+    FieldTooLong,
+    #[msg("Proposal description must not be empty")]
+    EmptyDescription,
+    #[msg("Reward has already been claimed for this vote")]
+    RewardAlreadyClaimed,
+    #[msg("Serialized account data does not fit in the account")]
+    SerializationOverflow,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("No tallies have been revealed yet")]
+    NoTalliesRevealed,
+    #[msg("Vote count does not match the revealed tally")]
+    RevealedCountMismatch,
+    #[msg("This system uses approval voting; cast an approval ballot instead")]
+    ApprovalVotingEnabled,
+    #[msg("Approval voting is not enabled for this system")]
+    ApprovalVotingDisabled,
+    #[msg("The ranked ballot box for this round is full")]
+    RankedBallotBoxFull,
+    #[msg("No ranked ballots have been cast in this round")]
+    NoRankedBallots,
+    #[msg("Round was not cancelled")]
+    RoundNotCancelled,
+    #[msg("Total round extension may not exceed the voting duration")]
+    ExtensionTooLong,
+    #[msg("No votes have been cast in this round")]
+    NoVotesCast,
+    #[msg("Batch must contain between 1 and 4 votes, each with a voter and receipt account")]
+    InvalidBatchSize,
+    #[msg("A voter may only appear once in a batch")]
+    DuplicateBatchVoter,
+    #[msg("No round has been completed yet")]
+    NoCompletedRound,
+    #[msg("Net voting is enabled for this system")]
+    NetVotingEnabled,
+    #[msg("Net voting is not enabled for this system")]
+    NetVotingDisabled,
+    #[msg("A voter cannot delegate to themselves")]
+    InvalidDelegate,
+    #[msg("Delegation account is invalid or does not delegate to the caller")]
+    InvalidDelegation,
+    #[msg("Proposal has been slashed")]
+    ProposalSlashed,
+    #[msg("Proposals must be committed and revealed while a commit phase is configured")]
+    CommitPhaseActive,
+    #[msg("No commit phase is configured for this round")]
+    CommitPhaseInactive,
+    #[msg("The commit phase has ended")]
+    CommitPhaseClosed,
+    #[msg("Proposals can only be revealed after the commit phase ends")]
+    CommitPhaseOpen,
+    #[msg("Revealed proposal does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("Proposals have already been submitted this round")]
+    ProposalsAlreadySubmitted,
+    #[msg("Proposal limit must be between 1 and the number of encrypted counters")]
+    InvalidProposalLimit,
+    #[msg("Another proposal in this round already uses this URL")]
+    DuplicateProposalUrl,
+    #[msg("Round escrow is not closed")]
+    RoundEscrowNotClosed,
+    #[msg("The emergency withdrawal grace period has not elapsed")]
+    GracePeriodNotElapsed,
+    #[msg("Supermajority threshold must be between 1 and 100 percent")]
+    InvalidThreshold,
+    #[msg("Round fees were paid in a different currency")]
+    FeeMintMismatch,
+    #[msg("Token fee accounts are required when fees are paid in a token")]
+    MissingTokenAccounts,
+    #[msg("Fee token account has the wrong mint or owner")]
+    InvalidFeeTokenAccount,
+    #[msg("Round escrow holds token fees, which cannot be paid out in SOL")]
+    TokenFeeEscrow,
+    #[msg("No proposal in the current round has this category")]
+    EmptyCategory,
+    #[msg("The system authority is not allowed to vote")]
+    AuthorityCannotVote,
+    #[msg("Too many reveal co-authorities")]
+    TooManyRevealAuthorities,
+    #[msg("Reveal co-authorities must be distinct")]
+    DuplicateRevealAuthority,
+    #[msg("Reveal threshold exceeds the number of co-authorities")]
+    InvalidRevealThreshold,
+    #[msg("Not enough co-authorities have approved this reveal")]
+    RevealNotApproved,
+    #[msg("This co-authority already approved the reveal")]
+    RevealAlreadyApproved,
+    #[msg("Vote ciphertext is empty; was the vote encrypted?")]
+    EmptyCiphertext,
+    #[msg("Voting again too soon; wait for the cooldown to pass")]
+    VoteRateLimited,
+    #[msg("Not enough proposals in the round to reveal a winner")]
+    NotEnoughProposals,
+    #[msg("Proposal has not been seconded enough times to receive votes")]
+    ProposalNotSeconded,
+    #[msg("Submitters cannot second their own proposal")]
+    CannotSecondOwnProposal,
+    #[msg("The round's reveal did not end in a tie")]
+    RoundNotTied,
+    #[msg("Runoff proposals must be 2 or more distinct tied proposals")]
+    InvalidRunoffProposals,
+    #[msg("Pending vote has expired; prepare it again")]
+    PendingVoteExpired,
+    #[msg("Pending vote can still be confirmed")]
+    PendingVoteNotExpired,
+    #[msg("The new round nonce must differ from the current nonce")]
+    NonceNotRotated,
+    #[msg("Round escrow did not receive exactly the submission fee")]
+    EscrowTransferMismatch,
+    #[msg("The system has run all of its rounds")]
+    SystemFinished,
+    #[msg("Maximum rounds must exceed the rounds already completed")]
+    InvalidMaxRounds,
+    #[msg("Weight proof does not match the voter snapshot")]
+    InvalidWeightProof,
+    #[msg("A winner reveal is already queued for this round")]
+    RevealAlreadyInProgress,
+    #[msg("A proposal can list at most 4 co-submitters")]
+    TooManyCoSubmitters,
+    #[msg("Co-submitters must be distinct and differ from the submitter")]
+    DuplicateCoSubmitter,
+    #[msg("Another proposal in this round already uses this title")]
+    DuplicateProposalTitle,
+    #[msg("Vote set mask must select at least one valid proposal slot")]
+    InvalidVoteSetMask,
+    #[msg("This key has been blacklisted by the authority")]
+    VoterBlacklisted,
+    #[msg("Vote receipt has no outstanding tally to reissue")]
+    VoteNotAwaitingTally,
+    #[msg("Vote's tally computation has not been reported as aborted")]
+    VoteReissueTooEarly,
+    #[msg("Submission window must be non-negative and end before voting does")]
+    InvalidSubmissionWindow,
+    #[msg("The round's submission window has closed")]
+    SubmissionClosed,
+    #[msg("Voting opens once the round's submission window closes")]
+    VotingNotOpen,
+    #[msg("Vote encryption key does not match the one recorded in the vote receipt")]
+    VoteEncryptionKeyMismatch,
+    #[msg("A round may only appear once in a batch verification")]
+    DuplicateBatchRound,
+    #[msg("Distribution delay cannot be negative")]
+    InvalidDistributionDelay,
+    #[msg("The round's escrow is still in its dispute window")]
+    DistributionLocked,
+    #[msg("Vote nonce does not match the nonce stored in the receipt")]
+    VoteNonceMismatch,
+    #[msg("Reward registration for this round has closed")]
+    RewardRegistrationClosed,
+    #[msg("Reward registration for this round is still open")]
+    RewardRegistrationOpen,
+    #[msg("This vote was not registered for the round's voter rewards")]
+    RewardNotRegistered,
+    #[msg("No winner reveal is queued")]
+    NoRevealInProgress,
+    #[msg("The queued reveal may still complete; wait before clearing it")]
+    RevealRecoveryTooEarly,
+    #[msg("Reveal deadline must be set with the commit deadline and come after it")]
+    InvalidRevealDeadline,
+    #[msg("The proposal reveal phase has closed")]
+    RevealPhaseClosed,
+    #[msg("Supermajority systems must be revealed or finalized with their _with_threshold instruction")]
+    ThresholdRevealRequired,
+    #[msg("Weighted votes need a voter snapshot; set one with set_voter_snapshot")]
+    VoterSnapshotRequired,
+    #[msg("Vote's tally or a change to it is outstanding or aborted; it cannot change yet")]
+    VoteTallyOutstanding,
+    #[msg("Delegated votes cannot be changed")]
+    DelegatedVoteLocked,
+    #[msg("Blacklist account is not the voter's blacklist PDA")]
+    InvalidBlacklistAccount,
+    #[msg("A past round's escrow still holds fees; pay them out before closing the system")]
+    EscrowNotEmpty,
+    #[msg("The encrypted tally is still being reset for the new round")]
+    TallyResetPending,
+    #[msg("No aborted tally reset to retry")]
+    TallyResetNotFailed,
+}
+
+#[event]
+pub struct VoteEvent {
+    pub timestamp: i64,
+    /// Whether the vote was counted (false if it targeted an out-of-range proposal)
+    pub accepted: bool,
+    /// Whether the vote was dropped because the proposal hit `max_votes_per_proposal`
+    pub rejected: bool,
+    /// Round the vote was cast in
+    pub round_id: u64,
+    /// Voters recorded in the round so far, including this one
+    pub total_voters: u64,
+    /// Voter who cast the ballot (the chosen proposal stays encrypted)
+    pub voter: Pubkey,
+}
+
+#[event]
+pub struct ProposalSubmittedEvent {
+    pub proposal_id: u8,
+    pub round_id: u64,
+    pub submitter: Pubkey,
+    pub category: u8,
+    pub content_hash: [u8; 32],
+    pub co_submitters: Vec<Pubkey>,
+}
+
+#[event]
+pub struct WinningProposalEvent {
+    pub winning_proposal_id: u8,
+    pub winning_vote_count: u64,
+    pub round_id: u64,
+    pub is_tie: bool,
+    /// Votes separating the winner from the runner-up (0 on a tie)
+    pub margin: u64,
+    /// Winner's net score; equals `winning_vote_count` unless net voting is enabled
+    pub net_score: i64,
+    /// Whether every proposal finished with a negative net score
+    pub all_negative: bool,
+}
+
+#[event]
+pub struct VoteReceiptCreatedEvent {
+    pub voter: Pubkey,
+    pub proposal_id: u8,
+    pub encrypted_proposal_id: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteDecryptedEvent {
+    pub decrypted_proposal_id: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteVerificationEvent {
+    /// Whether the vote matched the checked proposal
+    pub is_winning_vote: bool,
+    pub timestamp: i64,
+    /// Proposal checked by `verify_vote_for_proposal` (None when checked against the winner)
+    pub target_proposal_id: Option<u8>,
+    /// Whether `verify_not_winning_vote` proved the vote was for a non-winning proposal
+    pub dissent: bool,
+}
+
+#[event]
+pub struct SystemClosedEvent {
+    pub authority: Pubkey,
+    pub total_rounds: u64,
+}
+
+#[event]
+pub struct EscrowDistributedEvent {
+    pub round_id: u64,
+    pub winning_proposal_id: u8,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ProposalRefundedEvent {
+    pub round_id: u64,
+    pub proposal_id: u8,
+    pub submitter: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeeUpdatedEvent {
+    pub old_fee: u64,
+    pub new_fee: u64,
+}
+
+#[event]
+pub struct VotingPeriodEndedEvent {
+    pub round_id: u64,
+    pub voting_deadline: i64,
+}
+
+#[event]
+pub struct TopProposalsEvent {
+    pub round_id: u64,
+    /// Proposal IDs ordered by vote count; unused slots hold `u8::MAX`
+    pub proposal_ids: [u8; MAX_PROPOSALS],
+    /// Vote counts matching `proposal_ids`; unused slots hold 0
+    pub vote_counts: [i64; MAX_PROPOSALS],
+}
+
+#[event]
+pub struct VoteChangedEvent {
+    pub voter: Pubkey,
+    pub round_id: u64,
+    pub encrypted_proposal_id: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuorumStatusEvent {
+    pub round_id: u64,
+    pub total_voters: u64,
+    pub min_quorum: u64,
+}
+
+#[event]
+pub struct VoterAllowlistedEvent {
+    pub voter: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferredEvent {
+    pub old: Pubkey,
+    pub new: Pubkey,
+}
+
+#[event]
+pub struct PauseStateChangedEvent {
+    pub paused: bool,
+}
+
+#[event]
+pub struct ProposalWithdrawnEvent {
+    pub proposal_id: u8,
+    pub round_id: u64,
+    pub submitter: Pubkey,
+    pub refund_amount: u64,
+}
+
+#[event]
+pub struct ProposalEditedEvent {
+    pub proposal_id: u8,
+    pub round_id: u64,
+    pub submitter: Pubkey,
+}
+
+#[event]
+pub struct WinningVoterRegisteredEvent {
+    pub voter: Pubkey,
+    pub round_id: u64,
+    /// Voters registered for the round's pool so far
+    pub winning_voters: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteRewardClaimedEvent {
+    pub voter: Pubkey,
+    pub round_id: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TotalVotesRevealedEvent {
+    pub round_id: u64,
+    pub total_votes: i64,
+    pub total_voters: u64,
+}
+
+#[event]
+pub struct AllTalliesRevealedEvent {
+    pub counts: [i64; MAX_PROPOSALS],
+    pub round_id: u64,
+}
+
+#[event]
+pub struct ProposalCountSyncedEvent {
+    pub proposal_id: u8,
+    pub round_id: u64,
+    pub vote_count: u64,
+}
+
+#[event]
+pub struct VotingModeChangedEvent {
+    pub approval_voting: bool,
+    pub net_voting: bool,
+    pub round_id: u64,
+}
+
+#[event]
+pub struct ApprovalReceiptCreatedEvent {
+    pub voter: Pubkey,
+    pub round_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RankedVoteCastEvent {
+    pub voter: Pubkey,
+    pub round_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RankedWinnerEvent {
+    pub round_id: u64,
+    pub winning_proposal_id: u8,
+    pub elimination_rounds: u8,
+}
+
+#[event]
+pub struct RoundCancelledEvent {
+    pub round_id: u64,
+    pub reason_code: u8,
+}
+
+#[event]
+pub struct StuckRevealClearedEvent {
+    pub queued_at: i64,
+    pub cleared_at: i64,
+}
+
+#[event]
+pub struct RoundExtendedEvent {
+    pub round_id: u64,
+    pub new_deadline: i64,
+}
+
+#[event]
+pub struct NoWinnerEvent {
+    pub round_id: u64,
+}
+
+#[event]
+pub struct VoteCapUpdatedEvent {
+    pub max_votes_per_proposal: Option<u64>,
+}
+
+#[event]
+pub struct RoundNonceRotatedEvent {
+    pub round_id: u64,
+    pub nonce: u128,
+}
+
+/// Emitted when a voter closes their receipt. The receipt is gone for good, so the
+/// vote can no longer be proven or used to claim a reward.
+#[event]
+pub struct VoteReceiptClosedEvent {
+    pub voter: Pubkey,
+    pub round_id: u64,
+    pub rent_recovered: u64,
+}
+
+#[event]
+pub struct BatchVoteEvent {
+    pub timestamp: i64,
+    pub round_id: u64,
+    pub total_voters: u64,
+    /// Per-slot flags matching `VoteEvent::accepted` (slots past the batch are false)
+    pub accepted: [bool; 4],
+    /// Per-slot flags matching `VoteEvent::rejected`
+    pub rejected: [bool; 4],
+}
+
+#[event]
+pub struct DelegateSetEvent {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct SubmitProposal<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [b"proposal_system"],
-        bump = system_acc.bump
-    )]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
-    #[account(
-        mut,
-        seeds = [b"round_metadata"],
-        bump = round_metadata.bump
-    )]
-    pub round_metadata: Account<'info, RoundMetadataAccount>,
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + ProposalAccount::INIT_SPACE,
-        seeds = [
-            b"proposal", 
-            system_acc.key().as_ref(), 
-            round_metadata.current_round.to_le_bytes().as_ref(),
-            round_metadata.proposals_in_current_round.to_le_bytes().as_ref()
-        ],
-        bump,
-    )]
-    pub proposal_acc: Account<'info, ProposalAccount>,
-    #[account(
-        init_if_needed,
-        payer = payer,
-        space = 8 + RoundEscrowAccount::INIT_SPACE,
-        seeds = [b"round_escrow", round_metadata.current_round.to_le_bytes().as_ref()],
-        bump,
-    )]
-    pub round_escrow: Account<'info, RoundEscrowAccount>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct DelegateRevokedEvent {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
 }
 
-#[queue_computation_accounts("vote_for_proposal", payer)]
-#[derive(Accounts)]
-#[instruction(computation_offset: u64, proposal_id: u8)]
-pub struct VoteForProposal<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
-    )]
-    pub sign_pda_account: Account<'info, SignerAccount>,
-    #[account(
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Account<'info, MXEAccount>,
-    #[account(
-        mut,
-        address = derive_mempool_pda!()
-    )]
-    /// CHECK: mempool_account, checked by the arcium program
-    pub mempool_account: UncheckedAccount<'info>,
-    #[account(
-        mut,
-        address = derive_execpool_pda!()
-    )]
-    /// CHECK: executing_pool, checked by the arcium program
-    pub executing_pool: UncheckedAccount<'info>,
-    #[account(
-        mut,
-        address = derive_comp_pda!(computation_offset)
-    )]
-    /// CHECK: computation_account, checked by the arcium program.
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_FOR_PROPOSAL)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(
-        mut,
-        address = derive_cluster_pda!(mxe_account)
-    )]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(
-        mut,
-        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
-    )]
-    pub pool_account: Account<'info, FeePool>,
-    #[account(
-        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
-    )]
-    pub clock_account: Account<'info, ClockAccount>,
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        mut,
-        seeds = [b"proposal_system"],
-        bump = system_acc.bump
-    )]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
-    /// CHECK: Manually verified vote_receipt PDA
-    #[account(mut)]
-    pub vote_receipt: UncheckedAccount<'info>,
-    /// CHECK: Manually verified round_metadata PDA
-    #[account(
-        mut,
-        seeds = [b"round_metadata"],
-        bump = round_metadata.bump
-    )]
-    pub round_metadata: Account<'info, RoundMetadataAccount>,
+#[event]
+pub struct DelegatedVoteEvent {
+    pub delegate: Pubkey,
+    /// Delegations folded into the vote
+    pub delegations: u64,
+    /// Weight the vote was cast with (the delegate plus each delegator)
+    pub weight: u64,
+    pub round_id: u64,
 }
 
-#[callback_accounts("vote_for_proposal")]
-#[derive(Accounts)]
-pub struct VoteForProposalCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_FOR_PROPOSAL)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar, checked by the account constraint
-    pub instructions_sysvar: AccountInfo<'info>,
-    #[account(mut)]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
+#[event]
+pub struct RoundStatusEvent {
+    pub current_round: u64,
+    pub proposals_in_current_round: u8,
+    pub total_voters: u64,
+    pub winning_proposal_id: Option<u8>,
+    /// Escrow status of the current round (None until its first proposal is submitted)
+    pub round_status: Option<RoundStatus>,
+    pub voting_deadline: i64,
 }
 
-#[init_computation_definition_accounts("vote_for_proposal", payer)]
-#[derive(Accounts)]
-pub struct InitVoteForProposalCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    /// Can't check it here as it's not initialized yet.
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct ProposalSlashedEvent {
+    pub proposal_id: u8,
+    pub round_id: u64,
 }
 
-#[queue_computation_accounts("reveal_winning_proposal", payer)]
-#[derive(Accounts)]
-#[instruction(computation_offset: u64, _system_id: u32)]
-pub struct RevealWinningProposal<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
-    )]
-    pub sign_pda_account: Account<'info, SignerAccount>,
-    #[account(
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Account<'info, MXEAccount>,
-    #[account(
-        mut,
-        address = derive_mempool_pda!()
-    )]
-    /// CHECK: mempool_account, checked by the arcium program
-    pub mempool_account: UncheckedAccount<'info>,
-    #[account(
-        mut,
-        address = derive_execpool_pda!()
-    )]
-    /// CHECK: executing_pool, checked by the arcium program
-    pub executing_pool: UncheckedAccount<'info>,
-    #[account(
-        mut,
-        address = derive_comp_pda!(computation_offset)
-    )]
-    /// CHECK: computation_account, checked by the arcium program.
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(
-        mut,
-        address = derive_cluster_pda!(mxe_account)
-    )]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(
-        mut,
-        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
-    )]
-    pub pool_account: Account<'info, FeePool>,
-    #[account(
-        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
-    )]
-    pub clock_account: Account<'info, ClockAccount>,
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        seeds = [b"proposal_system"],
-        bump = system_acc.bump
-    )]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
-    #[account(
-        mut,
-        seeds = [b"round_metadata"],
-        bump = round_metadata.bump
-    )]
-    pub round_metadata: Account<'info, RoundMetadataAccount>,
+#[event]
+pub struct CommitDeadlineSetEvent {
+    pub round_id: u64,
+    pub commit_deadline: Option<i64>,
+    pub reveal_deadline: Option<i64>,
 }
 
-#[callback_accounts("reveal_winning_proposal")]
-#[derive(Accounts)]
-pub struct RevealWinningProposalCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WINNER)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar, checked by the account constraint
-    pub instructions_sysvar: AccountInfo<'info>,
-    #[account(mut)]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
-    #[account(mut)]
-    pub round_metadata: Account<'info, RoundMetadataAccount>,
+#[event]
+pub struct ProposalCommittedEvent {
+    pub round_id: u64,
+    pub submitter: Pubkey,
+    pub commitment: [u8; 32],
+}
+
+#[event]
+pub struct ProposalLimitSetEvent {
+    /// Round in progress when the limit was changed
+    pub round_id: u64,
+    pub limit: u8,
+}
+
+#[event]
+pub struct EmergencyWithdrawEvent {
+    pub round_id: u64,
+    pub amount: u64,
 }
 
-#[derive(Accounts)]
-pub struct CreateRoundHistory<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [b"proposal_system"],
-        bump = system_acc.bump
-    )]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
-    #[account(
-        mut,
-        seeds = [b"round_metadata"],
-        bump = round_metadata.bump
-    )]
-    pub round_metadata: Account<'info, RoundMetadataAccount>,
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + VotingRoundHistoryAccount::INIT_SPACE,
-        seeds = [b"voting_round_history", system_acc.key().as_ref(), (round_metadata.current_round - 1).to_le_bytes().as_ref()],
-        bump,
-    )]
-    pub round_history: Account<'info, VotingRoundHistoryAccount>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct VotingPowerQuorumUpdatedEvent {
+    pub min_voting_power: u128,
 }
 
-#[init_computation_definition_accounts("reveal_winning_proposal", payer)]
-#[derive(Accounts)]
-pub struct InitRevealWinnerCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    /// Can't check it here as it's not initialized yet.
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct ThresholdUpdatedEvent {
+    pub threshold_pct: u8,
 }
 
-/// Represents the proposal voting system with encrypted vote tallies for all proposals.
-/// NOTE: This account is passed to MXE - DO NOT modify its structure!
-#[account]
-#[derive(InitSpace)]
-pub struct ProposalSystemAccount {
-    /// PDA bump seed
-    pub bump: u8,
-    /// Public key of the system authority (only they can reveal results)
-    pub authority: Pubkey,
-    /// Cryptographic nonce for the encrypted vote counters
-    pub nonce: u128,
-    /// Next proposal ID to be assigned
-    pub next_proposal_id: u8,
-    /// Encrypted vote counters for all proposals (up to 10) as 32-byte ciphertexts
-    pub proposal_votes: [[u8; 32]; 10],
-    /// Winning proposal ID after reveal (None = not revealed yet)
-    pub winning_proposal_id: Option<u8>,
-    /// Number of votes the winning proposal received (None = not revealed yet)
-    pub winning_vote_count: Option<u64>,
-    /// Fixed fee for proposal submission (in lamports)
-    pub proposal_submission_fee: u64,
+#[event]
+pub struct SupermajorityNotReachedEvent {
+    pub round_id: u64,
+    pub threshold_pct: u8,
 }
 
-/// Represents the escrow account for a specific voting round.
-#[account]
-#[derive(InitSpace)]
-pub struct RoundEscrowAccount {
-    /// PDA bump seed
-    pub bump: u8,
-    /// Round ID this escrow belongs to
+#[event]
+pub struct FeeMintUpdatedEvent {
     pub round_id: u64,
-    /// Total fees collected in this round
-    pub total_collected: u64,
-    /// Total distributed from this round
-    pub total_distributed: u64,
-    /// Current available balance
-    pub current_balance: u64,
-    /// Status of this round's escrow
-    pub round_status: RoundStatus,
-    /// Timestamp when this round escrow was created
-    pub created_at: i64,
+    pub fee_mint: Option<Pubkey>,
 }
 
-/// Status of a round's escrow account.
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
-pub enum RoundStatus {
-    /// Round is ongoing, collecting fees
-    Active,
-    /// Round ended, escrow available for distribution
-    Completed,
-    /// Escrow fully distributed or closed
-    Closed,
+#[event]
+pub struct CategoryWinnerEvent {
+    pub round_id: u64,
+    pub category: u8,
+    pub winning_proposal_id: u8,
+    pub vote_count: i64,
+    pub is_tie: bool,
 }
 
-impl anchor_lang::Space for RoundStatus {
-    const INIT_SPACE: usize = 1; // 1 byte for the enum discriminant
+#[event]
+pub struct AuthorityCanVoteUpdatedEvent {
+    pub enabled: bool,
 }
 
-/// Represents a single proposal submitted to the system.
-#[account]
-#[derive(InitSpace)]
-pub struct ProposalAccount {
-    /// PDA bump seed
-    pub bump: u8,
-    /// Unique identifier for this proposal within the round
-    pub id: u8,
-    /// Round ID this proposal belongs to
+#[event]
+pub struct RevealApprovedEvent {
     pub round_id: u64,
-    /// Public key of the proposal submitter
-    pub submitter: Pubkey,
-    /// Number of votes this proposal has received (public count)
-    pub vote_count: u64,
-    /// Short title of the proposal (max 50 characters)
-    #[max_len(50)]
-    pub title: String,
-    /// Detailed description of the proposal (max 200 characters)
-    #[max_len(200)]
-    pub description: String,
-    /// URL associated with the proposal (max 200 characters)
-    #[max_len(200)]
-    pub url: String,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
 }
 
-/// Represents a vote receipt for a voter.
-/// This account stores the details of a vote cast by a specific voter.
-/// The proposal ID is encrypted for ballot secrecy - only the voter (who has the nonce) or MXE can decrypt it.
-/// Note: PDA is derived from voter only - each voter can only vote ONCE total (not once per proposal).
-/// The plaintext proposal_id is NOT stored to maintain complete ballot secrecy.
-#[account]
-#[derive(InitSpace)]
-pub struct VoteReceiptAccount {
-    /// PDA bump seed
-    pub bump: u8,
-    /// Public key of the voter
-    pub voter: Pubkey,
-    /// Encrypted proposal ID - only decryptable by voter (with their nonce) or MXE
-    pub encrypted_proposal_id: [u8; 32],
-    /// Timestamp when the vote was cast
-    pub timestamp: i64,
-    /// Voter's encryption public key used for the vote
-    pub vote_encryption_pubkey: [u8; 32],
+#[event]
+pub struct RateLimitUpdatedEvent {
+    pub cooldown_slots: u64,
 }
 
-/// Represents the history of a completed voting round.
-/// This account is created after a winner is revealed and stores the results permanently.
-/// Note: Vote counts are NOT stored here - they can be calculated on the frontend from the state.
-#[account]
-#[derive(InitSpace)]
-pub struct VotingRoundHistoryAccount {
-    /// PDA bump seed
-    pub bump: u8,
-    /// Round identifier (incremented for each voting round)
+#[event]
+pub struct RateLimitedEvent {
+    pub voter: Pubkey,
     pub round_id: u64,
-    /// ID of the winning proposal
-    pub winning_proposal_id: u8,
-    /// Timestamp when the winner was revealed
-    pub revealed_at: i64,
-    /// Authority who revealed the results
-    pub revealed_by: Pubkey,
-    /// Total number of proposals in this round
-    pub total_proposals: u8,
+    pub last_vote_slot: u64,
+    pub next_allowed_slot: u64,
 }
 
-/// Metadata account for tracking round information.
-/// This is separate from ProposalSystemAccount to avoid modifying accounts passed to MXE.
-#[account]
-#[derive(InitSpace)]
-pub struct RoundMetadataAccount {
-    /// PDA bump seed
-    pub bump: u8,
-    /// Current voting round number (incremented each time a winner is revealed)
-    pub current_round: u64,
-    /// Number of proposals submitted in the current round
-    pub proposals_in_current_round: u8,
-    /// Total number of voters in the current round
-    pub total_voters: u64,
-    /// Unix timestamp when the current round started
+/// Signals the start of a new round; complements `WinningProposalEvent`, which
+/// signals the end of the previous one.
+#[event]
+pub struct RoundStartedEvent {
+    pub round_id: u64,
     pub round_started: i64,
 }
 
-#[queue_computation_accounts("decrypt_vote", payer)]
-#[derive(Accounts)]
-#[instruction(computation_offset: u64)]
-pub struct DecryptVote<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
-    )]
-    pub sign_pda_account: Account<'info, SignerAccount>,
-    #[account(
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Account<'info, MXEAccount>,
-    #[account(
-        mut,
-        address = derive_mempool_pda!()
-    )]
-    /// CHECK: mempool_account, checked by the arcium program
-    pub mempool_account: UncheckedAccount<'info>,
-    #[account(
-        mut,
-        address = derive_execpool_pda!()
-    )]
-    /// CHECK: executing_pool, checked by the arcium program
-    pub executing_pool: UncheckedAccount<'info>,
-    #[account(
-        mut,
-        address = derive_comp_pda!(computation_offset)
-    )]
-    /// CHECK: computation_account, checked by the arcium program.
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_VOTE)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(
-        mut,
-        address = derive_cluster_pda!(mxe_account)
-    )]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(
-        mut,
-        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
-    )]
-    pub pool_account: Account<'info, FeePool>,
-    #[account(
-        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
-    )]
-    pub clock_account: Account<'info, ClockAccount>,
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        seeds = [b"proposal_system"],
-        bump = system_acc.bump
-    )]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
+#[event]
+pub struct MinProposalsUpdatedEvent {
+    pub min_proposals: u8,
+}
+
+#[event]
+pub struct EscrowSplitEvent {
+    pub round_id: u64,
+    pub winner_amount: u64,
+    pub treasury_amount: u64,
+}
+
+#[event]
+pub struct ProposalSecondedEvent {
+    pub proposal_id: u8,
+    pub round_id: u64,
+    pub seconder: Pubkey,
+    pub seconds: u8,
+}
+
+#[event]
+pub struct SubmitterStatsUpdatedEvent {
+    pub submitter: Pubkey,
+    pub total_submitted: u64,
+    pub total_wins: u64,
+}
+
+#[event]
+pub struct RunoffCreatedEvent {
+    pub from_round: u64,
+    pub proposal_ids: Vec<u8>,
+}
+
+#[event]
+pub struct PendingVoteCreatedEvent {
+    pub voter: Pubkey,
+    pub round_id: u64,
+    pub expires_at_slot: u64,
 }
 
-#[callback_accounts("decrypt_vote")]
-#[derive(Accounts)]
-pub struct DecryptVoteCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_VOTE)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar, checked by the account constraint
-    pub instructions_sysvar: AccountInfo<'info>,
-    #[account(mut)]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
+#[event]
+pub struct SingleCountRevealedEvent {
+    pub proposal_id: u8,
+    pub count: i64,
+    pub round_id: u64,
 }
 
-#[init_computation_definition_accounts("decrypt_vote", payer)]
-#[derive(Accounts)]
-pub struct InitDecryptVoteCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    /// Can't check it here as it's not initialized yet.
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct VoteReceiptPdaDerivedEvent {
+    pub voter: Pubkey,
+    pub round_id: u64,
+    pub vote_receipt: Pubkey,
+    pub bump: u8,
 }
 
-#[queue_computation_accounts("verify_winning_vote", payer)]
-#[derive(Accounts)]
-#[instruction(computation_offset: u64, round_id: u64)]
-pub struct VerifyWinningVote<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
-    )]
-    pub sign_pda_account: Account<'info, SignerAccount>,
-    #[account(
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Account<'info, MXEAccount>,
-    #[account(
-        mut,
-        address = derive_mempool_pda!()
-    )]
-    /// CHECK: mempool_account, checked by the arcium program
-    pub mempool_account: UncheckedAccount<'info>,
-    #[account(
-        mut,
-        address = derive_execpool_pda!()
-    )]
-    /// CHECK: executing_pool, checked by the arcium program
-    pub executing_pool: UncheckedAccount<'info>,
-    #[account(
-        mut,
-        address = derive_comp_pda!(computation_offset)
-    )]
-    /// CHECK: computation_account, checked by the arcium program.
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_WINNING_VOTE)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(
-        mut,
-        address = derive_cluster_pda!(mxe_account)
-    )]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(
-        mut,
-        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
-    )]
-    pub pool_account: Account<'info, FeePool>,
-    #[account(
-        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
-    )]
-    pub clock_account: Account<'info, ClockAccount>,
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        seeds = [b"proposal_system"],
-        bump = system_acc.bump
-    )]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
-    #[account(
-        seeds = [b"round_metadata"],
-        bump = round_metadata.bump
-    )]
-    pub round_metadata: Account<'info, RoundMetadataAccount>,
-    /// CHECK: round_history, manually verified in the function
-    pub round_history: UncheckedAccount<'info>,
-    /// CHECK: vote_receipt, manually verified in the function
-    pub vote_receipt: UncheckedAccount<'info>,
-    /// CHECK: round_escrow, manually verified in the function
-    #[account(mut)]
-    pub round_escrow: UncheckedAccount<'info>,
+#[event]
+pub struct SystemFinishedEvent {
+    pub total_rounds: u64,
 }
 
-#[callback_accounts("verify_winning_vote")]
-#[derive(Accounts)]
-pub struct VerifyWinningVoteCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_WINNING_VOTE)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar, checked by the account constraint
-    pub instructions_sysvar: AccountInfo<'info>,
-    #[account(mut)]
-    pub system_acc: Account<'info, ProposalSystemAccount>,
+#[event]
+pub struct MaxRoundsUpdatedEvent {
+    pub max_rounds: Option<u64>,
 }
 
-#[init_computation_definition_accounts("verify_winning_vote", payer)]
-#[derive(Accounts)]
-pub struct InitVerifyWinningVoteCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    /// Can't check it here as it's not initialized yet.
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct VoterSnapshotUpdatedEvent {
+    pub root: Option<[u8; 32]>,
 }
 
+#[event]
+pub struct VoteSetVerificationEvent {
+    pub in_set: bool,
+    pub mask: u32,
+    pub timestamp: i64,
+}
 
+#[event]
+pub struct VoterBlacklistedEvent {
+    pub voter: Pubkey,
+    pub blacklisted: bool,
+}
 
+#[event]
+pub struct RevealCostEstimateEvent {
+    pub cu_amount: u64,
+    pub cu_price: u64,
+    pub computation_fee: u64,
+    pub sign_pda_rent: u64,
+    pub total: u64,
+}
 
-
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Invalid authority")]
-    InvalidAuthority,
-    #[msg("The computation was aborted")]
-    AbortedComputation,
-    #[msg("Cluster not set")]
-    ClusterNotSet,
-    #[msg("Maximum number of proposals reached")]
-    MaxProposalsReached,
-    #[msg("Invalid proposal ID")]
-    InvalidProposalId,
-    #[msg("Voter has already voted - only one vote per voter allowed")]
-    AlreadyVoted,
-    #[msg("Account is already initialized")]
-    AccountAlreadyInitialized,
-    #[msg("Invalid round ID - can only vote in current active round")]
-    InvalidRoundId,
-    #[msg("No winner has been revealed yet")]
-    NoWinnerRevealed,
-    #[msg("Invalid vote receipt - must provide actual vote receipt account")]
-    InvalidVoteReceipt,
-    #[msg("Vote does not match stored vote in receipt")]
-    VoteMismatch,
-    #[msg("Insufficient funds for proposal submission fee")]
-    InsufficientFunds,
-    #[msg("Round escrow is not in active status")]
-    RoundEscrowNotActive,
-    #[msg("Invalid escrow round ID")]
-    InvalidEscrowRoundId,
+#[event]
+pub struct ConcentrationRevealedEvent {
+    pub round_id: u64,
+    pub concentration: u64,
 }
 
 #[event]
-pub struct VoteEvent {
+pub struct VoteReissuedEvent {
+    pub voter: Pubkey,
+    pub round_id: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct ProposalSubmittedEvent {
+pub struct ProposalViewEvent {
     pub proposal_id: u8,
     pub round_id: u64,
     pub submitter: Pubkey,
+    pub title: String,
+    /// None until the round has ended and the revealed count was synced
+    pub vote_count: Option<u64>,
 }
 
 #[event]
-pub struct WinningProposalEvent {
-    pub winning_proposal_id: u8,
-    pub winning_vote_count: u64,
-    pub round_id: u64,
+pub struct BatchVerificationEvent {
+    /// Voter whose past votes were checked
+    pub voter: Pubkey,
+    /// Rounds the checked votes were cast in, in the order they were submitted
+    pub rounds: Vec<u64>,
+    pub correct_count: u8,
+    pub total: u8,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct VoteReceiptCreatedEvent {
-    pub voter: Pubkey,
-    pub proposal_id: u8,
-    pub encrypted_proposal_id: [u8; 32],
-    pub timestamp: i64,
+pub struct ComputationAbortedEvent {
+    pub instruction_name: String,
+    /// Round the computation was for, when the callback knows it
+    pub round_id: Option<u64>,
 }
 
 #[event]
-pub struct VoteDecryptedEvent {
-    pub decrypted_proposal_id: u8,
+pub struct WinnerSharedEvent {
+    pub round_id: u64,
+    pub recipient_pubkey: [u8; 32],
     pub timestamp: i64,
 }
 
 #[event]
-pub struct VoteVerificationEvent {
-    pub is_winning_vote: bool,
-    pub timestamp: i64,
+pub struct DistributionDelayUpdatedEvent {
+    pub distribution_delay: i64,
 }
 
+#[cfg(test)]
+mod mxe_layout {
+    use super::*;
+
+    fn sample_system() -> ProposalSystemAccount {
+        let mut proposal_votes = [[0u8; 32]; MAX_PROPOSALS];
+        for (i, ciphertext) in proposal_votes.iter_mut().enumerate() {
+            *ciphertext = [i as u8 + 1; 32];
+        }
+
+        ProposalSystemAccount {
+            bump: 0xfe,
+            authority: Pubkey::new_unique(),
+            nonce: u128::MAX,
+            next_proposal_id: 0xfd,
+            proposal_votes,
+            winning_proposal_id: Some(3),
+            winning_vote_count: Some(42),
+            proposal_submission_fee: 1_000_000,
+            refund_bps: 5_000,
+            voting_duration: 86_400,
+            winner_was_tie: false,
+            min_quorum: 1,
+            governance_mint: Some(Pubkey::new_unique()),
+            allowlist_enabled: false,
+            pending_authority: None,
+            paused: false,
+            voter_reward_bps: 0,
+            approval_voting: false,
+            system_id: 7,
+            winning_margin: None,
+            max_votes_per_proposal: None,
+            net_voting: false,
+            all_scores_negative: false,
+            min_voting_power: 0,
+            threshold_pct: 0,
+            supermajority_met: None,
+            fee_mint: None,
+            next_computation_offset: 1,
+            authority_can_vote: false,
+            authorities: vec![Pubkey::new_unique()],
+            reveal_threshold: 0,
+            rate_limit: RateLimitConfig::default(),
+            min_proposals: 2,
+            treasury: Pubkey::new_unique(),
+            winner_bps: 10_000,
+            required_seconds: 0,
+            max_rounds: None,
+            finished: false,
+            voter_snapshot_root: None,
+            reveal_in_progress: false,
+            submission_duration: 0,
+            distribution_delay: 0,
+            reveal_queued_at: 0,
+            winner_tied_mask: 0,
+            tally_reset_pending: false,
+            tally_reset_failed: false,
+        }
+    }
+
+    fn sample_receipt() -> VoteReceiptAccount {
+        VoteReceiptAccount {
+            bump: 0xfd,
+            voter: Pubkey::new_unique(),
+            encrypted_proposal_id: [7; 32],
+            timestamp: 1_700_000_000,
+            vote_encryption_pubkey: [9; 32],
+            reward_claimed: false,
+            awaiting_tally: true,
+            vote_nonce: 42,
+            reward_registered: false,
+            tally_computation_offset: 3,
+            tally_failed: false,
+            rent_payer: Pubkey::new_unique(),
+            change_pending: true,
+            pending_proposal_id: [5; 32],
+            pending_encryption_pubkey: [6; 32],
+            pending_nonce: 43,
+            delegated: false,
+        }
+    }
+
+    fn sample_escrow() -> RoundEscrowAccount {
+        RoundEscrowAccount {
+            bump: 0xfc,
+            round_id: 0,
+            total_collected: 0,
+            total_distributed: 0,
+            current_balance: 0,
+            round_status: RoundStatus::Completed,
+            created_at: 0,
+            cancelled: false,
+            fee_mint: None,
+            token_collected: 0,
+            token_balance: 0,
+            is_initialized: true,
+            winning_voters: 0,
+            voter_rewards_paid: 0,
+            refundable_fees: 0,
+        }
+    }
+
+    fn account_info<'a>(
+        key: &'a Pubkey,
+        owner: &'a Pubkey,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, owner, false, 0)
+    }
+
+    #[test]
+    fn vote_receipt_store_rejects_undersized_accounts() {
+        let receipt = sample_receipt();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![0u8; VoteReceiptAccount::INIT_SPACE - 1];
+        let info = account_info(&key, &crate::ID, &mut lamports, &mut data);
+
+        assert_eq!(
+            receipt.store(&info).unwrap_err(),
+            ErrorCode::SerializationOverflow.into()
+        );
+    }
+
+    #[test]
+    fn vote_receipt_store_round_trips_through_allocated_space() {
+        let receipt = sample_receipt();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![0u8; 8 + VoteReceiptAccount::INIT_SPACE];
+        let info = account_info(&key, &crate::ID, &mut lamports, &mut data);
+
+        receipt.store(&info).unwrap();
+        let loaded = VoteReceiptAccount::load(&info).unwrap();
+        assert_eq!(loaded.voter, receipt.voter);
+        assert_eq!(loaded.encrypted_proposal_id, receipt.encrypted_proposal_id);
+        assert_eq!(loaded.vote_nonce, receipt.vote_nonce);
+        assert_eq!(loaded.rent_payer, receipt.rent_payer);
+    }
+
+    #[test]
+    fn truncated_vote_receipt_is_rejected() {
+        let mut data = Vec::new();
+        sample_receipt().serialize(&mut data).unwrap();
+        data.truncate(1 + 32 + 16);
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = account_info(&key, &crate::ID, &mut lamports, &mut data);
+
+        assert_eq!(
+            VoteReceiptAccount::load(&info).unwrap_err(),
+            ErrorCode::InvalidVoteReceipt.into()
+        );
+    }
+
+    #[test]
+    fn foreign_vote_receipt_is_rejected() {
+        let mut data = Vec::new();
+        sample_receipt().serialize(&mut data).unwrap();
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = account_info(&key, &owner, &mut lamports, &mut data);
+
+        assert_eq!(
+            VoteReceiptAccount::load(&info).unwrap_err(),
+            ErrorCode::InvalidVoteReceipt.into()
+        );
+    }
+
+    #[test]
+    fn truncated_round_history_is_rejected() {
+        // Only the discriminator and part of the round id, then less than a discriminator
+        let mut header_only = VotingRoundHistoryAccount::DISCRIMINATOR.to_vec();
+        header_only.extend_from_slice(&[0; 4]);
+        for mut data in [header_only, vec![0u8; 3]] {
+            let key = Pubkey::new_unique();
+            let mut lamports = 0;
+            let info = account_info(&key, &crate::ID, &mut lamports, &mut data);
+            assert_eq!(
+                load_round_history(&info).unwrap_err(),
+                ErrorCode::InvalidRoundId.into()
+            );
+        }
+    }
+
+    #[test]
+    fn escrow_payout_overflowing_total_distributed_is_rejected() {
+        let mut escrow = sample_escrow();
+        escrow.current_balance = 10;
+        escrow.total_distributed = u64::MAX - 9;
+
+        assert_eq!(
+            escrow.clone().record_payout(10).unwrap_err(),
+            ErrorCode::MathOverflow.into()
+        );
+
+        escrow.record_payout(9).unwrap();
+        assert_eq!(escrow.total_distributed, u64::MAX);
+        assert_eq!(escrow.current_balance, 1);
+    }
+
+    #[test]
+    fn escrow_shares_do_not_overflow_at_u64_max() {
+        let mut escrow = sample_escrow();
+        escrow.total_collected = u64::MAX;
+        escrow.refundable_fees = u64::MAX;
+
+        assert_eq!(escrow.voter_reward_pool(10_000), u64::MAX);
+        assert_eq!(escrow.voter_reward_pool(5_000), u64::MAX / 2);
+        assert_eq!(escrow.unclaimed_refunds(10_000, 0), u64::MAX);
+        assert_eq!(escrow.unclaimed_refunds(10_000, u64::MAX), 0);
+    }
+
+    #[test]
+    fn proposal_votes_prefix_matches_account_layout() {
+        let system = sample_system();
+        let mut data = Vec::new();
+        system.try_serialize(&mut data).unwrap();
+
+        let prefix = ProposalVotesPrefix {
+            bump: system.bump,
+            authority: system.authority,
+            nonce: system.nonce,
+            next_proposal_id: system.next_proposal_id,
+        };
+        let mut prefix_data = Vec::new();
+        prefix.serialize(&mut prefix_data).unwrap();
+        assert_eq!(prefix_data.len(), ProposalVotesPrefix::INIT_SPACE);
+        assert_eq!(data[8..PROPOSAL_VOTES_OFFSET as usize], prefix_data[..]);
+        assert_eq!(PROPOSAL_VOTES_LEN as usize, 32 * MAX_PROPOSALS);
+    }
+
+    #[test]
+    fn proposal_votes_are_serialized_at_offset() {
+        let system = sample_system();
+        let mut data = Vec::new();
+        system.try_serialize(&mut data).unwrap();
+
+        let start = PROPOSAL_VOTES_OFFSET as usize;
+        let end = start + PROPOSAL_VOTES_LEN as usize;
+        assert!(end <= data.len());
+        assert_eq!(data[start..end], system.proposal_votes.concat()[..]);
+
+        // The fields around the window must not bleed into it
+        assert_eq!(data[start - 1], system.next_proposal_id);
+        assert_eq!(data[end..end + 2], [1, system.winning_proposal_id.unwrap()]);
+    }
+
+    #[test]
+    fn every_counter_slot_is_addressed_by_the_offset_math() {
+        let system = sample_system();
+        let mut data = Vec::new();
+        system.try_serialize(&mut data).unwrap();
+
+        // Counter i must be the i-th 32-byte chunk of the window, for whichever
+        // MAX_PROPOSALS the crate was built with
+        for (i, ciphertext) in system.proposal_votes.iter().enumerate() {
+            let start = PROPOSAL_VOTES_OFFSET as usize + 32 * i;
+            assert_eq!(data[start..start + 32], ciphertext[..]);
+        }
+        assert_eq!(
+            PROPOSAL_VOTES_LEN as usize,
+            std::mem::size_of::<[[u8; 32]; MAX_PROPOSALS]>()
+        );
+        // Seconding and tie masks carry one bit per proposal in a u32
+        assert!(MAX_PROPOSALS <= u32::BITS as usize);
+    }
 
+    #[test]
+    fn ranked_ballots_are_serialized_at_offset() {
+        let mut encrypted_ballots = [[0u8; 32]; RANKED_BALLOT_CIPHERTEXTS];
+        for (i, ciphertext) in encrypted_ballots.iter_mut().enumerate() {
+            *ciphertext = [i as u8 + 1; 32];
+        }
+        let ranked_votes = RankedVoteAccount {
+            bump: 0xfe,
+            round_id: 7,
+            ballot_count: 3,
+            nonce: u128::MAX,
+            encrypted_ballots,
+        };
+        let mut data = Vec::new();
+        ranked_votes.try_serialize(&mut data).unwrap();
+
+        let prefix = RankedBallotsPrefix {
+            bump: ranked_votes.bump,
+            round_id: ranked_votes.round_id,
+            ballot_count: ranked_votes.ballot_count,
+            nonce: ranked_votes.nonce,
+        };
+        let mut prefix_data = Vec::new();
+        prefix.serialize(&mut prefix_data).unwrap();
+        assert_eq!(prefix_data.len(), RankedBallotsPrefix::INIT_SPACE);
+        assert_eq!(data[8..RANKED_BALLOTS_OFFSET as usize], prefix_data[..]);
+
+        let start = RANKED_BALLOTS_OFFSET as usize;
+        let end = start + 32 * RANKED_BALLOT_CIPHERTEXTS;
+        assert_eq!(end, 8 + RankedVoteAccount::INIT_SPACE);
+        assert_eq!(
+            data[start..end],
+            ranked_votes.encrypted_ballots.concat()[..]
+        );
+    }
 
+    #[test]
+    fn serialized_account_fits_allocated_space() {
+        let mut data = Vec::new();
+        sample_system().try_serialize(&mut data).unwrap();
+        assert!(data.len() <= 8 + ProposalSystemAccount::INIT_SPACE);
+    }
+}